@@ -1,5 +1,5 @@
 use criterion::{Criterion, criterion_group, criterion_main};
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
 use tempfile::tempdir;
@@ -8,7 +8,9 @@ use docgraph::core::collect::collect_workspace_all;
 use docgraph::core::config::Config;
 use docgraph::core::engine::execute_query;
 use docgraph::core::lint::check_workspace;
+use docgraph::core::parse::parse_document;
 use docgraph::core::parser::parse_query;
+use docgraph::core::types::{RefUse, SpecBlock};
 
 /// Generate test workspace with specified number of files and nodes
 fn generate_test_workspace(dir: &Path, num_files: usize, nodes_per_file: usize) {
@@ -75,6 +77,68 @@ fn bench_collect_1000_nodes(c: &mut Criterion) {
     });
 }
 
+/// Baseline for the parallel collection: walk and parse one file after the other
+fn collect_sequential(root: &Path) -> (Vec<SpecBlock>, Vec<RefUse>) {
+    let mut blocks = Vec::new();
+    let mut refs = Vec::new();
+    for entry in ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .build()
+        .flatten()
+    {
+        let path = entry.path();
+        if path.is_file()
+            && path.extension().is_some_and(|ext| ext == "md")
+            && let Ok(content) = fs::read_to_string(path)
+        {
+            let document = parse_document(&content, path);
+            blocks.extend(document.blocks);
+            refs.extend(document.refs);
+        }
+    }
+    (blocks, refs)
+}
+
+fn bench_collect_10k_nodes(c: &mut Criterion) {
+    let dir = tempdir().expect("Failed to create temp dir");
+    // 1000 files × 10 nodes = 10000 nodes
+    generate_test_workspace(dir.path(), 1000, 10);
+
+    let mut group = c.benchmark_group("collect_10000_nodes_1000_files");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            let (blocks, _refs) = collect_sequential(dir.path());
+            assert_eq!(blocks.len(), 10000);
+        })
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            let (blocks, _refs) = collect_workspace_all(dir.path(), &[], None);
+            assert_eq!(blocks.len(), 10000);
+        })
+    });
+    group.finish();
+}
+
+fn bench_lint_10k_nodes(c: &mut Criterion) {
+    let dir = tempdir().expect("Failed to create temp dir");
+    // 1000 files × 10 nodes = 10000 nodes
+    generate_test_workspace(dir.path(), 1000, 10);
+
+    let config = Config::load(dir.path()).expect("Failed to load config");
+
+    let mut group = c.benchmark_group("lint_10000_nodes_1000_files");
+    group.sample_size(10);
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            let diagnostics = check_workspace(dir.path(), None, true, &config, None);
+            assert!(!diagnostics.is_empty());
+        })
+    });
+    group.finish();
+}
+
 fn bench_lint_1000_nodes(c: &mut Criterion) {
     let dir = tempdir().expect("Failed to create temp dir");
     // 100 files × 10 nodes = 1000 nodes
@@ -84,7 +148,7 @@ fn bench_lint_1000_nodes(c: &mut Criterion) {
 
     c.bench_function("lint_1000_nodes_100_files", |b| {
         b.iter(|| {
-            let diagnostics = check_workspace(dir.path(), false, None, true, &config, None);
+            let diagnostics = check_workspace(dir.path(), None, true, &config, None);
             // Just run the check, verify diagnostic count to ensure rules are running
             assert!(!diagnostics.is_empty());
         })
@@ -155,7 +219,9 @@ fn bench_query_relationship(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_collect_1000_nodes,
+    bench_collect_10k_nodes,
     bench_lint_1000_nodes,
+    bench_lint_10k_nodes,
     bench_query_match_label,
     bench_query_where_filter,
     bench_query_relationship
@@ -17,17 +17,29 @@ pub enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
-        /// Output diagnostics in JSON format
+        /// Output diagnostics in JSON format (shorthand for --format json)
         #[arg(long)]
         json: bool,
 
+        /// Output format for diagnostics
+        #[arg(long, value_enum, default_value_t = CheckFormat::Text, conflicts_with = "json")]
+        format: CheckFormat,
+
         /// Automatically fix fixable issues
         #[arg(long)]
         fix: bool,
 
+        /// Show the fixes as a diff without writing them (requires --fix)
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+
         /// Run only specific rules (can be specified multiple times)
         #[arg(long)]
         rule: Option<Vec<String>>,
+
+        /// Check again whenever a Markdown file, docgraph.toml or a template changes
+        #[arg(long, conflicts_with = "fix")]
+        watch: bool,
     },
     /// Format the documentation (fix fixable issues)
     Fmt {
@@ -35,6 +47,10 @@ pub enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Show the fixes as a diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+
         /// Run only specific rules (can be specified multiple times)
         #[arg(long)]
         rule: Option<Vec<String>>,
@@ -50,6 +66,63 @@ pub enum Commands {
         /// Path to search for markdown files (defaults to current directory)
         #[arg(default_value = ".")]
         path: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = GraphFormat::Json)]
+        format: GraphFormat,
+
+        /// Export only the neighborhood of this node ID
+        #[arg(long)]
+        root: Option<String>,
+
+        /// Maximum number of hops from --root (defaults to 1)
+        #[arg(long, requires = "root")]
+        depth: Option<usize>,
+    },
+    /// Build a traceability matrix between two node types
+    Matrix {
+        /// Node type listed as rows (e.g. UC)
+        #[arg(long)]
+        rows: String,
+
+        /// Node type listed as columns (e.g. FR)
+        #[arg(long)]
+        cols: String,
+
+        /// Intermediate node types linking rows to columns, in order (e.g. --via FR,MOD)
+        #[arg(long, value_delimiter = ',')]
+        via: Vec<String>,
+
+        /// Follow only these relationships (can be specified multiple times)
+        #[arg(long)]
+        rel: Option<Vec<String>>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = MatrixFormat::Markdown)]
+        format: MatrixFormat,
+
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Show coverage and metrics of the documentation graph
+    Stats {
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = StatsFormat::Text)]
+        format: StatsFormat,
+    },
+    /// Generate a static HTML site for the documentation graph
+    Site {
+        /// Directory to write the site to
+        out_dir: PathBuf,
+
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
     },
     /// Describe a spec block and its relationships
     Describe {
@@ -60,6 +133,97 @@ pub enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
     },
+    /// Create a node from the template of its type
+    New {
+        /// Node type of the new node (e.g. FR)
+        node_type: String,
+
+        /// ID of the new node (defaults to the next free ID of the type)
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Title of the new node (defaults to one derived from the ID)
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Markdown file to append the node to (created if missing)
+        #[arg(long)]
+        file: PathBuf,
+
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Show the changes as a diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rename a node ID and update its anchor, heading and every link to it
+    Mv {
+        /// Current ID of the node
+        old_id: String,
+
+        /// New ID of the node
+        new_id: String,
+
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Show the changes as a diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move a node to another file and update every link to it and from it
+    Move {
+        /// ID of the node to move
+        id: String,
+
+        /// Markdown file to append the node to (created if missing)
+        #[arg(long)]
+        to: PathBuf,
+
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Show the changes as a diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Merge the node SOURCE into TARGET and point every link to SOURCE at TARGET
+    Merge {
+        /// ID of the node that is kept
+        target: String,
+
+        /// ID of the node whose body is appended to TARGET and then removed
+        source: String,
+
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Show the changes as a diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Split a file in two: the node and every node after it move to a new file
+    Split {
+        /// ID of the first node of the new file
+        id: String,
+
+        /// New markdown file (defaults to <ID>.md next to the current file)
+        #[arg(long)]
+        to: Option<PathBuf>,
+
+        /// Path to search for markdown files (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Show the changes as a diff without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Show node type information from configuration
     Type {
         /// Type ID to show details for (optional)
@@ -89,6 +253,45 @@ pub enum OutputFormat {
     Json,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum CheckFormat {
+    Text,
+    Json,
+    /// SARIF 2.1.0 log for code-scanning tools
+    Sarif,
+    /// JUnit XML report for test dashboards
+    Junit,
+    /// GitHub Actions workflow annotations
+    Github,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum GraphFormat {
+    /// Spec blocks as JSON
+    Json,
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// GraphML XML
+    Graphml,
+    /// Cytoscape.js elements JSON
+    Cytoscape,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum MatrixFormat {
+    Markdown,
+    Csv,
+    Html,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum StatsFormat {
+    Text,
+    Json,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,13 +309,19 @@ mod tests {
             Commands::Check {
                 path,
                 json,
+                format,
                 fix,
+                dry_run,
                 rule,
+                watch,
             } => {
                 assert_eq!(path, PathBuf::from("."));
                 assert!(!json);
+                assert_eq!(format, CheckFormat::Text);
                 assert!(!fix);
+                assert!(!dry_run);
                 assert!(rule.is_none());
+                assert!(!watch);
             }
             _ => panic!("Expected Check command"),
         }
@@ -128,14 +337,92 @@ mod tests {
                 path,
                 json,
                 fix,
+                dry_run,
                 rule,
+                ..
             } => {
                 assert_eq!(path, PathBuf::from("./doc"));
                 assert!(json);
                 assert!(fix);
+                assert!(!dry_run);
                 assert_eq!(rule, Some(vec!["MD001".to_string()]));
             }
             _ => panic!("Expected Check command"),
         }
     }
+
+    #[test]
+    fn test_check_format() {
+        let cli = Cli::parse_from(["docgraph", "check", "--format", "sarif"]);
+        match cli.command {
+            Commands::Check { format, .. } => assert_eq!(format, CheckFormat::Sarif),
+            _ => panic!("Expected Check command"),
+        }
+        assert!(Cli::try_parse_from(["docgraph", "check", "--json", "--format", "junit"]).is_err());
+    }
+
+    #[test]
+    fn test_check_watch() {
+        let cli = Cli::parse_from(["docgraph", "check", "--watch"]);
+        match cli.command {
+            Commands::Check { watch, .. } => assert!(watch),
+            _ => panic!("Expected Check command"),
+        }
+        assert!(Cli::try_parse_from(["docgraph", "check", "--watch", "--fix"]).is_err());
+    }
+
+    #[test]
+    fn test_graph_options() {
+        let cli = Cli::parse_from([
+            "docgraph", "graph", "--format", "dot", "--root", "FR-01", "--depth", "2",
+        ]);
+        match cli.command {
+            Commands::Graph {
+                path,
+                format,
+                root,
+                depth,
+            } => {
+                assert_eq!(path, PathBuf::from("."));
+                assert_eq!(format, GraphFormat::Dot);
+                assert_eq!(root.as_deref(), Some("FR-01"));
+                assert_eq!(depth, Some(2));
+            }
+            _ => panic!("Expected Graph command"),
+        }
+        assert!(Cli::try_parse_from(["docgraph", "graph", "--depth", "2"]).is_err());
+    }
+
+    #[test]
+    fn test_matrix_options() {
+        let cli = Cli::parse_from([
+            "docgraph", "matrix", "--rows", "UC", "--cols", "MOD", "--via", "FR,IF", "--format",
+            "csv",
+        ]);
+        match cli.command {
+            Commands::Matrix {
+                rows,
+                cols,
+                via,
+                rel,
+                format,
+                path,
+            } => {
+                assert_eq!(rows, "UC");
+                assert_eq!(cols, "MOD");
+                assert_eq!(via, vec!["FR".to_string(), "IF".to_string()]);
+                assert!(rel.is_none());
+                assert_eq!(format, MatrixFormat::Csv);
+                assert_eq!(path, PathBuf::from("."));
+            }
+            _ => panic!("Expected Matrix command"),
+        }
+        assert!(Cli::try_parse_from(["docgraph", "matrix", "--rows", "UC"]).is_err());
+    }
+
+    #[test]
+    fn test_check_dry_run_requires_fix() {
+        assert!(Cli::try_parse_from(["docgraph", "check", "--dry-run"]).is_err());
+        assert!(Cli::try_parse_from(["docgraph", "check", "--fix", "--dry-run"]).is_ok());
+    }
 }
@@ -1,11 +1,30 @@
 use super::common::print_diagnostics;
-use crate::core::{config, lint, types};
+use crate::cli::args::CheckFormat;
+use crate::core::index::WorkspaceIndex;
+use crate::core::{config, fix, lint, report, types, watch};
 use anyhow::Context;
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
 
-pub fn handle_check(path: PathBuf, json: bool, fix: bool, rule: Option<Vec<String>>) -> ExitCode {
-    match try_check(path, json, fix, rule) {
+/// Interval between two scans of the workspace in watch mode
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn handle_check(
+    path: PathBuf,
+    format: CheckFormat,
+    fix: bool,
+    dry_run: bool,
+    rule: Option<Vec<String>>,
+    watch: bool,
+) -> ExitCode {
+    let result = if watch {
+        try_watch(path, format, rule)
+    } else {
+        try_check(path, format, fix, dry_run, rule)
+    };
+    match result {
         Ok(code) => code,
         Err(e) => {
             eprintln!("Error: {:#}", e);
@@ -16,57 +35,32 @@ pub fn handle_check(path: PathBuf, json: bool, fix: bool, rule: Option<Vec<Strin
 
 fn try_check(
     path: PathBuf,
-    json: bool,
+    format: CheckFormat,
     fix: bool,
+    dry_run: bool,
     rule: Option<Vec<String>>,
 ) -> anyhow::Result<ExitCode> {
     let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
 
-    let diagnostics = lint::check_workspace(&path, fix, rule, true, &config, None);
-
-    if json {
-        let json_out = serde_json::to_string_pretty(&diagnostics)
-            .context("failed to serialize diagnostics to JSON")?;
-        println!("{}", json_out);
-    } else {
-        print_diagnostics(&diagnostics);
-
-        let error_count = diagnostics
-            .iter()
-            .filter(|d| matches!(d.severity, types::Severity::Error))
-            .count();
-        let warning_count = diagnostics
-            .iter()
-            .filter(|d| matches!(d.severity, types::Severity::Warning))
-            .count();
+    let mut diagnostics = lint::check_workspace(&path, rule.clone(), true, &config, None);
 
-        if error_count == 0 && warning_count == 0 {
-            println!("No errors or warnings found.");
+    if fix {
+        let summary = apply_fixes(&diagnostics, dry_run)?;
+        // Keep stdout machine-readable for structured formats
+        if format == CheckFormat::Text {
+            print!("{}", summary.diff);
+            println!("{}", summary.message);
         } else {
-            let mut summary = Vec::new();
-            if error_count > 0 {
-                summary.push(format!("{} error(s)", error_count));
-            }
-            if warning_count > 0 {
-                summary.push(format!("{} warning(s)", warning_count));
-            }
-
-            let summary_str = summary.join(" and ");
-
-            if !fix {
-                println!(
-                    "\nFound {}. Run with --fix to automatically fix some issues.",
-                    summary_str
-                );
-            } else {
-                println!(
-                    "\nFound {} that could not be fixed automatically.",
-                    summary_str
-                );
-            }
+            eprint!("{}", summary.diff);
+            eprintln!("{}", summary.message);
+        }
+        if summary.applied > 0 && !dry_run {
+            diagnostics = lint::check_workspace(&path, rule, true, &config, None);
         }
     }
 
+    print_report(&diagnostics, &config, format, fix)?;
+
     if diagnostics
         .iter()
         .any(|d| matches!(d.severity, types::Severity::Error))
@@ -77,8 +71,145 @@ fn try_check(
     }
 }
 
-pub fn handle_fmt(path: PathBuf, rule: Option<Vec<String>>) -> ExitCode {
-    match try_fmt(path, rule) {
+/// Check the workspace, then check it again after every change until interrupted
+fn try_watch(
+    path: PathBuf,
+    format: CheckFormat,
+    rule: Option<Vec<String>>,
+) -> anyhow::Result<ExitCode> {
+    let mut config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let mut index = WorkspaceIndex::load(&path, config.clone(), None);
+    let mut stamps = watch::stamps(&path, &config);
+    let mut status = format!("Watching {} for changes", path.display());
+
+    loop {
+        let diagnostics = watch::dedup(index.diagnostics(rule.as_deref()));
+        // Keep stdout machine-readable for structured formats
+        let header = format!("[{}] {}", watch::clock(SystemTime::now()), status);
+        if format == CheckFormat::Text {
+            if std::io::stdout().is_terminal() {
+                // Clear the screen so only the latest results are shown
+                print!("\x1b[2J\x1b[H");
+            }
+            println!("{}\n", header);
+        } else {
+            eprintln!("{}", header);
+        }
+        print_report(&diagnostics, &config, format, false)?;
+
+        status = wait_for_changes(&path, &mut config, &mut index, &mut stamps);
+    }
+}
+
+/// Block until a change modifies the index, and describe it
+fn wait_for_changes(
+    path: &Path,
+    config: &mut config::Config,
+    index: &mut WorkspaceIndex,
+    stamps: &mut watch::Stamps,
+) -> String {
+    loop {
+        std::thread::sleep(WATCH_INTERVAL);
+        let current = watch::stamps(path, config);
+        let changes = watch::changes(path, config, stamps, &current);
+        if changes.is_empty() {
+            continue;
+        }
+        *stamps = current;
+
+        let generation = index.generation();
+        if watch::apply(index, &changes) {
+            // Files saved without changes do not need a new check
+            if index.generation() != generation {
+                return format!("{} file(s) changed", changes.len());
+            }
+            continue;
+        }
+        match config::Config::load(path) {
+            Ok(reloaded) => {
+                *config = reloaded;
+                *index = WorkspaceIndex::load(path, config.clone(), None);
+                *stamps = watch::stamps(path, config);
+                return "Configuration changed".to_string();
+            }
+            // Keep the previous configuration until the file is fixed
+            Err(e) => eprintln!("Error: failed to load docgraph.toml: {:#}", e),
+        }
+    }
+}
+
+fn print_report(
+    diagnostics: &[types::Diagnostic],
+    config: &config::Config,
+    format: CheckFormat,
+    fix: bool,
+) -> anyhow::Result<()> {
+    // Report paths relative to the working directory, which is the repository root in CI
+    let root = std::env::current_dir().context("failed to get current directory")?;
+    match format {
+        CheckFormat::Json => {
+            let json_out = serde_json::to_string_pretty(diagnostics)
+                .context("failed to serialize diagnostics to JSON")?;
+            println!("{}", json_out);
+        }
+        CheckFormat::Sarif => {
+            let sarif = report::to_sarif(diagnostics, &root, config);
+            let sarif_out = serde_json::to_string_pretty(&sarif)
+                .context("failed to serialize diagnostics to SARIF")?;
+            println!("{}", sarif_out);
+        }
+        CheckFormat::Junit => print!("{}", report::to_junit(diagnostics, &root)),
+        CheckFormat::Github => print!("{}", report::to_github(diagnostics, &root)),
+        CheckFormat::Text => print_text_report(diagnostics, fix),
+    }
+    Ok(())
+}
+
+fn print_text_report(diagnostics: &[types::Diagnostic], fix: bool) {
+    print_diagnostics(diagnostics);
+
+    let count = |severity: types::Severity| {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let error_count = count(types::Severity::Error);
+    let warning_count = count(types::Severity::Warning);
+    let info_count = count(types::Severity::Info) + count(types::Severity::Hint);
+
+    if error_count == 0 && warning_count == 0 && info_count == 0 {
+        println!("No errors or warnings found.");
+    } else {
+        let mut summary = Vec::new();
+        if error_count > 0 {
+            summary.push(format!("{} error(s)", error_count));
+        }
+        if warning_count > 0 {
+            summary.push(format!("{} warning(s)", warning_count));
+        }
+        if info_count > 0 {
+            summary.push(format!("{} info message(s)", info_count));
+        }
+
+        let summary_str = summary.join(" and ");
+
+        if !fix {
+            println!(
+                "\nFound {}. Run with --fix to automatically fix some issues.",
+                summary_str
+            );
+        } else {
+            println!(
+                "\nFound {} that could not be fixed automatically.",
+                summary_str
+            );
+        }
+    }
+}
+
+pub fn handle_fmt(path: PathBuf, dry_run: bool, rule: Option<Vec<String>>) -> ExitCode {
+    match try_fmt(path, dry_run, rule) {
         Ok(code) => code,
         Err(e) => {
             eprintln!("Error: {:#}", e);
@@ -87,9 +218,61 @@ pub fn handle_fmt(path: PathBuf, rule: Option<Vec<String>>) -> ExitCode {
     }
 }
 
-fn try_fmt(path: PathBuf, rule: Option<Vec<String>>) -> anyhow::Result<ExitCode> {
+fn try_fmt(path: PathBuf, dry_run: bool, rule: Option<Vec<String>>) -> anyhow::Result<ExitCode> {
     let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
-    let diagnostics = lint::check_workspace(&path, true, rule, false, &config, None);
+    let mut diagnostics = lint::check_workspace(&path, rule.clone(), false, &config, None);
+
+    let summary = apply_fixes(&diagnostics, dry_run)?;
+    print!("{}", summary.diff);
+    println!("{}", summary.message);
+    if summary.applied > 0 && !dry_run {
+        diagnostics = lint::check_workspace(&path, rule, false, &config, None);
+    }
+
     print_diagnostics(&diagnostics);
     Ok(ExitCode::SUCCESS)
 }
+
+struct FixSummary {
+    applied: usize,
+    /// Unified diff of the fixes, only filled in for a dry run
+    diff: String,
+    message: String,
+}
+
+/// Apply the fixes attached to `diagnostics`, or render them as a diff when `dry_run` is set
+fn apply_fixes(diagnostics: &[types::Diagnostic], dry_run: bool) -> anyhow::Result<FixSummary> {
+    let edits = fix::collect_edits(diagnostics);
+    let changes = fix::plan_changes(&edits, None).context("failed to compute fixes")?;
+
+    let applied: usize = changes.iter().map(|c| c.applied).sum();
+    let skipped: usize = changes.iter().map(|c| c.skipped).sum();
+
+    let mut diff = String::new();
+    if dry_run {
+        for change in &changes {
+            diff.push_str(&fix::unified_diff(change));
+        }
+    } else {
+        fix::write_changes(&changes).context("failed to write fixes")?;
+    }
+
+    let mut message = format!(
+        "{} {} issue(s) in {} file(s).",
+        if dry_run { "Would fix" } else { "Fixed" },
+        applied,
+        changes.len()
+    );
+    if skipped > 0 {
+        message.push_str(&format!(
+            " {} overlapping fix(es) were skipped; run again to apply them.",
+            skipped
+        ));
+    }
+
+    Ok(FixSummary {
+        applied,
+        diff,
+        message,
+    })
+}
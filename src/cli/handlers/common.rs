@@ -1,4 +1,6 @@
-use crate::core::types;
+use crate::core::fix::{self, FileChange};
+use crate::core::types::{self, TextEdit};
+use anyhow::Context;
 
 pub fn glob_to_regex(glob: &str) -> String {
     let mut regex = String::from("^");
@@ -29,6 +31,8 @@ pub fn print_diagnostics(diagnostics: &[types::Diagnostic]) {
             match d.severity {
                 types::Severity::Error => "error",
                 types::Severity::Warning => "warning",
+                types::Severity::Info => "info",
+                types::Severity::Hint => "hint",
             },
             d.code,
             d.path.display(),
@@ -38,3 +42,16 @@ pub fn print_diagnostics(diagnostics: &[types::Diagnostic]) {
         );
     }
 }
+
+/// Print the changes as a diff when `dry_run`, otherwise write them
+pub fn apply_edits(edits: &[TextEdit], dry_run: bool) -> anyhow::Result<Vec<FileChange>> {
+    let changes = fix::plan_changes(edits, None).context("failed to compute changes")?;
+    if dry_run {
+        for change in &changes {
+            print!("{}", fix::unified_diff(change));
+        }
+    } else {
+        fix::write_changes(&changes).context("failed to write changes")?;
+    }
+    Ok(changes)
+}
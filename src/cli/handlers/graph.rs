@@ -1,10 +1,17 @@
+use crate::cli::args::GraphFormat;
+use crate::core::export::{self, ExportGraph};
 use crate::core::{collect, config};
 use anyhow::Context;
 use std::path::PathBuf;
 use std::process::ExitCode;
 
-pub fn handle_graph(path: PathBuf) -> ExitCode {
-    match try_graph(path) {
+pub fn handle_graph(
+    path: PathBuf,
+    format: GraphFormat,
+    root: Option<String>,
+    depth: Option<usize>,
+) -> ExitCode {
+    match try_graph(path, format, root, depth) {
         Ok(code) => code,
         Err(e) => {
             eprintln!("Error: {:#}", e);
@@ -13,11 +20,36 @@ pub fn handle_graph(path: PathBuf) -> ExitCode {
     }
 }
 
-fn try_graph(path: PathBuf) -> anyhow::Result<ExitCode> {
+fn try_graph(
+    path: PathBuf,
+    format: GraphFormat,
+    root: Option<String>,
+    depth: Option<usize>,
+) -> anyhow::Result<ExitCode> {
     let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
-    let (blocks, _refs) = collect::collect_workspace_all(&path, &config.graph.ignore, None);
-    let json_out =
-        serde_json::to_string_pretty(&blocks).context("failed to serialize graph to JSON")?;
-    println!("{}", json_out);
+    let (blocks, _refs) = collect::collect_workspace_cached(&path, &config);
+
+    let mut graph = ExportGraph::build(&blocks, &config);
+    if let Some(root) = root {
+        graph = graph
+            .neighborhood(&root, depth.unwrap_or(1))
+            .context("failed to select neighborhood")?;
+    }
+
+    match format {
+        GraphFormat::Json => {
+            let json_out = serde_json::to_string_pretty(&graph.nodes)
+                .context("failed to serialize graph to JSON")?;
+            println!("{}", json_out);
+        }
+        GraphFormat::Cytoscape => {
+            let json_out = serde_json::to_string_pretty(&export::to_cytoscape(&graph))
+                .context("failed to serialize graph to JSON")?;
+            println!("{}", json_out);
+        }
+        GraphFormat::Dot => print!("{}", export::to_dot(&graph, &config)),
+        GraphFormat::Mermaid => print!("{}", export::to_mermaid(&graph)),
+        GraphFormat::Graphml => print!("{}", export::to_graphml(&graph)),
+    }
     Ok(ExitCode::SUCCESS)
 }
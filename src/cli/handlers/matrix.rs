@@ -0,0 +1,45 @@
+use crate::cli::args::MatrixFormat;
+use crate::core::{collect, config, matrix};
+use anyhow::Context;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+pub fn handle_matrix(
+    rows: String,
+    cols: String,
+    via: Vec<String>,
+    rel: Option<Vec<String>>,
+    format: MatrixFormat,
+    path: PathBuf,
+) -> ExitCode {
+    match try_matrix(rows, cols, via, rel, format, path) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_matrix(
+    rows: String,
+    cols: String,
+    via: Vec<String>,
+    rel: Option<Vec<String>>,
+    format: MatrixFormat,
+    path: PathBuf,
+) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let (blocks, _refs) = collect::collect_workspace_cached(&path, &config);
+
+    let matrix = matrix::build_matrix(&blocks, &config, &rows, &cols, &via, rel.as_deref())
+        .context("failed to build matrix")?;
+
+    let output = match format {
+        MatrixFormat::Markdown => matrix::to_markdown(&matrix),
+        MatrixFormat::Csv => matrix::to_csv(&matrix),
+        MatrixFormat::Html => matrix::to_html(&matrix),
+    };
+    print!("{}", output);
+    Ok(ExitCode::SUCCESS)
+}
@@ -2,6 +2,11 @@ pub mod check;
 pub mod common;
 pub mod describe;
 pub mod graph;
+pub mod matrix;
+pub mod new_cmd;
 pub mod query;
+pub mod refactor;
 pub mod rule;
+pub mod site;
+pub mod stats;
 pub mod type_cmd;
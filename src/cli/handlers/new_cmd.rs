@@ -0,0 +1,47 @@
+use super::common::apply_edits;
+use crate::core::index::WorkspaceIndex;
+use crate::core::{config, scaffold};
+use anyhow::Context;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+pub fn handle_new(
+    node_type: String,
+    id: Option<String>,
+    title: Option<String>,
+    file: PathBuf,
+    path: PathBuf,
+    dry_run: bool,
+) -> ExitCode {
+    match try_new(node_type, id, title, file, path, dry_run) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_new(
+    node_type: String,
+    id: Option<String>,
+    title: Option<String>,
+    file: PathBuf,
+    path: PathBuf,
+    dry_run: bool,
+) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let index = WorkspaceIndex::load(&path, config, None);
+
+    let (id, edit) = scaffold::new_node(&index, &node_type, id.as_deref(), title.as_deref(), &file)
+        .with_context(|| format!("cannot create a {} node", node_type))?;
+    apply_edits(&[edit], dry_run)?;
+
+    println!(
+        "{} '{}' in {}.",
+        if dry_run { "Would create" } else { "Created" },
+        id,
+        file.display()
+    );
+    Ok(ExitCode::SUCCESS)
+}
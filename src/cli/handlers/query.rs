@@ -17,10 +17,13 @@ pub fn handle_query(query_str: String, format: OutputFormat, path: PathBuf) -> E
 
 fn try_query(query_str: String, format: OutputFormat, path: PathBuf) -> anyhow::Result<ExitCode> {
     let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
-    let (blocks, _) = collect::collect_workspace_all(&path, &config.graph.ignore, None);
+    let (blocks, _) = collect::collect_workspace_cached(&path, &config);
 
     let query = crate::core::parser::parse_query(&query_str).context("failed to parse query")?;
     let result = engine::execute_query(&query, &blocks, &config);
+    for warning in &result.warnings {
+        eprintln!("Warning: {}", warning);
+    }
 
     match format {
         OutputFormat::Table => {
@@ -33,7 +36,7 @@ fn try_query(query_str: String, format: OutputFormat, path: PathBuf) -> anyhow::
                 table.set_header(&result.columns);
 
                 for row in result.rows {
-                    table.add_row(row);
+                    table.add_row(row.iter().map(ToString::to_string));
                 }
 
                 println!("{table}");
@@ -46,7 +49,10 @@ fn try_query(query_str: String, format: OutputFormat, path: PathBuf) -> anyhow::
                 let mut obj = serde_json::Map::new();
                 for (i, col_name) in result.columns.iter().enumerate() {
                     if let Some(val) = row.get(i) {
-                        obj.insert(col_name.clone(), serde_json::Value::String(val.clone()));
+                        obj.insert(
+                            col_name.clone(),
+                            serde_json::to_value(val).context("failed to serialize to JSON")?,
+                        );
                     }
                 }
                 json_rows.push(serde_json::Value::Object(obj));
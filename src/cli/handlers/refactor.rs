@@ -0,0 +1,136 @@
+use super::common::apply_edits;
+use crate::core::index::WorkspaceIndex;
+use crate::core::{config, refactor};
+use anyhow::Context;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+pub fn handle_mv(old_id: String, new_id: String, path: PathBuf, dry_run: bool) -> ExitCode {
+    match try_mv(old_id, new_id, path, dry_run) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_mv(
+    old_id: String,
+    new_id: String,
+    path: PathBuf,
+    dry_run: bool,
+) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let index = WorkspaceIndex::load(&path, config, None);
+
+    let edits = refactor::rename_edits(&index, &old_id, &new_id)
+        .with_context(|| format!("cannot rename '{}' to '{}'", old_id, new_id))?;
+    let changes = apply_edits(&edits, dry_run)?;
+
+    println!(
+        "{} '{}' to '{}': {} edit(s) in {} file(s).",
+        if dry_run { "Would rename" } else { "Renamed" },
+        old_id,
+        new_id,
+        edits.len(),
+        changes.len()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn handle_move(id: String, to: PathBuf, path: PathBuf, dry_run: bool) -> ExitCode {
+    match try_move(id, to, path, dry_run) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_move(id: String, to: PathBuf, path: PathBuf, dry_run: bool) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let index = WorkspaceIndex::load(&path, config, None);
+
+    let edits = refactor::move_edits(&index, &id, &to)
+        .with_context(|| format!("cannot move '{}' to {}", id, to.display()))?;
+    let changes = apply_edits(&edits, dry_run)?;
+
+    println!(
+        "{} '{}' to {}: {} edit(s) in {} file(s).",
+        if dry_run { "Would move" } else { "Moved" },
+        id,
+        to.display(),
+        edits.len(),
+        changes.len()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn handle_merge(into_id: String, from_id: String, path: PathBuf, dry_run: bool) -> ExitCode {
+    match try_merge(into_id, from_id, path, dry_run) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_merge(
+    into_id: String,
+    from_id: String,
+    path: PathBuf,
+    dry_run: bool,
+) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let index = WorkspaceIndex::load(&path, config, None);
+
+    let edits = refactor::merge_edits(&index, &into_id, &from_id)
+        .with_context(|| format!("cannot merge '{}' into '{}'", from_id, into_id))?;
+    let changes = apply_edits(&edits, dry_run)?;
+
+    println!(
+        "{} '{}' into '{}': {} edit(s) in {} file(s).",
+        if dry_run { "Would merge" } else { "Merged" },
+        from_id,
+        into_id,
+        edits.len(),
+        changes.len()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+pub fn handle_split(id: String, to: Option<PathBuf>, path: PathBuf, dry_run: bool) -> ExitCode {
+    match try_split(id, to, path, dry_run) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_split(
+    id: String,
+    to: Option<PathBuf>,
+    path: PathBuf,
+    dry_run: bool,
+) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let index = WorkspaceIndex::load(&path, config, None);
+
+    let edits = refactor::split_edits(&index, &id, to.as_deref())
+        .with_context(|| format!("cannot split the file of '{}'", id))?;
+    let changes = apply_edits(&edits, dry_run)?;
+
+    println!(
+        "{} at '{}': {} edit(s) in {} file(s).",
+        if dry_run { "Would split" } else { "Split" },
+        id,
+        edits.len(),
+        changes.len()
+    );
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,23 @@
+use crate::core::{config, site};
+use anyhow::Context;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+pub fn handle_site(out_dir: PathBuf, path: PathBuf) -> ExitCode {
+    match try_site(out_dir, path) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_site(out_dir: PathBuf, path: PathBuf) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let pages = site::build_site(&path, &config).context("failed to render site")?;
+    site::write_site(&pages, &out_dir)
+        .with_context(|| format!("failed to write site to {}", out_dir.display()))?;
+    println!("Generated {} page(s) in {}", pages.len(), out_dir.display());
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,31 @@
+use crate::cli::args::StatsFormat;
+use crate::core::{collect, config, stats};
+use anyhow::Context;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+pub fn handle_stats(path: PathBuf, format: StatsFormat) -> ExitCode {
+    match try_stats(path, format) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_stats(path: PathBuf, format: StatsFormat) -> anyhow::Result<ExitCode> {
+    let config = config::Config::load(&path).context("failed to load docgraph.toml")?;
+    let (blocks, _refs) = collect::collect_workspace_cached(&path, &config);
+    let stats = stats::compute_stats(&blocks, &config);
+
+    match format {
+        StatsFormat::Text => print!("{}", stats::to_text(&stats)),
+        StatsFormat::Json => {
+            let json_out = serde_json::to_string_pretty(&stats)
+                .context("failed to serialize stats to JSON")?;
+            println!("{}", json_out);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
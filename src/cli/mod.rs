@@ -2,7 +2,7 @@ pub mod args;
 pub mod handlers;
 
 use crate::lsp;
-use args::{Cli, Commands};
+use args::{CheckFormat, Cli, Commands};
 use clap::Parser;
 use std::process::ExitCode;
 
@@ -17,13 +17,70 @@ pub fn run() -> ExitCode {
         Commands::Check {
             path,
             json,
+            format,
             fix,
+            dry_run,
+            rule,
+            watch,
+        } => {
+            let format = if json { CheckFormat::Json } else { format };
+            handlers::check::handle_check(path, format, fix, dry_run, rule, watch)
+        }
+        Commands::Fmt {
+            path,
+            dry_run,
             rule,
-        } => handlers::check::handle_check(path, json, fix, rule),
-        Commands::Fmt { path, rule } => handlers::check::handle_fmt(path, rule),
+        } => handlers::check::handle_fmt(path, dry_run, rule),
         Commands::Rule { rule } => handlers::rule::handle_rule(rule),
-        Commands::Graph { path } => handlers::graph::handle_graph(path),
+        Commands::Graph {
+            path,
+            format,
+            root,
+            depth,
+        } => handlers::graph::handle_graph(path, format, root, depth),
+        Commands::Matrix {
+            rows,
+            cols,
+            via,
+            rel,
+            format,
+            path,
+        } => handlers::matrix::handle_matrix(rows, cols, via, rel, format, path),
+        Commands::Stats { path, format } => handlers::stats::handle_stats(path, format),
+        Commands::Site { out_dir, path } => handlers::site::handle_site(out_dir, path),
         Commands::Describe { id, path } => handlers::describe::handle_describe(id, path),
+        Commands::New {
+            node_type,
+            id,
+            title,
+            file,
+            path,
+            dry_run,
+        } => handlers::new_cmd::handle_new(node_type, id, title, file, path, dry_run),
+        Commands::Mv {
+            old_id,
+            new_id,
+            path,
+            dry_run,
+        } => handlers::refactor::handle_mv(old_id, new_id, path, dry_run),
+        Commands::Move {
+            id,
+            to,
+            path,
+            dry_run,
+        } => handlers::refactor::handle_move(id, to, path, dry_run),
+        Commands::Merge {
+            target,
+            source,
+            path,
+            dry_run,
+        } => handlers::refactor::handle_merge(target, source, path, dry_run),
+        Commands::Split {
+            id,
+            to,
+            path,
+            dry_run,
+        } => handlers::refactor::handle_split(id, to, path, dry_run),
         Commands::Type { type_id } => handlers::type_cmd::handle_type(type_id),
         Commands::Query {
             query,
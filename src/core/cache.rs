@@ -0,0 +1,210 @@
+use crate::core::config::CacheConfig;
+use crate::core::parse::{ParsedDocument, parse_document};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Bump when the parser output changes, so caches written by older builds are discarded
+const FORMAT: u32 = 1;
+const CACHE_FILE: &str = "documents.json";
+
+/// Parsed documents stored on disk, keyed by file path and content hash.
+///
+/// Entries not requested since the cache was opened are dropped on save, so deleted files
+/// do not accumulate. Files can be parsed through the cache from several threads.
+pub struct ParseCache {
+    file: PathBuf,
+    entries: HashMap<PathBuf, Entry>,
+    used: Mutex<HashMap<PathBuf, Entry>>,
+    dirty: AtomicBool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: String,
+    files: HashMap<PathBuf, Entry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Entry {
+    hash: String,
+    document: ParsedDocument,
+}
+
+impl ParseCache {
+    /// The cache of the workspace at `root`, or `None` when disabled in [cache]. A missing,
+    /// unreadable or outdated cache file starts an empty cache.
+    pub fn open(root: &Path, config: &CacheConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let file = root.join(&config.dir).join(CACHE_FILE);
+        let entries = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .filter(|cache| cache.version == version())
+            .map(|cache| cache.files)
+            .unwrap_or_default();
+        Some(Self {
+            file,
+            entries,
+            used: Mutex::new(HashMap::new()),
+            dirty: AtomicBool::new(false),
+        })
+    }
+
+    /// The parsed document for `content`, parsed only when not cached for this content
+    pub fn parse(&self, content: &str, file_path: &Path) -> ParsedDocument {
+        let hash = content_hash(content);
+        let entry = match self.entries.get(file_path) {
+            Some(entry) if entry.hash == hash => entry.clone(),
+            _ => {
+                self.dirty.store(true, Ordering::Relaxed);
+                Entry {
+                    hash,
+                    document: parse_document(content, file_path),
+                }
+            }
+        };
+        let document = entry.document.clone();
+        self.used
+            .lock()
+            .unwrap()
+            .insert(file_path.to_path_buf(), entry);
+        document
+    }
+
+    /// Write the cache if any entry was added, changed or dropped
+    pub fn save(self) -> std::io::Result<()> {
+        let used = self.used.into_inner().unwrap();
+        if !self.dirty.into_inner() && used.len() == self.entries.len() {
+            return Ok(());
+        }
+        let dir = self.file.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(dir)?;
+        // Keep the cache out of version control
+        let gitignore = dir.join(".gitignore");
+        if !gitignore.exists() {
+            fs::write(&gitignore, "*\n")?;
+        }
+
+        let cache = CacheFile {
+            version: version(),
+            files: used,
+        };
+        let content = serde_json::to_string(&cache)?;
+        // Write then rename, so concurrent runs never read a partial file
+        let tmp = self
+            .file
+            .with_extension(format!("json.{}", std::process::id()));
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, &self.file)
+    }
+}
+
+/// Parse with the cache when there is one
+pub fn parse_with(cache: Option<&ParseCache>, content: &str, file_path: &Path) -> ParsedDocument {
+    match cache {
+        Some(cache) => cache.parse(content, file_path),
+        None => parse_document(content, file_path),
+    }
+}
+
+/// Version of the cache format and of the parser that wrote it
+fn version() -> String {
+    format!("{}+{}", env!("CARGO_PKG_VERSION"), FORMAT)
+}
+
+/// FNV-1a, which unlike the std hasher is stable across Rust releases
+fn content_hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn enabled() -> CacheConfig {
+        CacheConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_cache_roundtrip() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("b.md");
+        let content = "<a id=\"REQ-1\"></a>\n# Login\n\n[REQ-2](#REQ-2)\n";
+
+        assert!(ParseCache::open(dir.path(), &CacheConfig::default()).is_none());
+
+        let cache = ParseCache::open(dir.path(), &enabled()).unwrap();
+        let parsed = cache.parse(content, &a);
+        assert_eq!(parsed, parse_document(content, &a));
+        cache.parse("# B\n", &b);
+        cache.save().unwrap();
+        let cache_dir = dir.path().join(".docgraph/cache");
+        assert!(cache_dir.join(CACHE_FILE).exists());
+        assert_eq!(
+            fs::read_to_string(cache_dir.join(".gitignore")).unwrap(),
+            "*\n"
+        );
+
+        // Same content: served from the cache, nothing to write
+        let cache = ParseCache::open(dir.path(), &enabled()).unwrap();
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.parse(content, &a), parsed);
+        assert!(!cache.dirty.load(Ordering::Relaxed));
+
+        // Changed content is parsed again, and b.md is dropped on save
+        let changed = cache.parse("<a id=\"REQ-3\"></a>\n# Logout\n", &a);
+        assert_eq!(changed.blocks[0].id, "REQ-3");
+        assert!(cache.dirty.load(Ordering::Relaxed));
+        cache.save().unwrap();
+        let cache = ParseCache::open(dir.path(), &enabled()).unwrap();
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_cache_discards_other_versions() {
+        let dir = tempdir().unwrap();
+        let cache_dir = dir.path().join(".docgraph/cache");
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(
+            cache_dir.join(CACHE_FILE),
+            r#"{"version":"0.0.0+0","files":{"a.md":{"hash":"0","document":{"blocks":[],"refs":[],"suppressions":[]}}}}"#,
+        )
+        .unwrap();
+        assert!(
+            ParseCache::open(dir.path(), &enabled())
+                .unwrap()
+                .entries
+                .is_empty()
+        );
+
+        fs::write(cache_dir.join(CACHE_FILE), "not json").unwrap();
+        assert!(
+            ParseCache::open(dir.path(), &enabled())
+                .unwrap()
+                .entries
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_ne!(content_hash("a"), content_hash("b"));
+    }
+}
@@ -1,6 +1,8 @@
-use crate::core::parse::extract_all;
+use crate::core::cache::{self, ParseCache};
+use crate::core::config::Config;
+use crate::core::parse::ParsedDocument;
 use crate::core::types::{RefUse, SpecBlock};
-use crate::core::walk::find_markdown_files;
+use crate::core::walk::map_markdown_files;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -11,32 +13,72 @@ pub fn collect_workspace_all(
     ignore_patterns: &[String],
     overrides: Option<&HashMap<PathBuf, String>>,
 ) -> (Vec<SpecBlock>, Vec<RefUse>) {
-    let files = find_markdown_files(root, ignore_patterns);
     let mut all_blocks = Vec::new();
     let mut all_refs = Vec::new();
+    for doc in collect_workspace_documents(root, ignore_patterns, overrides) {
+        all_blocks.extend(doc.blocks);
+        all_refs.extend(doc.refs);
+    }
+    (all_blocks, all_refs)
+}
 
-    for file_path in files {
-        // Canonicalize the path for lookup to match the keys in overrides
-        let lookup_path = fs::canonicalize(&file_path).unwrap_or_else(|_| file_path.clone());
+/// Collect all SpecBlocks and standalone RefUses from the workspace, reusing the parse cache
+/// when it is enabled in [cache]
+pub fn collect_workspace_cached(root: &Path, config: &Config) -> (Vec<SpecBlock>, Vec<RefUse>) {
+    let cache = ParseCache::open(root, &config.cache);
+    let documents = read_documents(root, &config.graph.ignore, None, cache.as_ref());
+    if let Some(cache) = cache {
+        // A cache that cannot be written only makes the next run slower
+        let _ = cache.save();
+    }
 
-        let content_result = if let Some(map) = overrides
-            && let Some(content) = map.get(lookup_path.as_path())
-        {
-            Ok(content.clone())
-        } else {
-            fs::read_to_string(&file_path)
-        };
+    let mut all_blocks = Vec::new();
+    let mut all_refs = Vec::new();
+    for doc in documents {
+        all_blocks.extend(doc.blocks);
+        all_refs.extend(doc.refs);
+    }
+    (all_blocks, all_refs)
+}
 
-        match content_result {
-            Ok(content) => {
-                let (blocks, refs) = extract_all(&content, &file_path);
-                all_blocks.extend(blocks);
-                all_refs.extend(refs);
-            }
-            Err(_e) => {} // Silence read errors in core
+/// Parse every Markdown file of the workspace, one document per file
+pub fn collect_workspace_documents(
+    root: &Path,
+    ignore_patterns: &[String],
+    overrides: Option<&HashMap<PathBuf, String>>,
+) -> Vec<ParsedDocument> {
+    read_documents(root, ignore_patterns, overrides, None)
+}
+
+/// Read and parse every Markdown file on several threads, in path order
+fn read_documents(
+    root: &Path,
+    ignore_patterns: &[String],
+    overrides: Option<&HashMap<PathBuf, String>>,
+    cache: Option<&ParseCache>,
+) -> Vec<ParsedDocument> {
+    map_markdown_files(root, ignore_patterns, |file_path| {
+        let content = read_content(file_path, overrides)?;
+        Some(cache::parse_with(cache, &content, file_path))
+    })
+    .into_iter()
+    .map(|(_, document)| document)
+    .collect()
+}
+
+/// Content of a file, taken from `overrides` (keyed by canonical path) when present
+pub(crate) fn read_content(
+    path: &Path,
+    overrides: Option<&HashMap<PathBuf, String>>,
+) -> Option<String> {
+    // Canonicalize the path for lookup to match the keys in overrides
+    if let Some(map) = overrides {
+        let lookup_path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if let Some(content) = map.get(&lookup_path) {
+            return Some(content.clone());
         }
     }
-    (all_blocks, all_refs)
+    fs::read_to_string(path).ok() // Silence read errors in core
 }
 
 #[cfg(test)]
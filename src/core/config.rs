@@ -3,7 +3,8 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-use crate::core::error::Result;
+use crate::core::error::{Error, Result};
+use crate::core::types::Severity;
 
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct Config {
@@ -11,6 +12,35 @@ pub struct Config {
     pub nodes: HashMap<String, NodeConfig>,
     #[serde(default)]
     pub graph: GraphConfig,
+    /// Per-rule severity overrides, keyed by rule code (e.g. `DG004 = "warning"`)
+    #[serde(default)]
+    pub rules: HashMap<String, RuleLevel>,
+    #[serde(default)]
+    pub cache: CacheConfig,
+}
+
+/// Configured level of a rule. `Off` disables the rule.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Error,
+    Warning,
+    Info,
+    Hint,
+    Off,
+}
+
+impl RuleLevel {
+    /// The severity reported for this level, or `None` when the rule is disabled
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            RuleLevel::Error => Some(Severity::Error),
+            RuleLevel::Warning => Some(Severity::Warning),
+            RuleLevel::Info => Some(Severity::Info),
+            RuleLevel::Hint => Some(Severity::Hint),
+            RuleLevel::Off => None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Default, Clone)]
@@ -23,6 +53,29 @@ pub struct GraphConfig {
     pub ignore: Vec<String>,
 }
 
+/// On-disk cache of parsed documents, disabled unless `enabled = true`
+#[derive(Debug, Deserialize, Clone)]
+pub struct CacheConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cache directory, relative to the workspace root
+    #[serde(default = "default_cache_dir")]
+    pub dir: std::path::PathBuf,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: default_cache_dir(),
+        }
+    }
+}
+
+fn default_cache_dir() -> std::path::PathBuf {
+    std::path::PathBuf::from(".docgraph/cache")
+}
+
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct NodeConfig {
     pub desc: String,
@@ -58,8 +111,7 @@ impl Config {
             let config_path = dir.join("docgraph.toml");
             if config_path.exists() {
                 let content = fs::read_to_string(&config_path)?;
-                let config: Config = toml::from_str(&content)?;
-                return Ok(config);
+                return Self::parse(&content);
             }
             current = dir.parent();
         }
@@ -68,12 +120,69 @@ impl Config {
         let cwd_config = Path::new("docgraph.toml");
         if cwd_config.exists() {
             let content = fs::read_to_string(cwd_config)?;
-            let config: Config = toml::from_str(&content)?;
-            return Ok(config);
+            return Self::parse(&content);
         }
 
         Ok(Config::default())
     }
+
+    /// Parse and validate the content of a docgraph.toml
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Config = toml::from_str(content)?;
+
+        let known: Vec<&str> = crate::core::rules::get_all_rules()
+            .iter()
+            .map(|r| r.code)
+            .collect();
+        let mut unknown: Vec<&String> = config
+            .rules
+            .keys()
+            .filter(|code| !known.iter().any(|k| k.eq_ignore_ascii_case(code)))
+            .collect();
+        unknown.sort();
+        if let Some(code) = unknown.first() {
+            return Err(Error::InvalidConfig(format!(
+                "unknown rule '{}' in [rules]",
+                code
+            )));
+        }
+
+        Ok(config)
+    }
+
+    /// The `rel` label configured for an edge from `from_type` to `to_type`, looked up first
+    /// in the outgoing rules of the source type, then in the incoming rules of the target type
+    pub fn relationship_rel(&self, from_type: &str, to_type: &str) -> Option<&str> {
+        if let Some(node_conf) = self.nodes.get(from_type) {
+            for rule in &node_conf.rules {
+                if rule.dir == "to"
+                    && rule.targets.iter().any(|t| t == to_type)
+                    && let Some(ref rel_val) = rule.rel
+                {
+                    return Some(rel_val);
+                }
+            }
+        }
+        if let Some(node_conf) = self.nodes.get(to_type) {
+            for rule in &node_conf.rules {
+                if rule.dir == "from"
+                    && rule.targets.iter().any(|t| t == from_type)
+                    && let Some(ref rel_val) = rule.rel
+                {
+                    return Some(rel_val);
+                }
+            }
+        }
+        None
+    }
+
+    /// The configured level of a rule, if any
+    pub fn rule_level(&self, code: &str) -> Option<RuleLevel> {
+        self.rules
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(code))
+            .map(|(_, level)| *level)
+    }
 }
 
 #[cfg(test)]
@@ -81,6 +190,7 @@ mod tests {
     use super::*;
     use std::fs::File;
     use std::io::Write;
+    use std::path::PathBuf;
     use tempfile::tempdir;
 
     #[test]
@@ -120,6 +230,30 @@ mod tests {
         assert_eq!(config.nodes["REQ"].desc, "Requirement");
     }
 
+    #[test]
+    fn test_parse_rule_levels() {
+        let config = Config::parse("[rules]\nDG004 = \"warning\"\ndg007 = \"off\"\n").unwrap();
+        assert_eq!(config.rule_level("DG004"), Some(RuleLevel::Warning));
+        assert_eq!(config.rule_level("DG007"), Some(RuleLevel::Off));
+        assert_eq!(config.rule_level("DG001"), None);
+        assert_eq!(RuleLevel::Off.severity(), None);
+        assert_eq!(RuleLevel::Hint.severity(), Some(Severity::Hint));
+
+        assert!(Config::parse("[rules]\nDG999 = \"off\"\n").is_err());
+        assert!(Config::parse("[rules]\nDG001 = \"fatal\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        let config = Config::parse("").unwrap();
+        assert!(!config.cache.enabled);
+        assert_eq!(config.cache.dir, PathBuf::from(".docgraph/cache"));
+
+        let config = Config::parse("[cache]\nenabled = true\ndir = \"tmp/cache\"\n").unwrap();
+        assert!(config.cache.enabled);
+        assert_eq!(config.cache.dir, PathBuf::from("tmp/cache"));
+    }
+
     #[test]
     fn test_load_config_malformed() {
         let dir = tempdir().unwrap();
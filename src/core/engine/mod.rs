@@ -1,12 +1,21 @@
 use crate::core::config::Config;
 use crate::core::parser::ast;
 use crate::core::types::SpecBlock;
-use std::collections::HashMap;
+use regex::Regex;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+mod value;
+
+pub use value::{PathValue, RelationshipValue, Value};
 
 #[derive(Debug, Clone)]
 pub struct QueryResult {
     pub columns: Vec<String>,
-    pub rows: Vec<Vec<String>>,
+    pub rows: Vec<Vec<Value>>,
+    /// Reasons why the rows may be incomplete
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -17,63 +26,49 @@ pub enum EntityId {
         to_idx: usize,
         rel: String,
     },
+    /// Relationships matched by a variable-length pattern, in path order
+    Relationships(Vec<EntityId>),
+    /// A named path: the matched nodes and the relationships between them
+    Path {
+        nodes: Vec<usize>,
+        relationships: Vec<EntityId>,
+    },
 }
 
 type Bindings = HashMap<String, EntityId>;
 
+/// A node reached while walking a path, with the relationship followed to reach it
+type Hop = (usize, EntityId);
+
+/// The constraints of a depth-first path search
+struct Walk<'a> {
+    rel_pat: &'a ast::RelationshipPattern,
+    min: usize,
+    max: usize,
+    end: &'a dyn Fn(usize) -> bool,
+    steps: Cell<usize>,
+}
+
+/// Most paths listed from one start node for a path or relationship list variable. Dense
+/// graphs have exponentially many paths, so the rest are dropped with a warning.
+const MAX_PATHS_PER_NODE: usize = 1000;
+
+/// Most relationships followed while listing the paths from one start node, so that a
+/// search finding few matching paths in a dense graph also ends
+const MAX_WALK_STEPS: usize = 100_000;
+
 pub fn execute_query(query: &ast::Query, nodes: &[SpecBlock], config: &Config) -> QueryResult {
     // 1. Match patterns
+    let graph = Graph::new(nodes, config);
     let mut bindings_list: Vec<Bindings> = vec![HashMap::new()];
-
     for pattern_part in &query.match_clause.patterns {
-        let mut last_node_variable: Option<String> = None;
-
-        for chain in &pattern_part.chains {
-            match chain {
-                ast::PatternChain::Node(node_pat) => {
-                    // Ensure node has a variable for later reference (internal or explicit)
-                    // If explicit, use it. If not, we still need to track it for next steps?
-                    // Actually match_node_pattern binds explicit vars.
-                    // If implicit (no var), we can't key it in `Bindings`.
-                    // This is a limitation: Path traversal requires explicit variables or internal unique IDs.
-                    // For this MVP, let's fallback to requiring variables for path start.
-
-                    if let Some(ref v) = node_pat.variable {
-                        last_node_variable = Some(v.clone());
-                    }
-
-                    bindings_list = match_node_pattern(node_pat, nodes, bindings_list);
-                }
-                ast::PatternChain::Relationship(rel_pat, node_pat) => {
-                    if let Some(ref start_var) = last_node_variable {
-                        bindings_list = match_relationship_pattern(
-                            start_var,
-                            rel_pat,
-                            node_pat,
-                            nodes,
-                            config,
-                            bindings_list,
-                        );
-
-                        if let Some(ref v) = node_pat.variable {
-                            last_node_variable = Some(v.clone());
-                        }
-                    } else {
-                        // Error or panic? For MVP, skip if start node has no variable.
-                        // Ideally we should auto-generate variables during parsing or here.
-                        eprintln!(
-                            "Warning: Relationship pattern requires start node to have a variable."
-                        );
-                    }
-                }
-            }
-        }
+        bindings_list = match_pattern_part(pattern_part, &graph, bindings_list);
     }
 
     // 2. Filter with WHERE
     if let Some(where_clause) = &query.where_clause {
         bindings_list.retain(|bindings| {
-            evaluate_expression(&where_clause.expression, bindings, nodes, config)
+            evaluate_expression(&where_clause.expression, Scope::Row(bindings), &graph).is_truthy()
         });
     }
 
@@ -81,16 +76,16 @@ pub fn execute_query(query: &ast::Query, nodes: &[SpecBlock], config: &Config) -
     let mut expanded_columns = Vec::new();
     let mut item_projections = Vec::new(); // Store closure or flag to know how to expand each item
 
+    let node_variables = node_variables(&query.match_clause);
     for item in &query.return_clause.items {
-        match &item.expression {
-            ast::Expression::Comparison(comp)
-                if comp.operator.is_none()
-                    && comp.right.is_none()
-                    && comp.left.property.is_none()
-                    && item.alias.is_none() =>
+        match bare_variable(&item.expression) {
+            Some(pv)
+                if pv.property.is_none()
+                    && item.alias.is_none()
+                    && node_variables.contains(pv.variable.as_str()) =>
             {
                 // Expand node variable (only if NO alias and NO property)
-                let var = &comp.left.variable;
+                let var = &pv.variable;
                 expanded_columns.push(format!("{}.id", var));
                 expanded_columns.push(format!("{}.type", var));
                 expanded_columns.push(format!("{}.name", var));
@@ -101,68 +96,572 @@ pub fn execute_query(query: &ast::Query, nodes: &[SpecBlock], config: &Config) -
             }
             _ => {
                 // Single column (with or without alias)
-                if let Some(ref alias) = item.alias {
-                    expanded_columns.push(alias.clone());
-                } else {
-                    match &item.expression {
-                        ast::Expression::Comparison(comp)
-                            if comp.operator.is_none() && comp.right.is_none() =>
-                        {
-                            if let Some(ref prop) = comp.left.property {
-                                expanded_columns.push(format!("{}.{}", comp.left.variable, prop));
-                            } else {
-                                expanded_columns.push(comp.left.variable.clone());
-                            }
-                        }
-                        _ => expanded_columns.push("expression".to_string()),
-                    }
-                }
+                let name = item
+                    .alias
+                    .clone()
+                    .or_else(|| expression_name(&item.expression))
+                    .unwrap_or_else(|| "expression".to_string());
+                expanded_columns.push(name);
                 item_projections.push(Projection::Single(item.expression.clone()));
             }
         }
     }
 
-    let mut rows = Vec::new();
-    for bindings in bindings_list {
-        let mut row = Vec::new();
-        for proj in &item_projections {
-            match proj {
-                Projection::Single(expr) => {
-                    row.push(evaluate_expression_value(expr, &bindings, nodes, config));
-                }
-                Projection::Node(var) => {
-                    if let Some(EntityId::Node(idx)) = bindings.get(var) {
-                        let node = &nodes[*idx];
-                        row.push(node.id.clone());
-                        row.push(node.node_type.clone());
-                        row.push(node.name.clone().unwrap_or_else(|| "null".to_string()));
-                        row.push(node.file_path.to_string_lossy().to_string());
-                        row.push(node.line_start.to_string());
-                        row.push(node.content.clone());
+    // Each row keeps the bindings it was projected from, for ORDER BY
+    let mut rows: Vec<(Vec<Value>, Vec<Bindings>)> =
+        if item_projections.iter().any(Projection::is_aggregate) {
+            aggregate_rows(&item_projections, bindings_list, &graph)
+        } else {
+            bindings_list
+                .into_iter()
+                .map(|bindings| {
+                    let row = item_projections
+                        .iter()
+                        .flat_map(|proj| project(proj, &bindings, &graph))
+                        .collect();
+                    (row, vec![bindings])
+                })
+                .collect()
+        };
+
+    // 4. DISTINCT, ORDER BY, SKIP and LIMIT
+    let ret = &query.return_clause;
+    if ret.distinct {
+        let mut seen = HashSet::new();
+        rows.retain(|(row, _)| seen.insert(row.clone()));
+    }
+
+    if !ret.order_by.is_empty() {
+        let mut keyed: Vec<_> = rows
+            .into_iter()
+            .map(|(row, group)| {
+                let keys: Vec<Value> = ret
+                    .order_by
+                    .iter()
+                    .map(|item| {
+                        sort_value(&item.expression, &expanded_columns, &row, &group, &graph)
+                    })
+                    .collect();
+                (keys, (row, group))
+            })
+            .collect();
+        // Stable, so rows with equal keys keep their match order
+        keyed.sort_by(|(a, _), (b, _)| {
+            ret.order_by
+                .iter()
+                .zip(a.iter().zip(b))
+                .map(|(item, (a, b))| {
+                    let ordering = a.sort_cmp(b);
+                    if item.descending {
+                        ordering.reverse()
                     } else {
-                        for _ in 0..6 {
-                            row.push("null".to_string());
-                        }
+                        ordering
                     }
-                }
-            }
-        }
-        rows.push(row);
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        rows = keyed.into_iter().map(|(_, row)| row).collect();
+    }
+
+    let rows = rows
+        .into_iter()
+        .map(|(row, _)| row)
+        .skip(ret.skip.unwrap_or(0))
+        .take(ret.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    let mut warnings = Vec::new();
+    if graph.truncated.get() {
+        warnings.push(format!(
+            "path search stopped at its limit of {} paths or {} relationships from one node; \
+             results may be incomplete",
+            MAX_PATHS_PER_NODE, MAX_WALK_STEPS
+        ));
     }
 
     QueryResult {
         columns: expanded_columns,
         rows,
+        warnings,
+    }
+}
+
+/// The variable or property access an expression consists of, if any
+fn bare_variable(expr: &ast::Expression) -> Option<&ast::PropertyOrVariable> {
+    match expr {
+        ast::Expression::Comparison(ast::Comparison {
+            left: ast::Term::PropertyOrVariable(pv),
+            operator: None,
+            right: None,
+        }) => Some(pv),
+        _ => None,
+    }
+}
+
+/// Column name of a variable, property access or function call, e.g. `n.id` or
+/// `count(DISTINCT m.id)`
+fn expression_name(expr: &ast::Expression) -> Option<String> {
+    match expr {
+        ast::Expression::Comparison(ast::Comparison {
+            left,
+            operator: None,
+            right: None,
+        }) => term_name(left),
+        _ => None,
+    }
+}
+
+fn term_name(term: &ast::Term) -> Option<String> {
+    match term {
+        ast::Term::PropertyOrVariable(pv) => Some(match pv.property {
+            Some(ref prop) => format!("{}.{}", pv.variable, prop),
+            None => pv.variable.clone(),
+        }),
+        ast::Term::FunctionCall(call) => {
+            let args = if call.args.is_empty() {
+                "*".to_string()
+            } else {
+                call.args
+                    .iter()
+                    .map(expression_name)
+                    .collect::<Option<Vec<_>>>()?
+                    .join(", ")
+            };
+            let distinct = if call.distinct { "DISTINCT " } else { "" };
+            Some(format!("{}({}{})", call.name, distinct, args))
+        }
+        ast::Term::Literal(_) => None,
     }
 }
 
+/// Value of an ORDER BY expression. Names of returned columns (including aliases) refer to
+/// the projected value; anything else is evaluated against the bindings of the row.
+fn sort_value(
+    expr: &ast::Expression,
+    columns: &[String],
+    row: &[Value],
+    group: &[Bindings],
+    graph: &Graph,
+) -> Value {
+    expression_name(expr)
+        .and_then(|name| columns.iter().position(|c| *c == name))
+        .map(|i| row[i].clone())
+        .unwrap_or_else(|| evaluate_expression(expr, Scope::Group(group), graph))
+}
+
 enum Projection {
     Single(ast::Expression),
     Node(String),
 }
 
+impl Projection {
+    fn is_aggregate(&self) -> bool {
+        match self {
+            Projection::Single(expr) => expr.contains_aggregate(),
+            Projection::Node(_) => false,
+        }
+    }
+}
+
+/// Values of the columns of a projection for one match
+fn project(proj: &Projection, bindings: &Bindings, graph: &Graph) -> Vec<Value> {
+    match proj {
+        Projection::Single(expr) => {
+            vec![evaluate_expression(expr, Scope::Row(bindings), graph)]
+        }
+        Projection::Node(var) => {
+            if let Some(EntityId::Node(idx)) = bindings.get(var) {
+                let node = &graph.nodes[*idx];
+                vec![
+                    Value::from(node.id.clone()),
+                    Value::from(node.node_type.clone()),
+                    Value::from(node.name.clone()),
+                    Value::from(node.file_path.to_string_lossy().to_string()),
+                    Value::Int(node.line_start as i64),
+                    Value::from(node.content.clone()),
+                ]
+            } else {
+                vec![Value::Null; 6]
+            }
+        }
+    }
+}
+
+/// Group the matches by the values of the items without aggregate functions, then evaluate
+/// the aggregate items once per group
+fn aggregate_rows(
+    projections: &[Projection],
+    bindings_list: Vec<Bindings>,
+    graph: &Graph,
+) -> Vec<(Vec<Value>, Vec<Bindings>)> {
+    let mut groups: Vec<(Vec<Vec<Value>>, Vec<Bindings>)> = Vec::new();
+    let mut group_index: HashMap<Vec<Vec<Value>>, usize> = HashMap::new();
+    for bindings in bindings_list {
+        let key: Vec<Vec<Value>> = projections
+            .iter()
+            .filter(|proj| !proj.is_aggregate())
+            .map(|proj| project(proj, &bindings, graph))
+            .collect();
+        match group_index.get(&key) {
+            Some(&i) => groups[i].1.push(bindings),
+            None => {
+                group_index.insert(key.clone(), groups.len());
+                groups.push((key, vec![bindings]));
+            }
+        }
+    }
+
+    // Without grouping keys, aggregating no matches still returns one row (a count of 0)
+    if groups.is_empty() && projections.iter().all(Projection::is_aggregate) {
+        groups.push((Vec::new(), Vec::new()));
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, group)| {
+            let mut key = key.into_iter();
+            let mut row = Vec::new();
+            for proj in projections {
+                match proj {
+                    Projection::Single(expr) if proj.is_aggregate() => {
+                        row.push(evaluate_expression(expr, Scope::Group(&group), graph));
+                    }
+                    _ => row.extend(key.next().unwrap_or_default()),
+                }
+            }
+            (row, group)
+        })
+        .collect()
+}
+
+/// Variables bound to single nodes by the MATCH clause
+fn node_variables(match_clause: &ast::MatchClause) -> HashSet<&str> {
+    match_clause
+        .patterns
+        .iter()
+        .flat_map(|part| &part.chains)
+        .filter_map(|chain| match chain {
+            ast::PatternChain::Node(node_pat) | ast::PatternChain::Relationship(_, node_pat) => {
+                node_pat.variable.as_deref()
+            }
+        })
+        .collect()
+}
+
+/// The nodes and the edges between them, in both directions, with the `rel` configured for
+/// them
+struct Graph<'a> {
+    nodes: &'a [SpecBlock],
+    forward: Vec<Vec<(usize, String)>>,
+    backward: Vec<Vec<(usize, String)>>,
+    /// Set when a path search stopped at `MAX_PATHS_PER_NODE` or `MAX_WALK_STEPS`
+    truncated: Cell<bool>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(nodes: &'a [SpecBlock], config: &Config) -> Self {
+        let mut index = HashMap::new();
+        for (idx, node) in nodes.iter().enumerate() {
+            index.entry(node.id.as_str()).or_insert(idx);
+        }
+
+        let mut forward: Vec<Vec<(usize, String)>> = vec![Vec::new(); nodes.len()];
+        let mut backward: Vec<Vec<(usize, String)>> = vec![Vec::new(); nodes.len()];
+        for (idx, node) in nodes.iter().enumerate() {
+            for edge in &node.edges {
+                let Some(&target_idx) = index.get(edge.id.as_str()) else {
+                    continue;
+                };
+                // Several links to the same node are one relationship
+                if forward[idx].iter().any(|(t, _)| *t == target_idx) {
+                    continue;
+                }
+                // Find rel from docgraph.toml
+                let rel = config
+                    .relationship_rel(&node.node_type, &nodes[target_idx].node_type)
+                    .unwrap_or("references")
+                    .to_string();
+                forward[idx].push((target_idx, rel.clone()));
+                backward[target_idx].push((idx, rel));
+            }
+        }
+        Self {
+            nodes,
+            forward,
+            backward,
+            truncated: Cell::new(false),
+        }
+    }
+
+    /// Relationships from `node` that go in the direction of the pattern and have its type
+    fn neighbors(&self, node: usize, rel_pat: &ast::RelationshipPattern) -> Vec<Hop> {
+        let type_match = |rel: &String| rel_pat.rel_type.as_ref().is_none_or(|t| t == rel);
+        let mut hops = Vec::new();
+        if rel_pat.direction != ast::Direction::Left {
+            for (to_idx, rel) in self.forward[node].iter().filter(|(_, rel)| type_match(rel)) {
+                let relationship = EntityId::Relationship {
+                    from_idx: node,
+                    to_idx: *to_idx,
+                    rel: rel.clone(),
+                };
+                hops.push((*to_idx, relationship));
+            }
+        }
+        if rel_pat.direction != ast::Direction::Right {
+            for (from_idx, rel) in self.backward[node]
+                .iter()
+                .filter(|(_, rel)| type_match(rel))
+            {
+                let relationship = EntityId::Relationship {
+                    from_idx: *from_idx,
+                    to_idx: node,
+                    rel: rel.clone(),
+                };
+                hops.push((*from_idx, relationship));
+            }
+        }
+        hops
+    }
+
+    /// Nodes reachable from `start` with `min..=max` relationships, each listed once.
+    ///
+    /// Breadth-first over (node, depth), as only the end nodes are needed. Once a node is
+    /// reached at a depth of at least `min`, reaching it again deeper finds nothing new.
+    fn reachable(
+        &self,
+        start: usize,
+        rel_pat: &ast::RelationshipPattern,
+        min: usize,
+        max: usize,
+    ) -> Vec<usize> {
+        let mut reached = Vec::new();
+        let mut done = HashSet::new();
+        let mut seen = HashSet::from([(start, 0)]);
+        let mut frontier = vec![start];
+        let mut depth = 0;
+        loop {
+            let mut next_frontier = Vec::new();
+            for node in frontier {
+                if depth >= min {
+                    if !done.insert(node) {
+                        continue;
+                    }
+                    reached.push(node);
+                }
+                if depth == max {
+                    continue;
+                }
+                for (next, _) in self.neighbors(node, rel_pat) {
+                    // Below `min`, the same node must be walked again at each depth
+                    if depth + 1 >= min || seen.insert((next, depth + 1)) {
+                        next_frontier.push(next);
+                    }
+                }
+            }
+            if next_frontier.is_empty() {
+                break;
+            }
+            frontier = next_frontier;
+            depth += 1;
+        }
+        reached
+    }
+
+    /// Paths from `start` with `min..=max` relationships that never follow a relationship
+    /// twice and end on a node accepted by `end`, at most `MAX_PATHS_PER_NODE` of them
+    fn paths(
+        &self,
+        start: usize,
+        rel_pat: &ast::RelationshipPattern,
+        min: usize,
+        max: usize,
+        end: &dyn Fn(usize) -> bool,
+    ) -> Vec<Vec<Hop>> {
+        let mut paths = Vec::new();
+        let walk = Walk {
+            rel_pat,
+            min,
+            max,
+            end,
+            steps: Cell::new(0),
+        };
+        self.walk(&walk, start, &mut Vec::new(), &mut paths);
+        paths
+    }
+
+    /// Depth-first search for `paths`. Returns false once a limit is reached.
+    fn walk(
+        &self,
+        walk: &Walk,
+        node: usize,
+        path: &mut Vec<Hop>,
+        paths: &mut Vec<Vec<Hop>>,
+    ) -> bool {
+        if path.len() >= walk.min && (walk.end)(node) {
+            if paths.len() == MAX_PATHS_PER_NODE {
+                self.truncated.set(true);
+                return false;
+            }
+            paths.push(path.clone());
+        }
+        if path.len() == walk.max {
+            return true;
+        }
+        for (next, relationship) in self.neighbors(node, walk.rel_pat) {
+            if path.iter().any(|(_, r)| *r == relationship) {
+                continue;
+            }
+            if walk.steps.get() == MAX_WALK_STEPS {
+                self.truncated.set(true);
+                return false;
+            }
+            walk.steps.set(walk.steps.get() + 1);
+            path.push((next, relationship));
+            let more = self.walk(walk, next, path, paths);
+            path.pop();
+            if !more {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Shortest paths from `start` to every node reachable with at most `max` relationships,
+    /// keeping those with at least `min`. `ShortestPath::Single` keeps one path per node.
+    fn shortest_paths(
+        &self,
+        start: usize,
+        rel_pat: &ast::RelationshipPattern,
+        min: usize,
+        max: usize,
+        kind: ast::ShortestPath,
+    ) -> Vec<Vec<Hop>> {
+        // Breadth-first, recording every predecessor on a shortest path
+        let mut distance = HashMap::from([(start, 0)]);
+        let mut predecessors: HashMap<usize, Vec<Hop>> = HashMap::new();
+        let mut reached = vec![start];
+        let mut frontier = vec![start];
+        let mut depth = 0;
+        while !frontier.is_empty() && depth < max {
+            depth += 1;
+            let mut next_frontier = Vec::new();
+            for &node in &frontier {
+                for (next, relationship) in self.neighbors(node, rel_pat) {
+                    match distance.get(&next) {
+                        None => {
+                            distance.insert(next, depth);
+                            next_frontier.push(next);
+                            reached.push(next);
+                        }
+                        Some(&d) if d == depth => {}
+                        Some(_) => continue,
+                    }
+                    predecessors
+                        .entry(next)
+                        .or_default()
+                        .push((node, relationship));
+                }
+            }
+            frontier = next_frontier;
+        }
+
+        reached
+            .into_iter()
+            .filter(|node| distance[node] >= min)
+            .flat_map(|node| paths_to(node, start, &predecessors, kind))
+            .collect()
+    }
+}
+
+/// Shortest paths from `start` to `node`, rebuilt from the predecessors of each node
+fn paths_to(
+    node: usize,
+    start: usize,
+    predecessors: &HashMap<usize, Vec<Hop>>,
+    kind: ast::ShortestPath,
+) -> Vec<Vec<Hop>> {
+    if node == start {
+        return vec![Vec::new()];
+    }
+    let mut paths = Vec::new();
+    let preds = &predecessors[&node];
+    let preds = match kind {
+        ast::ShortestPath::Single => &preds[..1],
+        ast::ShortestPath::All => &preds[..],
+    };
+    for (prev, relationship) in preds {
+        for mut path in paths_to(*prev, start, predecessors, kind) {
+            path.push((node, relationship.clone()));
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Match one comma-separated part of the MATCH clause, extending every binding with the
+/// nodes, relationships and path it matches
+fn match_pattern_part(
+    part: &ast::PatternPart,
+    graph: &Graph,
+    mut bindings_list: Vec<Bindings>,
+) -> Vec<Bindings> {
+    // Anonymous nodes get a variable for the pattern to continue from, which no query can
+    // name because of the space. They are dropped once the part is matched.
+    let mut anonymous = Vec::new();
+    let mut last_node_variable = String::new();
+
+    for (i, chain) in part.chains.iter().enumerate() {
+        let (rel_pat, node_pat) = match chain {
+            ast::PatternChain::Node(node_pat) => (None, node_pat),
+            ast::PatternChain::Relationship(rel_pat, node_pat) => (Some(rel_pat), node_pat),
+        };
+        let variable = node_pat.variable.clone().unwrap_or_else(|| {
+            let v = format!(" node{}", i);
+            anonymous.push(v.clone());
+            v
+        });
+
+        bindings_list = match rel_pat {
+            None => {
+                let mut next = match_node_pattern(node_pat, &variable, graph.nodes, bindings_list);
+                if let Some(ref path_var) = part.variable {
+                    for bindings in &mut next {
+                        if let Some(EntityId::Node(idx)) = bindings.get(&variable) {
+                            let path = EntityId::Path {
+                                nodes: vec![*idx],
+                                relationships: Vec::new(),
+                            };
+                            bindings.insert(path_var.clone(), path);
+                        }
+                    }
+                }
+                next
+            }
+            Some(rel_pat) => match_relationship_pattern(
+                &last_node_variable,
+                rel_pat,
+                node_pat,
+                &variable,
+                part,
+                graph,
+                bindings_list,
+            ),
+        };
+        last_node_variable = variable;
+    }
+
+    for bindings in &mut bindings_list {
+        for v in &anonymous {
+            bindings.remove(v);
+        }
+    }
+    bindings_list
+}
+
 fn match_node_pattern(
     node_pat: &ast::NodePattern,
+    variable: &str,
     nodes: &[SpecBlock],
     current_bindings: Vec<Bindings>,
 ) -> Vec<Bindings> {
@@ -170,313 +669,381 @@ fn match_node_pattern(
 
     for bindings in current_bindings {
         for (i, node) in nodes.iter().enumerate() {
-            // Check labels
-            let label_match = if node_pat.labels.is_empty() {
-                true
-            } else {
-                node_pat.labels.contains(&node.node_type)
-            };
-
-            if !label_match {
+            if !labels_match(node_pat, node) {
                 continue;
             }
 
             // Bind variable
-            if let Some(ref var) = node_pat.variable {
-                if let Some(entity) = bindings.get(var) {
-                    if let EntityId::Node(prev_idx) = entity {
-                        if *prev_idx != i {
-                            continue;
-                        }
-                        next_bindings.push(bindings.clone());
-                    } else {
-                        // Var already bound to non-node?
-                        continue;
-                    }
-                } else {
+            match bindings.get(variable) {
+                Some(EntityId::Node(prev_idx)) if *prev_idx == i => {
+                    next_bindings.push(bindings.clone());
+                }
+                // Already bound to another node, or to a non-node
+                Some(_) => continue,
+                None => {
                     let mut new_bindings = bindings.clone();
-                    new_bindings.insert(var.clone(), EntityId::Node(i));
+                    new_bindings.insert(variable.to_string(), EntityId::Node(i));
                     next_bindings.push(new_bindings);
                 }
-            } else {
-                next_bindings.push(bindings.clone());
             }
         }
     }
     next_bindings
 }
 
+fn labels_match(node_pat: &ast::NodePattern, node: &SpecBlock) -> bool {
+    node_pat.labels.is_empty() || node_pat.labels.contains(&node.node_type)
+}
+
 fn match_relationship_pattern(
     start_node_var: &str,
     rel_pat: &ast::RelationshipPattern,
     end_node_pat: &ast::NodePattern,
-    nodes: &[SpecBlock],
-    config: &Config,
+    end_node_var: &str,
+    part: &ast::PatternPart,
+    graph: &Graph,
     current_bindings: Vec<Bindings>,
 ) -> Vec<Bindings> {
     let mut next_bindings = Vec::new();
 
-    // Build adjacency maps with rel type
-    // key: source_idx, value: (target_idx, rel)
-    let mut forward_adj: HashMap<usize, Vec<(usize, String)>> = HashMap::new();
-    let mut backward_adj: HashMap<usize, Vec<(usize, String)>> = HashMap::new();
-
-    for (idx, node) in nodes.iter().enumerate() {
-        for edge in &node.edges {
-            if let Some(target_idx) = nodes.iter().position(|n| n.id == edge.id) {
-                let target_node = &nodes[target_idx];
-                // Find rel from docgraph.toml
-                let rel = find_relationship_rel(config, &node.node_type, &target_node.node_type);
-
-                forward_adj
-                    .entry(idx)
-                    .or_default()
-                    .push((target_idx, rel.clone()));
-                backward_adj.entry(target_idx).or_default().push((idx, rel));
-            }
-        }
-    }
-
-    let min_hops = rel_pat.range.as_ref().and_then(|r| r.start).unwrap_or(1);
-    let max_hops = rel_pat.range.as_ref().and_then(|r| r.end).unwrap_or(1);
+    let (min_hops, max_hops) = match rel_pat.range {
+        Some(ref range) => (range.start.unwrap_or(1), range.end.unwrap_or(usize::MAX)),
+        None => (1, 1),
+    };
 
     for bindings in current_bindings {
-        if let Some(EntityId::Node(start_idx)) = bindings.get(start_node_var) {
-            let start_idx = *start_idx;
-            // BFS for reachability
-            let mut queue = std::collections::VecDeque::new();
-            queue.push_back((start_idx, 0, None::<String>)); // (curr, dist, last_rel)
-
-            let mut visited = std::collections::HashSet::new();
-            visited.insert((start_idx, 0));
-
-            while let Some((curr, dist, last_rel)) = queue.pop_front() {
-                if dist > max_hops {
-                    continue;
+        let Some(EntityId::Node(start_idx)) = bindings.get(start_node_var) else {
+            continue;
+        };
+        let end_matches = |end_idx: usize| {
+            labels_match(end_node_pat, &graph.nodes[end_idx])
+                && match bindings.get(end_node_var) {
+                    Some(EntityId::Node(prev_idx)) => *prev_idx == end_idx,
+                    Some(_) => false,
+                    None => true,
                 }
+        };
+        // Paths are only listed when a variable needs them; otherwise the end nodes suffice
+        let ends: Vec<(usize, Vec<Hop>)> = if part.shortest.is_some()
+            || part.variable.is_some()
+            || rel_pat.variable.is_some()
+        {
+            let paths = match part.shortest {
+                Some(kind) => graph.shortest_paths(*start_idx, rel_pat, min_hops, max_hops, kind),
+                None => graph.paths(*start_idx, rel_pat, min_hops, max_hops, &end_matches),
+            };
+            paths
+                .into_iter()
+                .map(|hops| (hops.last().map_or(*start_idx, |(node, _)| *node), hops))
+                .collect()
+        } else {
+            graph
+                .reachable(*start_idx, rel_pat, min_hops, max_hops)
+                .into_iter()
+                .map(|end_idx| (end_idx, Vec::new()))
+                .collect()
+        };
+
+        for (end_idx, hops) in ends {
+            if !end_matches(end_idx) {
+                continue;
+            }
 
-                if dist >= min_hops && dist > 0 {
-                    // Check rel_type if specified
-                    let rel_match = if let Some(ref target_rel_type) = rel_pat.rel_type {
-                        if let Some(ref actual_rel) = last_rel {
-                            actual_rel == target_rel_type
-                        } else {
-                            false
-                        }
-                    } else {
-                        true
-                    };
-
-                    if rel_match {
-                        // Check if current node matches end_node_pat
-                        let node = &nodes[curr];
-                        let label_match = if end_node_pat.labels.is_empty() {
-                            true
-                        } else {
-                            end_node_pat.labels.contains(&node.node_type)
-                        };
-
-                        if label_match {
-                            let mut new_bindings = bindings.clone();
-
-                            // Bind relationship variable if present (only for length 1 for now)
-                            // Cypher behavior: (n)-[r*1..2]->(m) makes r a list.
-                            // Our engine is MVP, let's only bind r if dist == 1.
-                            if dist == 1
-                                && let Some(ref r_var) = rel_pat.variable
-                                && let Some(ref rel_val) = last_rel
-                            {
-                                new_bindings.insert(
-                                    r_var.clone(),
-                                    EntityId::Relationship {
-                                        from_idx: start_idx,
-                                        to_idx: curr,
-                                        rel: rel_val.clone(),
-                                    },
-                                );
-                            }
-
-                            // Bind end variable
-                            if let Some(ref var) = end_node_pat.variable {
-                                if let Some(EntityId::Node(prev_idx)) = bindings.get(var) {
-                                    if *prev_idx == curr {
-                                        next_bindings.push(new_bindings);
-                                    }
-                                } else {
-                                    new_bindings.insert(var.clone(), EntityId::Node(curr));
-                                    next_bindings.push(new_bindings);
-                                }
-                            } else {
-                                next_bindings.push(new_bindings);
-                            }
-                        }
-                    }
-                }
+            let mut new_bindings = bindings.clone();
+            new_bindings.insert(end_node_var.to_string(), EntityId::Node(end_idx));
 
-                // Continue traversal
-                if dist < max_hops {
-                    let mut neighbors = Vec::new();
-                    match rel_pat.direction {
-                        ast::Direction::Right => {
-                            if let Some(n) = forward_adj.get(&curr) {
-                                neighbors.extend(n);
-                            }
-                        }
-                        ast::Direction::Left => {
-                            if let Some(n) = backward_adj.get(&curr) {
-                                neighbors.extend(n);
-                            }
-                        }
-                        ast::Direction::Both => {
-                            if let Some(n) = forward_adj.get(&curr) {
-                                neighbors.extend(n);
-                            }
-                            if let Some(n) = backward_adj.get(&curr) {
-                                neighbors.extend(n);
-                            }
-                        }
-                    }
+            // A variable-length relationship binds the list of relationships it followed
+            if let Some(ref r_var) = rel_pat.variable {
+                let mut relationships: Vec<EntityId> =
+                    hops.iter().map(|(_, r)| r.clone()).collect();
+                let relationship = if rel_pat.range.is_some() {
+                    EntityId::Relationships(relationships)
+                } else {
+                    relationships.remove(0)
+                };
+                new_bindings.insert(r_var.clone(), relationship);
+            }
 
-                    for (next, ctx) in neighbors {
-                        if !visited.contains(&(*next, dist + 1)) {
-                            visited.insert((*next, dist + 1));
-                            queue.push_back((*next, dist + 1, Some(ctx.clone())));
-                        }
-                    }
+            if let Some(ref path_var) = part.variable
+                && let Some(EntityId::Path {
+                    nodes: path_nodes,
+                    relationships,
+                }) = new_bindings.get_mut(path_var)
+            {
+                for (node, relationship) in hops {
+                    path_nodes.push(node);
+                    relationships.push(relationship);
                 }
             }
+
+            next_bindings.push(new_bindings);
         }
     }
 
     next_bindings
 }
 
-fn find_relationship_rel(config: &Config, from_type: &str, to_type: &str) -> String {
-    if let Some(node_conf) = config.nodes.get(from_type) {
-        for rule in &node_conf.rules {
-            if rule.dir == "to"
-                && rule.targets.contains(&to_type.to_string())
-                && let Some(ref rel_val) = rule.rel
-            {
-                return rel_val.clone();
+/// What an expression is evaluated against: the bindings of one match, or every match of
+/// a group when aggregating. Outside of aggregate functions, a group reads the bindings of
+/// its first match, which all matches of the group share for the returned items.
+#[derive(Clone, Copy)]
+enum Scope<'a> {
+    Row(&'a Bindings),
+    Group(&'a [Bindings]),
+}
+
+fn evaluate_expression(expr: &ast::Expression, scope: Scope, graph: &Graph) -> Value {
+    match expr {
+        ast::Expression::And(exprs) => Value::Bool(
+            exprs
+                .iter()
+                .all(|e| evaluate_expression(e, scope, graph).is_truthy()),
+        ),
+        ast::Expression::Or(exprs) => Value::Bool(
+            exprs
+                .iter()
+                .any(|e| evaluate_expression(e, scope, graph).is_truthy()),
+        ),
+        ast::Expression::Not(inner) => match evaluate_expression(inner, scope, graph) {
+            Value::Null => Value::Null,
+            value => Value::Bool(!value.is_truthy()),
+        },
+        ast::Expression::Pattern(part) => {
+            let bindings = match scope {
+                Scope::Row(bindings) => Some(bindings),
+                Scope::Group(group) => group.first(),
+            };
+            let bindings = bindings.cloned().unwrap_or_default();
+            Value::Bool(!match_pattern_part(part, graph, vec![bindings]).is_empty())
+        }
+        ast::Expression::Comparison(comp) => {
+            let left_val = evaluate_term(&comp.left, scope, graph);
+            match (&comp.operator, &comp.right) {
+                (Some(ast::ComparisonOperator::IsNull), _) => Value::Bool(left_val.is_null()),
+                (Some(ast::ComparisonOperator::IsNotNull), _) => Value::Bool(!left_val.is_null()),
+                (Some(op), Some(right_term)) => {
+                    let right_val = evaluate_term(right_term, scope, graph);
+                    compare(op, &left_val, &right_val)
+                }
+                _ => left_val,
             }
         }
     }
-    if let Some(node_conf) = config.nodes.get(to_type) {
-        for rule in &node_conf.rules {
-            if rule.dir == "from"
-                && rule.targets.contains(&from_type.to_string())
-                && let Some(ref rel_val) = rule.rel
-            {
-                return rel_val.clone();
+}
+
+/// Apply a comparison operator. Comparing with null, or ordering values of different
+/// types, yields null.
+fn compare(op: &ast::ComparisonOperator, left: &Value, right: &Value) -> Value {
+    if left.is_null() || right.is_null() {
+        return Value::Null;
+    }
+    let ordered = |accept: fn(Ordering) -> bool| {
+        left.partial_cmp_same_type(right)
+            .map_or(Value::Null, |ordering| Value::Bool(accept(ordering)))
+    };
+    match op {
+        ast::ComparisonOperator::Eq => Value::Bool(left == right),
+        ast::ComparisonOperator::NotEq => Value::Bool(left != right),
+        ast::ComparisonOperator::Contains => match (left, right) {
+            (Value::String(l), Value::String(r)) => Value::Bool(l.contains(r.as_str())),
+            _ => Value::Null,
+        },
+        ast::ComparisonOperator::StartsWith => match (left, right) {
+            (Value::String(l), Value::String(r)) => Value::Bool(l.starts_with(r.as_str())),
+            _ => Value::Null,
+        },
+        ast::ComparisonOperator::EndsWith => match (left, right) {
+            (Value::String(l), Value::String(r)) => Value::Bool(l.ends_with(r.as_str())),
+            _ => Value::Null,
+        },
+        ast::ComparisonOperator::Regex => match (left, right) {
+            (Value::String(l), Value::String(r)) => {
+                regex_match(r, l).map_or(Value::Null, Value::Bool)
             }
-        }
+            _ => Value::Null,
+        },
+        ast::ComparisonOperator::In => match right {
+            Value::List(items) => Value::Bool(items.contains(left)),
+            _ => Value::Null,
+        },
+        // Handled before the operands are compared
+        ast::ComparisonOperator::IsNull | ast::ComparisonOperator::IsNotNull => Value::Null,
+        ast::ComparisonOperator::Lt => ordered(Ordering::is_lt),
+        ast::ComparisonOperator::Gt => ordered(Ordering::is_gt),
+        ast::ComparisonOperator::LtEq => ordered(Ordering::is_le),
+        ast::ComparisonOperator::GtEq => ordered(Ordering::is_ge),
     }
-    "references".to_string()
 }
 
-fn evaluate_expression(
-    expr: &ast::Expression,
-    bindings: &Bindings,
-    nodes: &[SpecBlock],
-    config: &Config,
-) -> bool {
-    match expr {
-        ast::Expression::And(exprs) => exprs
-            .iter()
-            .all(|e| evaluate_expression(e, bindings, nodes, config)),
-        ast::Expression::Or(exprs) => exprs
-            .iter()
-            .any(|e| evaluate_expression(e, bindings, nodes, config)),
-        ast::Expression::Comparison(comp) => {
-            let left_val = evaluate_property_or_variable(&comp.left, bindings, nodes, config);
-            if let Some(right_term) = &comp.right {
-                let right_val = match right_term {
-                    ast::Term::Literal(lit) => match lit {
-                        ast::Literal::String(s) => s.clone(),
-                        ast::Literal::Number(n) => n.to_string(),
-                    },
-                    ast::Term::PropertyOrVariable(pv) => {
-                        evaluate_property_or_variable(pv, bindings, nodes, config)
-                    }
-                };
+/// Whether `pattern` matches the whole of `text`, or `None` when it is not a valid regex.
+/// Compiled patterns are kept, as the same pattern is usually matched against every row.
+fn regex_match(pattern: &str, text: &str) -> Option<bool> {
+    thread_local! {
+        static REGEXES: RefCell<HashMap<String, Option<Regex>>> = RefCell::new(HashMap::new());
+    }
+    REGEXES.with(|regexes| {
+        regexes
+            .borrow_mut()
+            .entry(pattern.to_string())
+            .or_insert_with(|| Regex::new(&format!("^(?:{})$", pattern)).ok())
+            .as_ref()
+            .map(|regex| regex.is_match(text))
+    })
+}
 
-                if let Some(op) = &comp.operator {
-                    match op {
-                        ast::ComparisonOperator::Eq => left_val == right_val,
-                        ast::ComparisonOperator::NotEq => left_val != right_val,
-                        ast::ComparisonOperator::Contains => left_val.contains(&right_val),
-                        ast::ComparisonOperator::Lt => left_val < right_val,
-                        ast::ComparisonOperator::Gt => left_val > right_val,
-                        ast::ComparisonOperator::LtEq => left_val <= right_val,
-                        ast::ComparisonOperator::GtEq => left_val >= right_val,
-                    }
-                } else {
-                    !left_val.is_empty() && left_val != "null"
+fn literal_value(literal: &ast::Literal) -> Value {
+    match literal {
+        ast::Literal::String(s) => Value::from(s.clone()),
+        ast::Literal::Number(n) => Value::Int(*n),
+        ast::Literal::List(items) => Value::List(items.iter().map(literal_value).collect()),
+    }
+}
+
+fn evaluate_term(term: &ast::Term, scope: Scope, graph: &Graph) -> Value {
+    match term {
+        ast::Term::Literal(literal) => literal_value(literal),
+        ast::Term::PropertyOrVariable(pv) => {
+            let bindings = match scope {
+                Scope::Row(bindings) => Some(bindings),
+                Scope::Group(group) => group.first(),
+            };
+            bindings.map_or(Value::Null, |bindings| {
+                evaluate_property_or_variable(pv, bindings, graph)
+            })
+        }
+        ast::Term::FunctionCall(call) if call.is_aggregate() => match scope {
+            Scope::Group(group) => aggregate(call, group, graph),
+            // Rejected by the parser in WHERE and inside other aggregates
+            Scope::Row(_) => Value::Null,
+        },
+        ast::Term::FunctionCall(call) => {
+            let arg = evaluate_expression(&call.args[0], scope, graph);
+            match (call.name.as_str(), arg) {
+                ("size", Value::List(items)) => Value::Int(items.len() as i64),
+                ("size", Value::String(s)) => Value::Int(s.chars().count() as i64),
+                ("length", Value::Path(path)) => Value::Int(path.relationships.len() as i64),
+                ("nodes", Value::Path(path)) => {
+                    Value::List(path.nodes.into_iter().map(Value::String).collect())
                 }
-            } else {
-                !left_val.is_empty() && left_val != "null"
+                ("relationships", Value::Path(path)) => Value::List(
+                    path.relationships
+                        .into_iter()
+                        .map(|r| Value::String(r.rel))
+                        .collect(),
+                ),
+                _ => Value::Null,
             }
         }
     }
 }
 
-fn evaluate_expression_value(
-    expr: &ast::Expression,
-    bindings: &Bindings,
-    nodes: &[SpecBlock],
-    config: &Config,
-) -> String {
-    match expr {
-        ast::Expression::Comparison(comp) => {
-            if comp.operator.is_none() && comp.right.is_none() {
-                evaluate_property_or_variable(&comp.left, bindings, nodes, config)
-            } else {
-                evaluate_expression(expr, bindings, nodes, config).to_string()
-            }
-        }
-        _ => "complex_expr".to_string(),
+/// Evaluate an aggregate function over the matches of a group. Null values are skipped.
+fn aggregate(call: &ast::FunctionCall, group: &[Bindings], graph: &Graph) -> Value {
+    let Some(arg) = call.args.first() else {
+        // count(*)
+        return Value::Int(group.len() as i64);
+    };
+
+    let mut values: Vec<Value> = group
+        .iter()
+        .map(|bindings| evaluate_expression(arg, Scope::Row(bindings), graph))
+        .filter(|value| !value.is_null())
+        .collect();
+    if call.distinct {
+        let mut seen = HashSet::new();
+        values.retain(|value| seen.insert(value.clone()));
+    }
+
+    match call.name.as_str() {
+        "count" => Value::Int(values.len() as i64),
+        "collect" => Value::List(values),
+        "min" => values
+            .into_iter()
+            .min_by(Value::sort_cmp)
+            .unwrap_or(Value::Null),
+        "max" => values
+            .into_iter()
+            .max_by(Value::sort_cmp)
+            .unwrap_or(Value::Null),
+        _ => Value::Null,
     }
 }
 
 fn evaluate_property_or_variable(
     pv: &ast::PropertyOrVariable,
     bindings: &Bindings,
-    nodes: &[SpecBlock],
-    _config: &Config,
-) -> String {
+    graph: &Graph,
+) -> Value {
     if let Some(entity) = bindings.get(&pv.variable) {
         match entity {
             EntityId::Node(idx) => {
-                let node = &nodes[*idx];
+                let node = &graph.nodes[*idx];
                 if let Some(ref prop) = pv.property {
                     match prop.as_str() {
-                        "id" => node.id.clone(),
-                        "node_type" => node.node_type.clone(),
-                        "type" => node.node_type.clone(),
-                        "name" => node.name.clone().unwrap_or_else(|| "null".to_string()),
-                        "file" => node.file_path.to_string_lossy().to_string(),
-                        "line" => node.line_start.to_string(),
-                        "content" => node.content.clone(),
-                        _ => "null".to_string(),
+                        "id" => Value::from(node.id.clone()),
+                        "node_type" => Value::from(node.node_type.clone()),
+                        "type" => Value::from(node.node_type.clone()),
+                        "name" => Value::from(node.name.clone()),
+                        "file" => Value::from(node.file_path.to_string_lossy().to_string()),
+                        "line" => Value::Int(node.line_start as i64),
+                        "content" => Value::from(node.content.clone()),
+                        _ => Value::Null,
                     }
                 } else {
-                    node.id.clone()
+                    Value::from(node.id.clone())
                 }
             }
             EntityId::Relationship { rel, .. } => {
                 if let Some(ref prop) = pv.property {
                     match prop.as_str() {
-                        "type" => rel.clone(),
-                        _ => "null".to_string(),
+                        "type" => Value::from(rel.clone()),
+                        _ => Value::Null,
                     }
                 } else {
-                    rel.clone()
+                    Value::from(rel.clone())
                 }
             }
+            EntityId::Relationships(relationships) if pv.property.is_none() => Value::List(
+                relationships
+                    .iter()
+                    .map(|r| {
+                        relationship_value(r, graph.nodes)
+                            .map_or(Value::Null, |r| Value::from(r.rel))
+                    })
+                    .collect(),
+            ),
+            EntityId::Path {
+                nodes: path_nodes,
+                relationships,
+            } if pv.property.is_none() => Value::Path(PathValue {
+                nodes: path_nodes
+                    .iter()
+                    .map(|idx| graph.nodes[*idx].id.clone())
+                    .collect(),
+                relationships: relationships
+                    .iter()
+                    .filter_map(|r| relationship_value(r, graph.nodes))
+                    .collect(),
+            }),
+            EntityId::Relationships(_) | EntityId::Path { .. } => Value::Null,
         }
     } else {
-        "null".to_string()
+        Value::Null
+    }
+}
+
+fn relationship_value(relationship: &EntityId, nodes: &[SpecBlock]) -> Option<RelationshipValue> {
+    match relationship {
+        EntityId::Relationship {
+            from_idx,
+            to_idx,
+            rel,
+        } => Some(RelationshipValue {
+            rel: rel.clone(),
+            from: nodes[*from_idx].id.clone(),
+            to: nodes[*to_idx].id.clone(),
+        }),
+        _ => None,
     }
 }
 
@@ -642,6 +1209,79 @@ mod tests {
         assert_eq!(result.rows.len(), 2);
     }
 
+    #[test]
+    fn test_execute_where_predicates() {
+        let mut nodes = mock_nodes();
+        nodes.push(SpecBlock {
+            id: "FR_002".to_string(),
+            node_type: "FR".to_string(),
+            file_path: PathBuf::from("test.md"),
+            ..Default::default()
+        });
+        let config = Config::default();
+        let ids = |q: &str| -> Vec<Value> {
+            let q = crate::core::parser::parse_query(q).unwrap();
+            execute_query(&q, &nodes, &config)
+                .rows
+                .into_iter()
+                .map(|row| row[0].clone())
+                .collect()
+        };
+
+        // Unrealized requirements: no module below them
+        assert_eq!(
+            ids("MATCH (n:FR) WHERE NOT (n)-->(:MOD) RETURN n.id"),
+            vec![Value::from("FR_002")]
+        );
+        assert_eq!(
+            ids("MATCH (n:FR) WHERE (:UC)-->(n)-[*]->(:MOD) RETURN n.id"),
+            vec![Value::from("FR_001")]
+        );
+
+        assert_eq!(
+            ids("MATCH (n) WHERE n.name IS NULL RETURN n.id"),
+            vec![Value::from("FR_002")]
+        );
+        assert_eq!(
+            ids("MATCH (n:FR) WHERE n.name IS NOT NULL RETURN n.id"),
+            vec![Value::from("FR_001")]
+        );
+        // NOT of null stays null, so the row is dropped
+        assert!(ids("MATCH (n:FR) WHERE NOT n.name = 'Authentication' RETURN n.id").is_empty());
+
+        assert_eq!(
+            ids("MATCH (n) WHERE n.id IN ['UC_001', 'MOD_001', 3] RETURN n.id ORDER BY n.id"),
+            vec![Value::from("MOD_001"), Value::from("UC_001")]
+        );
+        assert_eq!(
+            ids("MATCH (n) WHERE n.line IN [0] AND n.id STARTS WITH 'FR' RETURN n.id"),
+            vec![Value::from("FR_001"), Value::from("FR_002")]
+        );
+        assert_eq!(
+            ids("MATCH (n) WHERE n.name ENDS WITH 'Module' RETURN n.id"),
+            vec![Value::from("MOD_001")]
+        );
+
+        // Regular expressions match the whole value
+        assert_eq!(
+            ids("MATCH (n) WHERE n.id =~ 'FR_\\d+' RETURN n.id"),
+            vec![Value::from("FR_001"), Value::from("FR_002")]
+        );
+        assert!(ids("MATCH (n) WHERE n.id =~ 'FR' RETURN n.id").is_empty());
+
+        // Parentheses group before AND
+        assert_eq!(
+            ids(
+                "MATCH (n) WHERE (n.id = 'UC_001' OR n.id = 'FR_001') AND n.name CONTAINS 'Auth' RETURN n.id"
+            ),
+            vec![Value::from("FR_001")]
+        );
+        assert_eq!(
+            ids("MATCH (n) WHERE n.id <= 'FR_002' RETURN n.id"),
+            vec![Value::from("FR_001"), Value::from("FR_002")]
+        );
+    }
+
     #[test]
     fn test_execute_match_multiple_labels() {
         let nodes = mock_nodes();
@@ -785,6 +1425,298 @@ mod tests {
         assert_eq!(result.rows[0][0], "uses");
     }
 
+    #[test]
+    fn test_execute_order_skip_limit_distinct() {
+        let mut nodes = mock_nodes();
+        nodes[0].line_start = 10;
+        nodes[1].line_start = 9;
+        nodes[2].line_start = 100;
+        let config = Config::default();
+        let run = |q: &str| {
+            let q = crate::core::parser::parse_query(q).unwrap();
+            execute_query(&q, &nodes, &config).rows
+        };
+        let ids =
+            |rows: Vec<Vec<Value>>| rows.into_iter().map(|r| r[0].clone()).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(run("MATCH (n) RETURN n.id ORDER BY n.id")),
+            ["FR_001", "MOD_001", "UC_001"]
+        );
+        assert_eq!(
+            ids(run("MATCH (n) RETURN n.id AS id ORDER BY id DESC")),
+            ["UC_001", "MOD_001", "FR_001"]
+        );
+        // Integers sort numerically, also on expressions that are not returned
+        assert_eq!(
+            ids(run("MATCH (n) RETURN n.id ORDER BY n.line")),
+            ["FR_001", "UC_001", "MOD_001"]
+        );
+        assert_eq!(
+            ids(run("MATCH (n) RETURN n.id ORDER BY n.id SKIP 1 LIMIT 1")),
+            ["MOD_001"]
+        );
+        assert!(run("MATCH (n) RETURN n.id SKIP 5").is_empty());
+
+        // Every node pairs with every node in the same file: one distinct file
+        assert_eq!(run("MATCH (a), (b) RETURN a.file").len(), 9);
+        assert_eq!(run("MATCH (a), (b) RETURN DISTINCT a.file").len(), 1);
+    }
+
+    #[test]
+    fn test_execute_aggregation() {
+        let nodes = mock_nodes();
+        let config = Config::default();
+        let run = |q: &str| {
+            let q = crate::core::parser::parse_query(q).unwrap();
+            execute_query(&q, &nodes, &config)
+        };
+
+        let result = run("MATCH (n) RETURN count(*), min(n.id), max(n.id), size(collect(n.id))");
+        assert_eq!(
+            result.columns,
+            ["count(*)", "min(n.id)", "max(n.id)", "size(collect(n.id))"]
+        );
+        assert_eq!(
+            result.rows,
+            [vec![
+                Value::Int(3),
+                Value::from("FR_001"),
+                Value::from("UC_001"),
+                Value::Int(3)
+            ]]
+        );
+
+        // Aggregating no matches still returns a count
+        assert_eq!(
+            run("MATCH (n:XYZ) RETURN count(*)").rows,
+            [vec![Value::Int(0)]]
+        );
+        assert!(run("MATCH (n:XYZ) RETURN n.id, count(*)").rows.is_empty());
+
+        // Grouped by the other items, in match order unless sorted
+        let result = run(
+            "MATCH (a)-[*1..2]->(b) RETURN a.id, count(b) AS n, collect(b.id) AS ids ORDER BY n",
+        );
+        assert_eq!(
+            result.rows,
+            [
+                vec![
+                    Value::from("FR_001"),
+                    Value::Int(1),
+                    Value::List(vec![Value::from("MOD_001")])
+                ],
+                vec![
+                    Value::from("UC_001"),
+                    Value::Int(2),
+                    Value::List(vec![Value::from("FR_001"), Value::from("MOD_001")])
+                ],
+            ]
+        );
+
+        let result = run("MATCH (a), (b) RETURN count(a.file), count(DISTINCT a.file)");
+        assert_eq!(result.rows, [vec![Value::Int(9), Value::Int(1)]]);
+    }
+
+    #[test]
+    fn test_execute_typed_values() {
+        let mut nodes = mock_nodes();
+        nodes[0].line_start = 10;
+        nodes[1].line_start = 9;
+        nodes[2].name = None;
+        let config = Config::default();
+        let run = |q: &str| {
+            let q = crate::core::parser::parse_query(q).unwrap();
+            execute_query(&q, &nodes, &config).rows
+        };
+
+        assert_eq!(
+            run("MATCH (n) WHERE n.line > 9 RETURN n.id, n.line"),
+            [vec![Value::from("UC_001"), Value::Int(10)]]
+        );
+        // Strings are not numbers, and comparisons with a missing value never match
+        assert!(run("MATCH (n) WHERE n.line = \"10\" RETURN n.id").is_empty());
+        assert_eq!(run("MATCH (n) WHERE n.name <> \"x\" RETURN n.id").len(), 2);
+        assert_eq!(
+            run("MATCH (n:MOD) RETURN n.name, n.id = \"MOD_001\""),
+            [vec![Value::Null, Value::Bool(true)]]
+        );
+    }
+
+    #[test]
+    fn test_execute_paths() {
+        let nodes = mock_nodes();
+        let config = Config::default();
+        let run = |q: &str| {
+            let q = crate::core::parser::parse_query(q).unwrap();
+            execute_query(&q, &nodes, &config)
+        };
+
+        let result =
+            run("MATCH p = (u:UC)-[*]->(m:MOD) RETURN p, length(p), nodes(p), relationships(p)");
+        assert_eq!(
+            result.columns,
+            ["p", "length(p)", "nodes(p)", "relationships(p)"]
+        );
+        assert_eq!(result.rows.len(), 1);
+        let row = &result.rows[0];
+        assert_eq!(
+            row[0].to_string(),
+            "(UC_001)-[references]->(FR_001)-[references]->(MOD_001)"
+        );
+        assert_eq!(row[1], Value::Int(2));
+        assert_eq!(row[2].to_string(), "[UC_001, FR_001, MOD_001]");
+        assert_eq!(row[3].to_string(), "[references, references]");
+
+        let result = run("MATCH p = (m:MOD)<-[r*]-(u:UC) RETURN p, r");
+        assert_eq!(
+            result.rows[0][0].to_string(),
+            "(MOD_001)<-[references]-(FR_001)<-[references]-(UC_001)"
+        );
+        assert_eq!(result.rows[0][1].to_string(), "[references, references]");
+
+        // `*` has no upper bound, `*0..` also matches the start node
+        assert_eq!(run("MATCH (u:UC)-[*]->(n) RETURN n.id").rows.len(), 2);
+        assert_eq!(run("MATCH (u:UC)-[*0..]->(n) RETURN n.id").rows.len(), 3);
+        let result = run("MATCH p = (n:MOD)-[*0..]->(m) RETURN p, length(p)");
+        assert_eq!(
+            result.rows,
+            [vec![
+                Value::Path(PathValue {
+                    nodes: vec!["MOD_001".to_string()],
+                    relationships: Vec::new(),
+                }),
+                Value::Int(0)
+            ]]
+        );
+
+        // Patterns may start from an anonymous node
+        assert_eq!(
+            run("MATCH ()-[]->(m:MOD) RETURN m.id").rows,
+            [vec![Value::from("MOD_001")]]
+        );
+    }
+
+    #[test]
+    fn test_execute_shortest_paths() {
+        // A -> B -> D, A -> C -> D, A -> D2 -> E -> D
+        let node = |id: &str, targets: &[&str]| SpecBlock {
+            id: id.to_string(),
+            node_type: id[..1].to_string(),
+            edges: targets
+                .iter()
+                .map(|t| EdgeUse {
+                    id: t.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let nodes = vec![
+            node("A", &["B", "C", "D2"]),
+            node("B", &["D"]),
+            node("C", &["D"]),
+            node("D", &[]),
+            node("D2", &["E"]),
+            node("E", &["D"]),
+        ];
+        let config = Config::default();
+        let run = |q: &str| {
+            let q = crate::core::parser::parse_query(q).unwrap();
+            execute_query(&q, &nodes, &config)
+                .rows
+                .into_iter()
+                .map(|row| row[0].to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            run("MATCH p = (a)-[*]->(d) WHERE a.id = \"A\" AND d.id = \"D\" RETURN p").len(),
+            3
+        );
+        assert_eq!(
+            run("MATCH p = shortestPath((a:A)-[*]->(d)) WHERE d.id = \"D\" RETURN p"),
+            ["(A)-[references]->(B)-[references]->(D)"]
+        );
+        assert_eq!(
+            run("MATCH p = allShortestPaths((a:A)-[*]->(d)) WHERE d.id = \"D\" RETURN p"),
+            [
+                "(A)-[references]->(B)-[references]->(D)",
+                "(A)-[references]->(C)-[references]->(D)"
+            ]
+        );
+        // Shortest paths are searched within the range of the relationship
+        assert!(
+            run("MATCH p = shortestPath((a:A)-[*..1]->(d)) WHERE d.id = \"D\" RETURN p").is_empty()
+        );
+        assert_eq!(
+            run("MATCH (e:E), p = shortestPath((a:A)-[*]-(e)) RETURN length(p)"),
+            ["2"]
+        );
+    }
+
+    #[test]
+    fn test_execute_paths_dense_graph() {
+        let node = |id: &str, targets: &[&str]| SpecBlock {
+            id: id.to_string(),
+            node_type: id[..1].to_string(),
+            edges: targets
+                .iter()
+                .map(|t| EdgeUse {
+                    id: t.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        // S_00 leads into a clique of four N nodes before reaching X_00; T_00 leads into a
+        // clique of five M nodes that never reaches X_00
+        let mut nodes = vec![
+            node("S_00", &["N_00", "N_01", "N_02", "N_03", "X_00"]),
+            node("X_00", &[]),
+            node("T_00", &["M_00"]),
+        ];
+        for (prefix, size) in [("N", 4), ("M", 5)] {
+            let ids: Vec<String> = (0..size).map(|i| format!("{prefix}_{i:02}")).collect();
+            for id in &ids {
+                let targets: Vec<&str> = ids
+                    .iter()
+                    .filter(|t| *t != id)
+                    .map(String::as_str)
+                    .collect();
+                nodes.push(node(id, &targets));
+            }
+        }
+        let config = Config::default();
+        let run = |q: &str| {
+            let q = crate::core::parser::parse_query(q).unwrap();
+            execute_query(&q, &nodes, &config)
+        };
+
+        // Without a path variable each end node is matched once
+        assert_eq!(
+            run("MATCH (a:S)-[*]->(b) RETURN count(*)").rows,
+            [vec![Value::Int(5)]]
+        );
+        assert_eq!(
+            run("MATCH (a:S)-[*2..]-(b) RETURN count(*)").rows,
+            [vec![Value::Int(6)]]
+        );
+
+        // Paths not ending on an X node do not count towards the limit
+        let result = run("MATCH p = (a:S)-[*]->(b:X) RETURN length(p)");
+        assert_eq!(result.rows, [vec![Value::Int(1)]]);
+        assert!(result.warnings.is_empty());
+
+        // Stopping at a limit is reported
+        let result = run("MATCH p = (a:S)-[*]->(b) RETURN count(*)");
+        assert_eq!(result.rows, [vec![Value::Int(MAX_PATHS_PER_NODE as i64)]]);
+        assert_eq!(result.warnings.len(), 1);
+        let result = run("MATCH p = (a:T)-[*]->(b:X) RETURN count(*)");
+        assert_eq!(result.rows, [vec![Value::Int(0)]]);
+        assert_eq!(result.warnings.len(), 1);
+    }
+
     #[test]
     fn test_execute_match_mismatch() {
         let nodes = mock_nodes();
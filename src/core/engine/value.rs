@@ -0,0 +1,205 @@
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A value produced by a query: a property, a literal or the result of a function
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(untagged)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    String(String),
+    List(Vec<Value>),
+    Path(PathValue),
+}
+
+/// A matched path, shown as `(A)-[rel]->(B)<-[rel]-(C)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct PathValue {
+    /// IDs of the nodes along the path
+    pub nodes: Vec<String>,
+    /// Relationships between consecutive nodes, in their own direction
+    pub relationships: Vec<RelationshipValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct RelationshipValue {
+    #[serde(rename = "type")]
+    pub rel: String,
+    pub from: String,
+    pub to: String,
+}
+
+impl Value {
+    /// Whether a WHERE clause keeps a row evaluating to this value
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Int(_) => true,
+            Value::String(s) => !s.is_empty(),
+            Value::List(items) => !items.is_empty(),
+            Value::Path(_) => true,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == Value::Null
+    }
+
+    /// Order of values in ORDER BY, min and max. Values of different types are ordered
+    /// lists, paths, strings, booleans, then numbers, and null comes after everything else.
+    pub fn sort_cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::List(a), Value::List(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| a.sort_cmp(b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (Value::Path(a), Value::Path(b)) => a.nodes.cmp(&b.nodes),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+
+    /// Compare two values of the same type, or `None` when they cannot be compared
+    pub fn partial_cmp_same_type(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Null, _) | (_, Value::Null) => None,
+            _ if self.rank() == other.rank() => Some(self.sort_cmp(other)),
+            _ => None,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Value::List(_) => 0,
+            Value::Path(_) => 1,
+            Value::String(_) => 2,
+            Value::Bool(_) => 3,
+            Value::Int(_) => 4,
+            Value::Null => 5,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Int(n) => write!(f, "{}", n),
+            Value::String(s) => write!(f, "{}", s),
+            Value::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Path(path) => write!(f, "{}", path),
+        }
+    }
+}
+
+impl fmt::Display for PathValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            if let Some(r) = i.checked_sub(1).and_then(|i| self.relationships.get(i)) {
+                if r.to == *node {
+                    write!(f, "-[{}]->", r.rel)?;
+                } else {
+                    write!(f, "<-[{}]-", r.rel)?;
+                }
+            }
+            write!(f, "({})", node)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq<&str> for Value {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Value::String(s) if s == other)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<Option<String>> for Value {
+    fn from(s: Option<String>) -> Self {
+        s.map_or(Value::Null, Value::String)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_cmp() {
+        assert_eq!(Value::Int(9).sort_cmp(&Value::Int(10)), Ordering::Less);
+        assert_eq!(
+            Value::from("b").sort_cmp(&Value::from("a")),
+            Ordering::Greater
+        );
+        assert_eq!(Value::Null.sort_cmp(&Value::Int(1)), Ordering::Greater);
+        assert_eq!(Value::Null.sort_cmp(&Value::Null), Ordering::Equal);
+        assert_eq!(Value::from("9").sort_cmp(&Value::Int(1)), Ordering::Less);
+        assert_eq!(Value::from("9").partial_cmp_same_type(&Value::Int(1)), None);
+    }
+
+    #[test]
+    fn test_display_and_json() {
+        let list = Value::List(vec![Value::from("FR_001"), Value::Int(2), Value::Null]);
+        assert_eq!(list.to_string(), "[FR_001, 2, null]");
+        assert_eq!(
+            serde_json::to_value(&list).unwrap(),
+            serde_json::json!(["FR_001", 2, null])
+        );
+
+        let relationship = |rel: &str, from: &str, to: &str| RelationshipValue {
+            rel: rel.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let path = Value::Path(PathValue {
+            nodes: vec!["UC_1".into(), "FR_1".into(), "MOD_1".into()],
+            relationships: vec![
+                relationship("uses", "UC_1", "FR_1"),
+                relationship("implements", "MOD_1", "FR_1"),
+            ],
+        });
+        assert_eq!(
+            path.to_string(),
+            "(UC_1)-[uses]->(FR_1)<-[implements]-(MOD_1)"
+        );
+        assert_eq!(
+            serde_json::to_value(&path).unwrap(),
+            serde_json::json!({
+                "nodes": ["UC_1", "FR_1", "MOD_1"],
+                "relationships": [
+                    { "type": "uses", "from": "UC_1", "to": "FR_1" },
+                    { "type": "implements", "from": "MOD_1", "to": "FR_1" },
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use crate::core::config::Config;
+use crate::core::error::{Error, Result};
+use crate::core::types::SpecBlock;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Fill colors assigned to node types, in order of their sorted names
+const PALETTE: &[&str] = &[
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5",
+    "#d9d9d9", "#bc80bd",
+];
+
+/// A resolved edge between two spec blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// Relationship label from the matching `RuleConfig`, or "references"
+    pub rel: String,
+}
+
+/// The spec blocks and resolved edges to export
+#[derive(Debug, Clone)]
+pub struct ExportGraph<'a> {
+    pub nodes: Vec<&'a SpecBlock>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl<'a> ExportGraph<'a> {
+    /// Build the graph of all blocks. Links to unknown IDs are dropped, duplicate links
+    /// between the same pair of blocks are merged, and a duplicated ID keeps its first block.
+    pub fn build(blocks: &'a [SpecBlock], config: &Config) -> Self {
+        let mut nodes = Vec::new();
+        let mut by_id: HashMap<&str, &SpecBlock> = HashMap::new();
+        for block in blocks {
+            if !by_id.contains_key(block.id.as_str()) {
+                by_id.insert(&block.id, block);
+                nodes.push(block);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut edges = Vec::new();
+        for block in blocks {
+            for edge in &block.edges {
+                if let Some(target) = by_id.get(edge.id.as_str())
+                    && seen.insert((block.id.as_str(), target.id.as_str()))
+                {
+                    edges.push(GraphEdge {
+                        from: block.id.clone(),
+                        to: target.id.clone(),
+                        rel: config
+                            .relationship_rel(&block.node_type, &target.node_type)
+                            .unwrap_or("references")
+                            .to_string(),
+                    });
+                }
+            }
+        }
+
+        ExportGraph { nodes, edges }
+    }
+
+    /// Restrict the graph to the nodes within `depth` hops of `root`, following edges in
+    /// both directions
+    pub fn neighborhood(&self, root: &str, depth: usize) -> Result<Self> {
+        if !self.nodes.iter().any(|n| n.id == root) {
+            return Err(Error::Other(format!("node '{}' not found", root)));
+        }
+
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+        for edge in &self.edges {
+            adjacency.entry(&edge.from).or_default().push(&edge.to);
+            adjacency.entry(&edge.to).or_default().push(&edge.from);
+        }
+
+        let mut visited: HashSet<&str> = HashSet::from([root]);
+        let mut queue = VecDeque::from([(root, 0)]);
+        while let Some((id, dist)) = queue.pop_front() {
+            if dist == depth {
+                continue;
+            }
+            for next in adjacency.get(id).into_iter().flatten() {
+                if visited.insert(next) {
+                    queue.push_back((next, dist + 1));
+                }
+            }
+        }
+
+        Ok(ExportGraph {
+            nodes: self
+                .nodes
+                .iter()
+                .filter(|n| visited.contains(n.id.as_str()))
+                .copied()
+                .collect(),
+            edges: self
+                .edges
+                .iter()
+                .filter(|e| visited.contains(e.from.as_str()) && visited.contains(e.to.as_str()))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// Node types in sorted order
+    fn node_types(&self) -> Vec<&str> {
+        self.nodes
+            .iter()
+            .map(|n| n.node_type.as_str())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn colors(&self) -> HashMap<&str, &'static str> {
+        self.node_types()
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| (t, PALETTE[i % PALETTE.len()]))
+            .collect()
+    }
+}
+
+fn node_label(block: &SpecBlock) -> String {
+    match &block.name {
+        Some(name) => format!("{}\n{}", block.id, name),
+        None => block.id.clone(),
+    }
+}
+
+/// Render as a Graphviz DOT digraph with one cluster per node type
+pub fn to_dot(graph: &ExportGraph, config: &Config) -> String {
+    let colors = graph.colors();
+    let mut out = String::from("digraph docgraph {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\"];\n");
+
+    for node_type in graph.node_types() {
+        let label = match config.nodes.get(node_type) {
+            Some(conf) if !conf.desc.is_empty() => format!("{} ({})", node_type, conf.desc),
+            _ => node_type.to_string(),
+        };
+        out.push_str(&format!(
+            "  subgraph \"cluster_{}\" {{\n",
+            dot_escape(node_type)
+        ));
+        out.push_str(&format!("    label=\"{}\";\n", dot_escape(&label)));
+        for node in graph.nodes.iter().filter(|n| n.node_type == node_type) {
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", fillcolor=\"{}\"];\n",
+                dot_escape(&node.id),
+                dot_escape(&node_label(node)),
+                colors[node_type]
+            ));
+        }
+        out.push_str("  }\n");
+    }
+
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\"];\n",
+            dot_escape(&edge.from),
+            dot_escape(&edge.to),
+            dot_escape(&edge.rel)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Render as a Mermaid flowchart with one subgraph per node type
+pub fn to_mermaid(graph: &ExportGraph) -> String {
+    let colors = graph.colors();
+    // Mermaid identifiers are restricted, so nodes are referred to by index
+    let index: HashMap<&str, usize> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.id.as_str(), i))
+        .collect();
+
+    let mut out = String::from("graph LR\n");
+    for node_type in graph.node_types() {
+        out.push_str(&format!("  subgraph {}\n", mermaid_class(node_type)));
+        for node in graph.nodes.iter().filter(|n| n.node_type == node_type) {
+            out.push_str(&format!(
+                "    n{}[\"{}\"]\n",
+                index[node.id.as_str()],
+                mermaid_escape(&node_label(node)).replace('\n', "<br/>")
+            ));
+        }
+        out.push_str("  end\n");
+    }
+
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "  n{} -->|{}| n{}\n",
+            index[edge.from.as_str()],
+            mermaid_escape(&edge.rel),
+            index[edge.to.as_str()]
+        ));
+    }
+
+    for node_type in graph.node_types() {
+        let class = mermaid_class(node_type);
+        out.push_str(&format!(
+            "  classDef {} fill:{}\n",
+            class, colors[node_type]
+        ));
+        let members: Vec<String> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == node_type)
+            .map(|n| format!("n{}", index[n.id.as_str()]))
+            .collect();
+        out.push_str(&format!("  class {} {}\n", members.join(","), class));
+    }
+    out
+}
+
+fn mermaid_class(node_type: &str) -> String {
+    let cleaned: String = node_type
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("type_{}", cleaned)
+}
+
+fn mermaid_escape(s: &str) -> String {
+    s.replace('"', "#quot;").replace('|', "#124;")
+}
+
+/// Render as GraphML with `label`, `type`, `name`, `file` node data and `rel` edge data
+pub fn to_graphml(graph: &ExportGraph) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+    out.push_str("  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"file\" for=\"node\" attr.name=\"file\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"color\" for=\"node\" attr.name=\"color\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"rel\" for=\"edge\" attr.name=\"rel\" attr.type=\"string\"/>\n");
+    out.push_str("  <graph id=\"docgraph\" edgedefault=\"directed\">\n");
+
+    let colors = graph.colors();
+    for node in &graph.nodes {
+        out.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&node.id)));
+        out.push_str(&format!(
+            "      <data key=\"type\">{}</data>\n",
+            xml_escape(&node.node_type)
+        ));
+        if let Some(name) = &node.name {
+            out.push_str(&format!(
+                "      <data key=\"name\">{}</data>\n",
+                xml_escape(name)
+            ));
+        }
+        out.push_str(&format!(
+            "      <data key=\"file\">{}</data>\n",
+            xml_escape(&node.file_path.display().to_string())
+        ));
+        out.push_str(&format!(
+            "      <data key=\"color\">{}</data>\n",
+            colors[node.node_type.as_str()]
+        ));
+        out.push_str("    </node>\n");
+    }
+    for (i, edge) in graph.edges.iter().enumerate() {
+        out.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+            i,
+            xml_escape(&edge.from),
+            xml_escape(&edge.to)
+        ));
+        out.push_str(&format!(
+            "      <data key=\"rel\">{}</data>\n",
+            xml_escape(&edge.rel)
+        ));
+        out.push_str("    </edge>\n");
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+/// Escape text for XML and HTML, in content and in quoted attribute values
+pub(crate) fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Render as Cytoscape.js elements JSON
+pub fn to_cytoscape(graph: &ExportGraph) -> Value {
+    let colors = graph.colors();
+    let nodes: Vec<Value> = graph
+        .nodes
+        .iter()
+        .map(|n| {
+            json!({
+                "data": {
+                    "id": n.id,
+                    "label": n.id,
+                    "name": n.name,
+                    "type": n.node_type,
+                    "file": n.file_path.display().to_string(),
+                    "line": n.line_start,
+                    "color": colors[n.node_type.as_str()],
+                }
+            })
+        })
+        .collect();
+    let edges: Vec<Value> = graph
+        .edges
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            json!({
+                "data": {
+                    "id": format!("e{}", i),
+                    "source": e.from,
+                    "target": e.to,
+                    "rel": e.rel,
+                }
+            })
+        })
+        .collect();
+
+    json!({ "elements": { "nodes": nodes, "edges": edges } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{NodeConfig, RuleConfig};
+    use crate::core::types::EdgeUse;
+    use std::path::PathBuf;
+
+    fn block(id: &str, name: &str, targets: &[&str]) -> SpecBlock {
+        SpecBlock {
+            id: id.to_string(),
+            node_type: id.split(['-', '_']).next().unwrap().to_string(),
+            name: Some(name.to_string()),
+            edges: targets
+                .iter()
+                .map(|t| EdgeUse {
+                    id: t.to_string(),
+                    name: None,
+                    line: 1,
+                    col_start: 1,
+                    col_end: 1,
+                })
+                .collect(),
+            file_path: PathBuf::from("test.md"),
+            line_start: 1,
+            line_end: 1,
+            content: String::new(),
+        }
+    }
+
+    fn fixture() -> (Vec<SpecBlock>, Config) {
+        let blocks = vec![
+            block("FR-01", "Login", &["UC-01", "UC-01", "XX-99"]),
+            block("UC-01", "Sign in", &[]),
+            block("FR-02", "Logout", &["FR-01"]),
+            block("FR-03", "Audit", &["FR-02"]),
+        ];
+        let mut config = Config::default();
+        config.nodes.insert(
+            "FR".to_string(),
+            NodeConfig {
+                desc: "Functional Requirement".to_string(),
+                rules: vec![RuleConfig {
+                    dir: "to".to_string(),
+                    targets: vec!["UC".to_string()],
+                    rel: Some("derived_from".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        (blocks, config)
+    }
+
+    #[test]
+    fn test_build_resolves_rel_and_drops_broken_links() {
+        let (blocks, config) = fixture();
+        let graph = ExportGraph::build(&blocks, &config);
+
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(
+            graph.edges[0],
+            GraphEdge {
+                from: "FR-01".to_string(),
+                to: "UC-01".to_string(),
+                rel: "derived_from".to_string(),
+            }
+        );
+        assert_eq!(graph.edges[1].rel, "references");
+    }
+
+    #[test]
+    fn test_build_merges_duplicate_ids() {
+        let (mut blocks, config) = fixture();
+        let mut duplicate = blocks[0].clone();
+        duplicate.name = Some("Copy".to_string());
+        blocks.push(duplicate);
+        let graph = ExportGraph::build(&blocks, &config);
+
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.edges.len(), 3);
+        let mermaid = to_mermaid(&graph);
+        assert_eq!(mermaid.matches("n0[").count(), 1);
+        assert!(!mermaid.contains("Copy"));
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let (blocks, config) = fixture();
+        let graph = ExportGraph::build(&blocks, &config);
+
+        let sub = graph.neighborhood("FR-01", 1).unwrap();
+        let mut ids: Vec<&str> = sub.nodes.iter().map(|n| n.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["FR-01", "FR-02", "UC-01"]);
+        assert_eq!(sub.edges.len(), 2);
+
+        assert_eq!(graph.neighborhood("FR-01", 2).unwrap().nodes.len(), 4);
+        assert!(graph.neighborhood("NOPE", 1).is_err());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let (blocks, config) = fixture();
+        let dot = to_dot(&ExportGraph::build(&blocks, &config), &config);
+
+        assert!(dot.starts_with("digraph docgraph {"));
+        assert!(dot.contains("subgraph \"cluster_FR\""));
+        assert!(dot.contains("label=\"FR (Functional Requirement)\";"));
+        assert!(dot.contains("\"FR-01\" [label=\"FR-01\\nLogin\", fillcolor=\"#8dd3c7\"];"));
+        assert!(dot.contains("\"FR-01\" -> \"UC-01\" [label=\"derived_from\"];"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let (blocks, config) = fixture();
+        let mermaid = to_mermaid(&ExportGraph::build(&blocks, &config));
+
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("  subgraph type_UC\n    n1[\"UC-01<br/>Sign in\"]\n  end\n"));
+        assert!(mermaid.contains("  n0 -->|derived_from| n1\n"));
+        assert!(mermaid.contains("  class n0,n2,n3 type_FR\n"));
+    }
+
+    #[test]
+    fn test_to_graphml_and_cytoscape() {
+        let (blocks, config) = fixture();
+        let graph = ExportGraph::build(&blocks, &config);
+
+        let graphml = to_graphml(&graph);
+        assert!(graphml.contains("<node id=\"FR-01\">"));
+        assert!(graphml.contains("<edge id=\"e0\" source=\"FR-01\" target=\"UC-01\">"));
+        assert!(graphml.contains("<data key=\"rel\">derived_from</data>"));
+
+        let cy = to_cytoscape(&graph);
+        assert_eq!(cy["elements"]["nodes"].as_array().unwrap().len(), 4);
+        assert_eq!(cy["elements"]["edges"][0]["data"]["rel"], "derived_from");
+        assert_eq!(cy["elements"]["nodes"][1]["data"]["type"], "UC");
+    }
+}
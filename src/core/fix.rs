@@ -0,0 +1,251 @@
+use crate::core::error::{Error, Result};
+use crate::core::types::{Diagnostic, TextEdit};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The result of applying edits to one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub original: String,
+    pub updated: String,
+    /// Number of edits applied to this file
+    pub applied: usize,
+    /// Number of edits dropped because they overlapped an earlier edit
+    pub skipped: usize,
+}
+
+/// Collect the edits of all fixable diagnostics
+pub fn collect_edits(diagnostics: &[Diagnostic]) -> Vec<TextEdit> {
+    diagnostics
+        .iter()
+        .filter_map(|d| d.fix.as_ref())
+        .flat_map(|f| f.edits.iter().cloned())
+        .collect()
+}
+
+/// Compute the new content of every file touched by `edits` without writing anything.
+///
+/// Files are read from `overrides` when present (e.g. unsaved editor buffers), otherwise
+/// from disk. A file that does not exist yet is treated as empty, so edits can create it.
+pub fn plan_changes(
+    edits: &[TextEdit],
+    overrides: Option<&HashMap<PathBuf, String>>,
+) -> Result<Vec<FileChange>> {
+    let mut by_file: BTreeMap<&Path, Vec<&TextEdit>> = BTreeMap::new();
+    for edit in edits {
+        by_file.entry(edit.path.as_path()).or_default().push(edit);
+    }
+
+    let mut changes = Vec::new();
+    for (path, file_edits) in by_file {
+        let original = if let Some(map) = overrides
+            && let Some(content) = map.get(path)
+        {
+            content.clone()
+        } else if path.exists() {
+            fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+
+        let (updated, applied, skipped) = apply_edits(&original, &file_edits);
+        if updated != original || !path.exists() {
+            changes.push(FileChange {
+                path: path.to_path_buf(),
+                original,
+                updated,
+                applied,
+                skipped,
+            });
+        }
+    }
+    Ok(changes)
+}
+
+/// Apply edits to `content`. Returns the new content and the number of applied and skipped edits.
+///
+/// Edits are applied back to front so earlier offsets stay valid. Duplicate edits are
+/// applied once, and an edit overlapping one that was already accepted is skipped.
+pub fn apply_edits(content: &str, edits: &[&TextEdit]) -> (String, usize, usize) {
+    let mut resolved: Vec<(usize, usize, &str)> = edits
+        .iter()
+        .map(|e| {
+            let start = offset_at(content, e.range.start_line, e.range.start_col);
+            let end = offset_at(content, e.range.end_line, e.range.end_col).max(start);
+            (start, end, e.new_text.as_str())
+        })
+        .collect();
+    resolved.sort_by_key(|(start, end, _)| (*start, *end));
+    resolved.dedup();
+
+    let mut accepted: Vec<(usize, usize, &str)> = Vec::new();
+    let mut skipped = 0;
+    for edit in resolved {
+        match accepted.last() {
+            // An edit overlapping the previous one, or starting at the same point as an
+            // accepted insertion, has no well-defined result
+            Some(prev) if edit.0 < prev.1 || (edit.0 == prev.0 && edit.0 == prev.1) => {
+                skipped += 1;
+            }
+            _ => accepted.push(edit),
+        }
+    }
+
+    let mut result = content.to_string();
+    for (start, end, new_text) in accepted.iter().rev() {
+        // An insertion past the last line starts on a fresh line
+        if *start == result.len() && *start == *end && !result.is_empty() && !result.ends_with('\n')
+        {
+            result.push('\n');
+            result.push_str(new_text);
+        } else {
+            result.replace_range(*start..*end, new_text);
+        }
+    }
+
+    (result, accepted.len(), skipped)
+}
+
+/// Convert a 1-based line/column (byte) position into a byte offset, clamped to the content.
+fn offset_at(content: &str, line: usize, col: usize) -> usize {
+    let mut line_start = 0;
+    for _ in 1..line.max(1) {
+        match content[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return content.len(),
+        }
+    }
+    let line_end = content[line_start..]
+        .find('\n')
+        .map(|i| line_start + i)
+        .unwrap_or(content.len());
+    let mut offset = (line_start + col.saturating_sub(1)).min(line_end);
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// Write all changes, or none of them.
+///
+/// Every file is first written to a temporary sibling; only when all of them succeeded are
+/// they renamed over the originals.
+pub fn write_changes(changes: &[FileChange]) -> Result<()> {
+    let mut staged: Vec<(PathBuf, &Path)> = Vec::new();
+
+    for change in changes {
+        let tmp = temp_path(&change.path);
+        let result = change
+            .path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| fs::write(&tmp, &change.updated));
+
+        if let Err(e) = result {
+            let _ = fs::remove_file(&tmp);
+            for (t, _) in &staged {
+                let _ = fs::remove_file(t);
+            }
+            return Err(Error::Io(e));
+        }
+        staged.push((tmp, change.path.as_path()));
+    }
+
+    for (tmp, path) in &staged {
+        fs::rename(tmp, path)?;
+    }
+    Ok(())
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.docgraph-tmp", name))
+}
+
+/// Render a change as a unified diff
+pub fn unified_diff(change: &FileChange) -> String {
+    let path = change.path.display().to_string();
+    similar::TextDiff::from_lines(&change.original, &change.updated)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Range;
+    use tempfile::tempdir;
+
+    fn edit(path: &Path, line: usize, start: usize, end: usize, text: &str) -> TextEdit {
+        TextEdit {
+            path: path.to_path_buf(),
+            range: Range {
+                start_line: line,
+                start_col: start,
+                end_line: line,
+                end_col: end,
+            },
+            new_text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_edits_replace_and_insert() {
+        let path = PathBuf::from("a.md");
+        let content = "<a id=\"A\"></a>\n[x](#A)\n";
+        let e1 = edit(&path, 2, 2, 3, "A (Title)");
+        let e2 = edit(&path, 2, 1, 1, "See ");
+        let (out, applied, skipped) = apply_edits(content, &[&e1, &e2]);
+        assert_eq!(out, "<a id=\"A\"></a>\nSee [A (Title)](#A)\n");
+        assert_eq!((applied, skipped), (2, 0));
+    }
+
+    #[test]
+    fn test_apply_edits_skips_overlap_and_dedups() {
+        let path = PathBuf::from("a.md");
+        let content = "abcdef";
+        let e1 = edit(&path, 1, 2, 5, "X");
+        let e2 = edit(&path, 1, 3, 6, "Y");
+        let (out, applied, skipped) = apply_edits(content, &[&e1, &e1.clone(), &e2]);
+        assert_eq!(out, "aXef");
+        assert_eq!((applied, skipped), (1, 1));
+    }
+
+    #[test]
+    fn test_apply_edits_past_end_starts_new_line() {
+        let path = PathBuf::from("a.md");
+        let e = edit(&path, 2, 1, 1, "# Title\n");
+        let (out, _, _) = apply_edits("<a id=\"A\"></a>", &[&e]);
+        assert_eq!(out, "<a id=\"A\"></a>\n# Title\n");
+    }
+
+    #[test]
+    fn test_plan_and_write_changes() {
+        let dir = tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("sub/b.md");
+        fs::write(&a, "hello world\n").unwrap();
+
+        let edits = vec![
+            edit(&a, 1, 7, 12, "docgraph"),
+            edit(&b, 1, 1, 1, "new file\n"),
+        ];
+        let changes = plan_changes(&edits, None).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(unified_diff(&changes[0]).contains("+hello docgraph"));
+
+        write_changes(&changes).unwrap();
+        assert_eq!(fs::read_to_string(&a).unwrap(), "hello docgraph\n");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "new file\n");
+        assert!(!temp_path(&a).exists());
+    }
+}
@@ -0,0 +1,338 @@
+use crate::core::cache::{self, ParseCache};
+use crate::core::collect::read_content;
+use crate::core::config::{Config, RuleLevel};
+use crate::core::parse::{ParsedDocument, parse_document};
+use crate::core::rules::{dg001, dg002, dg003, dg004, dg005, dg006, dg007, dg008};
+use crate::core::types::{Diagnostic, RefUse, SpecBlock};
+use crate::core::walk::map_markdown_files;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// In-memory index of the workspace, updated one file at a time.
+///
+/// Each file keeps its content, its parsed document and the diagnostics of the rules that
+/// only depend on that file. Cross-file rules (DG002, DG003, DG006) are recomputed from the
+/// indexed blocks without touching the disk, and DG004 is only re-run for every file when a
+/// node title changed.
+#[derive(Debug, Clone)]
+pub struct WorkspaceIndex {
+    root: PathBuf,
+    config: Config,
+    files: BTreeMap<PathBuf, IndexedFile>,
+    titles: HashMap<String, String>,
+    generation: u64,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedFile {
+    content: String,
+    document: ParsedDocument,
+    /// Diagnostics of the rules that only look at this file: DG001, DG005 and DG007
+    local: Vec<Diagnostic>,
+    /// DG004 diagnostics, dropped when the title of any node changes
+    link_text: Option<Vec<Diagnostic>>,
+}
+
+impl WorkspaceIndex {
+    /// Create an empty index
+    pub fn new(root: &Path, config: Config) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            config,
+            files: BTreeMap::new(),
+            titles: HashMap::new(),
+            generation: 0,
+        }
+    }
+
+    /// Index every Markdown file of the workspace. Contents in `overrides` (keyed by
+    /// canonical path) take precedence over the files on disk.
+    pub fn load(root: &Path, config: Config, overrides: Option<&HashMap<PathBuf, String>>) -> Self {
+        let mut index = Self::new(root, config);
+        let cache = ParseCache::open(root, &index.config.cache);
+        let files = map_markdown_files(root, &index.config.graph.ignore, |file_path| {
+            let content = read_content(file_path, overrides)?;
+            let document = cache::parse_with(cache.as_ref(), &content, file_path);
+            Some((content, document))
+        });
+        for (file_path, (content, document)) in files {
+            index.files.insert(
+                file_path,
+                IndexedFile {
+                    content,
+                    document,
+                    local: Vec::new(),
+                    link_text: None,
+                },
+            );
+        }
+
+        if let Some(cache) = cache {
+            // A cache that cannot be written only makes the next run slower
+            let _ = cache.save();
+        }
+
+        // Run the file-local rules once over the whole workspace, then distribute the results
+        let blocks = index.blocks();
+        for d in local_diagnostics(&index.root, &index.config, &blocks) {
+            if let Some(file) = index.files.get_mut(&d.path) {
+                file.local.push(d);
+            }
+        }
+        index.titles = dg004::title_map(&blocks);
+        index.generation = 1;
+        index
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Incremented on every change, so callers can tell whether derived data is stale
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// Indexed file paths, in sorted order
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Current content of an indexed file
+    pub fn content(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(|f| f.content.as_str())
+    }
+
+    /// Re-parse a single file. Returns false when the content did not change.
+    pub fn update_file(&mut self, path: &Path, content: String) -> bool {
+        if self.content(path) == Some(content.as_str()) {
+            return false;
+        }
+
+        let document = parse_document(&content, path);
+        let local = local_diagnostics(&self.root, &self.config, &document.blocks);
+        self.files.insert(
+            path.to_path_buf(),
+            IndexedFile {
+                content,
+                document,
+                local,
+                link_text: None,
+            },
+        );
+        self.refresh_titles();
+        self.generation += 1;
+        true
+    }
+
+    /// Drop a file from the index. Returns false when it was not indexed.
+    pub fn remove_file(&mut self, path: &Path) -> bool {
+        if self.files.remove(path).is_none() {
+            return false;
+        }
+        self.refresh_titles();
+        self.generation += 1;
+        true
+    }
+
+    /// Parsed document of an indexed file
+    pub fn document(&self, path: &Path) -> Option<&ParsedDocument> {
+        self.files.get(path).map(|f| &f.document)
+    }
+
+    /// Parsed documents of every indexed file
+    pub fn documents(&self) -> impl Iterator<Item = &ParsedDocument> {
+        self.files.values().map(|f| &f.document)
+    }
+
+    /// All spec blocks of the workspace
+    pub fn blocks(&self) -> Vec<SpecBlock> {
+        self.files
+            .values()
+            .flat_map(|f| f.document.blocks.iter().cloned())
+            .collect()
+    }
+
+    /// All references outside of spec blocks
+    pub fn refs(&self) -> Vec<RefUse> {
+        self.files
+            .values()
+            .flat_map(|f| f.document.refs.iter().cloned())
+            .collect()
+    }
+
+    /// Lint the indexed workspace. Rules named in `rule_filter` always run; otherwise rules
+    /// set to "off" in [rules] are skipped.
+    pub fn diagnostics(&mut self, rule_filter: Option<&[String]>) -> Vec<Diagnostic> {
+        let config = &self.config;
+        let should_run = |rule_name: &str| -> bool {
+            if let Some(names) = rule_filter {
+                names.iter().any(|n| n == rule_name)
+            } else {
+                config.rule_level(rule_name) != Some(RuleLevel::Off)
+            }
+        };
+
+        if should_run("DG004") {
+            for (path, file) in self.files.iter_mut() {
+                if file.link_text.is_none() {
+                    file.link_text = Some(dg004::check_content(path, &file.content, &self.titles));
+                }
+            }
+        }
+
+        let blocks = self.blocks();
+        let local = |code: &str| -> Vec<Diagnostic> {
+            self.files
+                .values()
+                .flat_map(|f| f.local.iter().filter(|d| d.code == code).cloned())
+                .collect()
+        };
+
+        let mut diagnostics = Vec::new();
+
+        // DG005: Strict Node Types
+        if should_run("DG005") {
+            diagnostics.extend(local("DG005"));
+        }
+
+        // DG006: Strict Relations
+        if should_run("DG006") {
+            diagnostics.extend(dg006::check_relationships(config, &blocks));
+        }
+
+        // DG004: Strict Link Text
+        if should_run("DG004") {
+            for file in self.files.values() {
+                diagnostics.extend(file.link_text.iter().flatten().cloned());
+            }
+        }
+
+        // DG001: Anchor followed by Heading
+        if should_run("DG001") {
+            diagnostics.extend(local("DG001"));
+        }
+
+        // DG002: Duplicate IDs
+        if should_run("DG002") {
+            diagnostics.extend(dg002::check_duplicate_anchors(&blocks));
+        }
+
+        // DG003: Broken Links
+        if should_run("DG003") {
+            diagnostics.extend(dg003::check_broken_links(&blocks, &self.refs()));
+        }
+
+        // DG007: Template Validation
+        if should_run("DG007") {
+            diagnostics.extend(local("DG007"));
+        }
+
+        // DG008: Drop suppressed diagnostics and report unused suppressions
+        let suppressions: Vec<_> = self
+            .files
+            .values()
+            .flat_map(|f| f.document.suppressions.iter().cloned())
+            .collect();
+        let mut diagnostics =
+            dg008::apply_suppressions(diagnostics, &suppressions, should_run, should_run("DG008"));
+
+        apply_rule_levels(&mut diagnostics, config);
+        diagnostics
+    }
+
+    /// DG004 compares link texts with node titles, so every file must be re-checked when a
+    /// title is added, renamed or removed
+    fn refresh_titles(&mut self) {
+        let titles = dg004::title_map(&self.blocks());
+        if titles != self.titles {
+            self.titles = titles;
+            for file in self.files.values_mut() {
+                file.link_text = None;
+            }
+        }
+    }
+}
+
+/// Run the rules whose diagnostics only depend on the blocks of their own file
+fn local_diagnostics(root: &Path, config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnostic> {
+    let mut diagnostics = dg005::check_node_types(config, blocks);
+    diagnostics.extend(dg001::check_anchor_headings(blocks));
+    diagnostics.extend(dg007::check(root, config, blocks));
+    diagnostics
+}
+
+/// Replace each diagnostic's default severity with the level configured in [rules]
+fn apply_rule_levels(diagnostics: &mut [Diagnostic], config: &Config) {
+    for d in diagnostics {
+        if let Some(severity) = config.rule_level(&d.code).and_then(RuleLevel::severity) {
+            d.severity = severity;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_index_update_file() {
+        let dir = tempdir().unwrap();
+        let defs = dir.path().join("defs.md");
+        let links = dir.path().join("links.md");
+        std::fs::write(&defs, "<a id=\"ID-1\"></a>\n# Start\n").unwrap();
+        std::fs::write(
+            &links,
+            "<a id=\"ID-2\"></a>\n# Links\n[ID-1 (Start)](#ID-1)\n",
+        )
+        .unwrap();
+
+        let filter = vec!["DG003".to_string(), "DG004".to_string()];
+        let mut index = WorkspaceIndex::load(dir.path(), Config::default(), None);
+        assert_eq!(index.blocks().len(), 2);
+        assert!(index.diagnostics(Some(&filter)).is_empty());
+
+        // Unchanged content does not bump the generation
+        let generation = index.generation();
+        assert!(!index.update_file(&defs, "<a id=\"ID-1\"></a>\n# Start\n".to_string()));
+        assert_eq!(index.generation(), generation);
+
+        // Renaming the title in one file re-checks the link text in the other
+        assert!(index.update_file(&defs, "<a id=\"ID-1\"></a>\n# Begin\n".to_string()));
+        let diagnostics = index.diagnostics(Some(&filter));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "DG004");
+        assert_eq!(diagnostics[0].path, links);
+
+        // Removing the target breaks the link
+        assert!(index.remove_file(&defs));
+        let diagnostics = index.diagnostics(Some(&filter));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "DG003");
+    }
+
+    #[test]
+    fn test_index_local_rules_follow_updates() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.md");
+        std::fs::write(&path, "<a id=\"FOO-1\"></a>\n# Foo\n").unwrap();
+
+        let mut index = WorkspaceIndex::load(dir.path(), Config::default(), None);
+        assert!(index.diagnostics(None).iter().any(|d| d.code == "DG005"));
+        assert!(!index.diagnostics(None).iter().any(|d| d.code == "DG001"));
+
+        index.update_file(&path, "<a id=\"FOO-1\"></a>\n".to_string());
+        let diagnostics = index.diagnostics(None);
+        assert!(diagnostics.iter().any(|d| d.code == "DG001"));
+        assert_eq!(diagnostics.iter().filter(|d| d.code == "DG005").count(), 1);
+    }
+}
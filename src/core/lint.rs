@@ -1,80 +1,24 @@
+use crate::core::config::Config;
+use crate::core::index::WorkspaceIndex;
 use crate::core::types::Diagnostic;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 pub fn check_workspace(
     path: &Path,
-    _fix: bool, // Fix mode not yet implemented for custom parser
     rule_filter: Option<Vec<String>>,
     _use_docgraph_filter: bool,
-    config: &crate::core::config::Config,
+    config: &Config,
     overrides: Option<&HashMap<PathBuf, String>>,
 ) -> Vec<Diagnostic> {
-    let mut diagnostics = Vec::new();
-    let files = crate::core::walk::find_markdown_files(path, &config.graph.ignore);
-
-    // Collect all spec blocks and refs using our custom parser
-    let (spec_blocks, refs) =
-        crate::core::collect::collect_workspace_all(path, &config.graph.ignore, overrides);
-
-    // Filter rules if provided
-    let should_run = |rule_name: &str| -> bool {
-        if let Some(ref names) = rule_filter {
-            names.iter().any(|n| n == rule_name)
-        } else {
-            true
-        }
-    };
-
-    // DG005: Strict Node Types
-    if should_run("DG005") {
-        let dg005_diags = crate::core::rules::dg005::check_node_types(config, &spec_blocks);
-        diagnostics.extend(dg005_diags);
-    }
-
-    // DG006: Strict Relations
-    if should_run("DG006") {
-        let dg006_diags = crate::core::rules::dg006::check_relationships(config, &spec_blocks);
-        diagnostics.extend(dg006_diags);
-    }
-
-    // DG004: Strict Link Text
-    if should_run("DG004") {
-        let dg004_diags = crate::core::rules::dg004::check_link_text(&files, &spec_blocks);
-        diagnostics.extend(dg004_diags);
-    }
-
-    // DG001: Anchor followed by Heading
-    if should_run("DG001") {
-        let dg001_diags = crate::core::rules::dg001::check_anchor_headings(&files, &spec_blocks);
-        diagnostics.extend(dg001_diags);
-    }
-
-    // DG002: Duplicate IDs
-    if should_run("DG002") {
-        let dg002_diags = crate::core::rules::dg002::check_duplicate_anchors(&spec_blocks);
-        diagnostics.extend(dg002_diags);
-    }
-
-    // DG003: Broken Links
-    if should_run("DG003") {
-        let dg003_diags = crate::core::rules::dg003::check_broken_links(&spec_blocks, &refs);
-        diagnostics.extend(dg003_diags);
-    }
-
-    // DG007: Template Validation
-    if should_run("DG007") {
-        let dg007_diags = crate::core::rules::dg007::check(path, config, &spec_blocks);
-        diagnostics.extend(dg007_diags);
-    }
-
-    diagnostics
+    let mut index = WorkspaceIndex::load(path, config.clone(), overrides);
+    index.diagnostics(rule_filter.as_deref())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::config::Config;
+    use crate::core::types::Severity;
     use tempfile::tempdir;
 
     #[test]
@@ -88,13 +32,63 @@ mod tests {
         std::fs::write(&f1, content).unwrap();
 
         let config = Config::default();
-        let diagnostics = check_workspace(dir.path(), false, None, true, &config, None);
+        let diagnostics = check_workspace(dir.path(), None, true, &config, None);
 
         // Expect DG005 error for unknown node type
         assert!(
             diagnostics
                 .iter()
-                .any(|d| d.code == "DG005" && d.severity == crate::core::types::Severity::Error)
+                .any(|d| d.code == "DG005" && d.severity == Severity::Error)
+        );
+    }
+
+    #[test]
+    fn test_check_workspace_suppressions() {
+        let dir = tempdir().unwrap();
+        let content = r#"<!-- docgraph-disable-next-line DG005 -->
+<a id="FOO-001"></a>
+
+# Heading
+
+<!-- docgraph-disable DG003 -->
+"#;
+        std::fs::write(dir.path().join("test.md"), content).unwrap();
+
+        let config = Config::default();
+        let diagnostics = check_workspace(dir.path(), None, true, &config, None);
+
+        assert!(!diagnostics.iter().any(|d| d.code == "DG005"));
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.code == "DG008" && d.message.contains("DG003"))
+        );
+    }
+
+    #[test]
+    fn test_check_workspace_rule_levels() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("test.md"), "<a id=\"FOO-001\"></a>\n").unwrap();
+
+        // DG001 (missing heading) downgraded, DG005 (unknown type) disabled
+        let config = Config::parse("[rules]\nDG001 = \"info\"\nDG005 = \"off\"\n").unwrap();
+        let diagnostics = check_workspace(dir.path(), None, true, &config, None);
+
+        assert!(!diagnostics.iter().any(|d| d.code == "DG005"));
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.code == "DG001" && d.severity == Severity::Info)
+        );
+
+        // An explicit --rule still runs a disabled rule
+        let diagnostics = check_workspace(
+            dir.path(),
+            Some(vec!["DG005".to_string()]),
+            true,
+            &config,
+            None,
         );
+        assert!(diagnostics.iter().any(|d| d.code == "DG005"));
     }
 }
@@ -1,3 +1,4 @@
+use crate::core::config::{Config, RuleConfig};
 use crate::core::types::{RefUse, SpecBlock};
 use regex::Regex;
 use std::path::{Path, PathBuf};
@@ -212,9 +213,41 @@ pub fn find_definition(blocks: &[SpecBlock], target_id: &str) -> Option<LocateRe
     None
 }
 
-/// Get all candidates for completion (currently just all blocks).
-pub fn completion_candidates(blocks: &[SpecBlock]) -> Vec<&SpecBlock> {
-    blocks.iter().collect()
+/// Get the candidates for a link completion inside `current`, if any.
+/// Targets the node's "to" rules allow are kept, ranked by the first rule naming their type;
+/// nodes without configured rules may link to any block.
+pub fn completion_candidates<'a>(
+    config: &Config,
+    blocks: &'a [SpecBlock],
+    current: Option<&SpecBlock>,
+) -> Vec<&'a SpecBlock> {
+    let rules: Vec<&RuleConfig> = current
+        .and_then(|c| config.nodes.get(&c.node_type))
+        .map(|n| n.rules.iter().filter(|r| r.dir == "to").collect())
+        .unwrap_or_default();
+    let restricted = current.is_some_and(|c| config.nodes.contains_key(&c.node_type))
+        && !rules.iter().any(|r| r.targets.iter().any(|t| t == "*"));
+
+    // Same acceptance as DG006: listed by a "to" rule, or accepting "from" any type
+    let rank = |b: &SpecBlock| -> Option<usize> {
+        if let Some(i) = rules.iter().position(|r| r.targets.contains(&b.node_type)) {
+            return Some(i);
+        }
+        let accepts_all = config.nodes.get(&b.node_type).is_some_and(|n| {
+            n.rules
+                .iter()
+                .any(|r| r.dir == "from" && r.targets.iter().any(|t| t == "*"))
+        });
+        (!restricted || accepts_all).then_some(rules.len())
+    };
+
+    let mut candidates: Vec<(usize, &SpecBlock)> = blocks
+        .iter()
+        .filter(|b| current.is_none_or(|c| c.id != b.id))
+        .filter_map(|b| rank(b).map(|r| (r, b)))
+        .collect();
+    candidates.sort_by(|(ra, a), (rb, b)| ra.cmp(rb).then_with(|| a.id.cmp(&b.id)));
+    candidates.into_iter().map(|(_, b)| b).collect()
 }
 
 /// Find all outgoing edges from the target ID.
@@ -389,10 +422,48 @@ mod tests {
                 ..Default::default()
             },
         ];
-        let candidates = completion_candidates(&blocks);
+        let candidates = completion_candidates(&Config::default(), &blocks, None);
         assert_eq!(candidates.len(), 2);
     }
 
+    #[test]
+    fn test_completion_candidates_follow_rules() {
+        let block = |id: &str| SpecBlock {
+            id: id.to_string(),
+            node_type: id.split('-').next().unwrap().to_string(),
+            ..Default::default()
+        };
+        let blocks = vec![
+            block("FR-01"),
+            block("FR-02"),
+            block("MOD-01"),
+            block("UC-01"),
+            block("X-01"),
+        ];
+        let config: Config = toml::from_str(
+            r#"
+[nodes.FR]
+desc = "Functional"
+rules = [
+  { dir = "to", targets = ["UC"], min = 1 },
+  { dir = "to", targets = ["MOD"] },
+]
+"#,
+        )
+        .unwrap();
+
+        let ids = |c: Vec<&SpecBlock>| c.iter().map(|b| b.id.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            ids(completion_candidates(&config, &blocks, Some(&blocks[0]))),
+            vec!["UC-01", "MOD-01"]
+        );
+        // Unconfigured node types may link anywhere, except to themselves
+        assert_eq!(
+            ids(completion_candidates(&config, &blocks, Some(&blocks[4]))),
+            vec!["FR-01", "FR-02", "MOD-01", "UC-01"]
+        );
+    }
+
     #[test]
     fn test_locate_target_id_method() {
         let block = SpecBlock {
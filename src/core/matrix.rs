@@ -0,0 +1,385 @@
+use crate::core::config::Config;
+use crate::core::error::{Error, Result};
+use crate::core::export::{ExportGraph, xml_escape};
+use crate::core::types::SpecBlock;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A row or column header of the matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixHeader {
+    pub id: String,
+    pub name: Option<String>,
+}
+
+/// Coverage of one node type by another
+#[derive(Debug, Clone)]
+pub struct Matrix {
+    pub row_type: String,
+    pub col_type: String,
+    pub rows: Vec<MatrixHeader>,
+    pub cols: Vec<MatrixHeader>,
+    /// `cells[row][col]`: the relationship names linking the pair directly, or the chains of
+    /// intermediate IDs when the matrix is built through `via` types. Empty when not linked.
+    pub cells: Vec<Vec<Vec<String>>>,
+}
+
+impl Matrix {
+    /// Whether a row is linked to at least one column
+    pub fn is_covered(&self, row: usize) -> bool {
+        self.cells[row].iter().any(|c| !c.is_empty())
+    }
+}
+
+/// Build the coverage matrix of `row_type` against `col_type`.
+///
+/// Links are followed in either direction. With `via`, a row covers a column through a chain
+/// of nodes of the given intermediate types, in order. `rels` restricts which relationships
+/// (the `rel` of the matching `RuleConfig`, or "references") are followed.
+pub fn build_matrix(
+    blocks: &[SpecBlock],
+    config: &Config,
+    row_type: &str,
+    col_type: &str,
+    via: &[String],
+    rels: Option<&[String]>,
+) -> Result<Matrix> {
+    for t in std::iter::once(row_type)
+        .chain(via.iter().map(String::as_str))
+        .chain(std::iter::once(col_type))
+    {
+        if !config.nodes.contains_key(t) {
+            return Err(Error::InvalidNodeType(t.to_string()));
+        }
+    }
+
+    let graph = ExportGraph::build(blocks, config);
+    let node_type: HashMap<&str, &str> = blocks
+        .iter()
+        .map(|b| (b.id.as_str(), b.node_type.as_str()))
+        .collect();
+
+    // Undirected adjacency: id -> [(neighbor, rel)]
+    let mut adjacency: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for edge in &graph.edges {
+        if rels.is_some_and(|r| !r.contains(&edge.rel)) {
+            continue;
+        }
+        adjacency
+            .entry(&edge.from)
+            .or_default()
+            .push((&edge.to, &edge.rel));
+        adjacency
+            .entry(&edge.to)
+            .or_default()
+            .push((&edge.from, &edge.rel));
+    }
+
+    let headers = |t: &str| -> Vec<MatrixHeader> {
+        let mut headers: Vec<MatrixHeader> = blocks
+            .iter()
+            .filter(|b| b.node_type == t)
+            .map(|b| MatrixHeader {
+                id: b.id.clone(),
+                name: b.name.clone(),
+            })
+            .collect();
+        headers.sort_by(|a, b| a.id.cmp(&b.id));
+        headers.dedup_by(|a, b| a.id == b.id);
+        headers
+    };
+    let rows = headers(row_type);
+    let cols = headers(col_type);
+    let col_index: HashMap<&str, usize> = cols
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.id.as_str(), i))
+        .collect();
+
+    let mut cells = vec![vec![Vec::new(); cols.len()]; rows.len()];
+    for (r, row) in rows.iter().enumerate() {
+        // Nodes reached so far, with the chain of intermediate IDs leading to them. Each node
+        // is expanded once per row, through the first chain that reaches it.
+        let mut frontier: Vec<(&str, Vec<&str>)> = vec![(row.id.as_str(), Vec::new())];
+        let mut visited: HashSet<&str> = HashSet::from([row.id.as_str()]);
+        for hop_type in via {
+            let mut next = Vec::new();
+            for (id, chain) in &frontier {
+                for (neighbor, _) in adjacency.get(id).into_iter().flatten() {
+                    if node_type.get(neighbor) == Some(&hop_type.as_str())
+                        && visited.insert(neighbor)
+                    {
+                        let mut chain = chain.clone();
+                        chain.push(neighbor);
+                        next.push((*neighbor, chain));
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut found: Vec<BTreeSet<String>> = vec![BTreeSet::new(); cols.len()];
+        for (id, chain) in &frontier {
+            for (neighbor, rel) in adjacency.get(id).into_iter().flatten() {
+                if let Some(&c) = col_index.get(neighbor) {
+                    let label = if via.is_empty() {
+                        rel.to_string()
+                    } else {
+                        chain.join(" → ")
+                    };
+                    found[c].insert(label);
+                }
+            }
+        }
+        for (c, labels) in found.into_iter().enumerate() {
+            cells[r][c] = labels.into_iter().collect();
+        }
+    }
+
+    Ok(Matrix {
+        row_type: row_type.to_string(),
+        col_type: col_type.to_string(),
+        rows,
+        cols,
+        cells,
+    })
+}
+
+/// Render as CSV, with a trailing `covered` column
+pub fn to_csv(matrix: &Matrix) -> String {
+    let mut out = String::new();
+    let mut header = vec![csv_field(&matrix.row_type)];
+    header.extend(matrix.cols.iter().map(|c| csv_field(&c.id)));
+    header.push("covered".to_string());
+    out.push_str(&header.join(","));
+    out.push('\n');
+
+    for (r, row) in matrix.rows.iter().enumerate() {
+        let mut line = vec![csv_field(&row.id)];
+        line.extend(matrix.cells[r].iter().map(|c| csv_field(&c.join("; "))));
+        line.push(if matrix.is_covered(r) { "yes" } else { "no" }.to_string());
+        out.push_str(&line.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Render as a Markdown table; uncovered rows are marked in bold
+pub fn to_markdown(matrix: &Matrix) -> String {
+    let mut out = format!("| {} \\ {} |", matrix.row_type, matrix.col_type);
+    for col in &matrix.cols {
+        out.push_str(&format!(" {} |", md_cell(&col.id)));
+    }
+    out.push_str("\n|---|");
+    out.push_str(&"---|".repeat(matrix.cols.len()));
+    out.push('\n');
+
+    for (r, row) in matrix.rows.iter().enumerate() {
+        let label = match &row.name {
+            Some(name) => format!("{} ({})", row.id, name),
+            None => row.id.clone(),
+        };
+        if matrix.is_covered(r) {
+            out.push_str(&format!("| {} |", md_cell(&label)));
+        } else {
+            out.push_str(&format!("| **{} — uncovered** |", md_cell(&label)));
+        }
+        for cell in &matrix.cells[r] {
+            out.push_str(&format!(" {} |", md_cell(&cell.join(", "))));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+/// Render as a standalone HTML page; uncovered rows are highlighted
+pub fn to_html(matrix: &Matrix) -> String {
+    let title = format!(
+        "{} → {} traceability",
+        xml_escape(&matrix.row_type),
+        xml_escape(&matrix.col_type)
+    );
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>\ntable {{ border-collapse: collapse; font-family: sans-serif; }}\nth, td {{ border: 1px solid #ccc; padding: 0.3em 0.6em; }}\ntd.linked {{ background: #e6f4ea; }}\ntr.uncovered th {{ background: #fce8e6; color: #a50e0e; }}\n</style>\n</head>\n<body>\n<h1>{0}</h1>\n<table>\n<thead>\n<tr><th></th>",
+        title
+    );
+    for col in &matrix.cols {
+        out.push_str(&format!(
+            "<th title=\"{}\">{}</th>",
+            xml_escape(col.name.as_deref().unwrap_or_default()),
+            xml_escape(&col.id)
+        ));
+    }
+    out.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    for (r, row) in matrix.rows.iter().enumerate() {
+        let class = if matrix.is_covered(r) {
+            ""
+        } else {
+            " class=\"uncovered\""
+        };
+        out.push_str(&format!(
+            "<tr{}><th title=\"{}\">{}</th>",
+            class,
+            xml_escape(row.name.as_deref().unwrap_or_default()),
+            xml_escape(&row.id)
+        ));
+        for cell in &matrix.cells[r] {
+            if cell.is_empty() {
+                out.push_str("<td></td>");
+            } else {
+                out.push_str(&format!(
+                    "<td class=\"linked\">{}</td>",
+                    xml_escape(&cell.join(", "))
+                ));
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{NodeConfig, RuleConfig};
+    use crate::core::types::EdgeUse;
+    use std::path::PathBuf;
+
+    fn block(id: &str, targets: &[&str]) -> SpecBlock {
+        SpecBlock {
+            id: id.to_string(),
+            node_type: id.split(['-', '_']).next().unwrap().to_string(),
+            name: Some(format!("{} name", id)),
+            edges: targets
+                .iter()
+                .map(|t| EdgeUse {
+                    id: t.to_string(),
+                    name: None,
+                    line: 1,
+                    col_start: 1,
+                    col_end: 1,
+                })
+                .collect(),
+            file_path: PathBuf::from("test.md"),
+            line_start: 1,
+            line_end: 1,
+            content: String::new(),
+        }
+    }
+
+    fn fixture() -> (Vec<SpecBlock>, Config) {
+        let blocks = vec![
+            block("UC-01", &[]),
+            block("UC-02", &[]),
+            block("FR-01", &["UC-01"]),
+            block("FR-02", &["UC-01"]),
+            block("MOD-01", &["FR-01"]),
+        ];
+        let mut config = Config::default();
+        config.nodes.insert(
+            "FR".to_string(),
+            NodeConfig {
+                rules: vec![RuleConfig {
+                    dir: "to".to_string(),
+                    targets: vec!["UC".to_string()],
+                    rel: Some("derived_from".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+        config.nodes.insert("UC".to_string(), NodeConfig::default());
+        config
+            .nodes
+            .insert("MOD".to_string(), NodeConfig::default());
+        (blocks, config)
+    }
+
+    #[test]
+    fn test_build_matrix_direct() {
+        let (blocks, config) = fixture();
+        let matrix = build_matrix(&blocks, &config, "UC", "FR", &[], None).unwrap();
+
+        assert_eq!(matrix.rows.len(), 2);
+        assert_eq!(matrix.cols.len(), 2);
+        assert_eq!(matrix.cells[0][0], vec!["derived_from"]);
+        assert_eq!(matrix.cells[0][1], vec!["derived_from"]);
+        assert!(matrix.is_covered(0));
+        assert!(!matrix.is_covered(1));
+    }
+
+    #[test]
+    fn test_build_matrix_via_and_rel_filter() {
+        let (blocks, config) = fixture();
+        let matrix =
+            build_matrix(&blocks, &config, "UC", "MOD", &["FR".to_string()], None).unwrap();
+        assert_eq!(matrix.cells[0][0], vec!["FR-01"]);
+        assert!(!matrix.is_covered(1));
+
+        let filter = vec!["realized_by".to_string()];
+        let matrix = build_matrix(&blocks, &config, "UC", "FR", &[], Some(&filter)).unwrap();
+        assert!(!matrix.is_covered(0));
+
+        assert!(build_matrix(&blocks, &config, "UC", "XX", &[], None).is_err());
+    }
+
+    #[test]
+    fn test_build_matrix_via_dense_graph() {
+        // Every FR links to every DS, so each DS is reached through 20 FRs
+        let frs: Vec<String> = (0..20).map(|i| format!("FR-{:02}", i)).collect();
+        let dss: Vec<String> = (0..20).map(|i| format!("DS-{:02}", i)).collect();
+        let mut blocks = vec![block("MOD-01", &[])];
+        let fr_refs: Vec<&str> = frs.iter().map(String::as_str).collect();
+        blocks.push(block("UC-01", &fr_refs));
+        for ds in &dss {
+            let mut targets = fr_refs.clone();
+            targets.push("MOD-01");
+            blocks.push(block(ds, &targets));
+        }
+        for fr in &frs {
+            blocks.push(block(fr, &[]));
+        }
+        let mut config = Config::default();
+        for t in ["UC", "FR", "DS", "MOD"] {
+            config.nodes.insert(t.to_string(), NodeConfig::default());
+        }
+
+        let via = vec!["FR".to_string(), "DS".to_string()];
+        let matrix = build_matrix(&blocks, &config, "UC", "MOD", &via, None).unwrap();
+        // One chain per DS, through the first FR reaching it
+        assert_eq!(matrix.cells[0][0].len(), 20);
+        assert!(matrix.cells[0][0].iter().all(|c| c.starts_with("FR-00 → ")));
+    }
+
+    #[test]
+    fn test_matrix_renderers() {
+        let (blocks, config) = fixture();
+        let matrix = build_matrix(&blocks, &config, "UC", "FR", &[], None).unwrap();
+
+        assert_eq!(
+            to_csv(&matrix),
+            "UC,FR-01,FR-02,covered\nUC-01,derived_from,derived_from,yes\nUC-02,,,no\n"
+        );
+
+        let md = to_markdown(&matrix);
+        assert!(md.starts_with("| UC \\ FR | FR-01 | FR-02 |\n|---|---|---|\n"));
+        assert!(md.contains("| **UC-02 (UC-02 name) — uncovered** |  |  |\n"));
+
+        let html = to_html(&matrix);
+        assert!(html.contains("<tr class=\"uncovered\"><th title=\"UC-02 name\">UC-02</th>"));
+        assert!(html.contains("<td class=\"linked\">derived_from</td>"));
+    }
+}
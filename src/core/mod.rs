@@ -1,11 +1,22 @@
+pub mod cache;
 pub mod collect;
 pub mod config;
 pub mod engine;
 pub mod error;
+pub mod export;
+pub mod fix;
+pub mod index;
 pub mod lint;
 pub mod locate;
+pub mod matrix;
 pub mod parse;
 pub mod parser;
+pub mod refactor;
+pub mod report;
 pub mod rules;
+pub mod scaffold;
+pub mod site;
+pub mod stats;
 pub mod types;
 pub mod walk;
+pub mod watch;
@@ -1,12 +1,35 @@
-use crate::core::types::{EdgeUse, RefUse, SpecBlock};
+use crate::core::types::{EdgeUse, RefUse, SpecBlock, Suppression};
 use pulldown_cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::sync::OnceLock;
+
+static RE_SUPPRESSION: OnceLock<Regex> = OnceLock::new();
+static RE_ANCHOR: OnceLock<Regex> = OnceLock::new();
+static RE_LINK_TEXT: OnceLock<Regex> = OnceLock::new();
+
+/// Everything extracted from a single Markdown file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedDocument {
+    pub blocks: Vec<SpecBlock>,
+    pub refs: Vec<RefUse>,
+    pub suppressions: Vec<Suppression>,
+}
 
 /// Extract all definitions and references from content using pulldown-cmark
 pub fn extract_all(content: &str, file_path: &Path) -> (Vec<SpecBlock>, Vec<RefUse>) {
+    let doc = parse_document(content, file_path);
+    (doc.blocks, doc.refs)
+}
+
+/// Extract definitions, references and suppression comments from content
+pub fn parse_document(content: &str, file_path: &Path) -> ParsedDocument {
     let mut blocks = Vec::new();
     let mut standalone_refs = Vec::new();
+    let mut suppressions = Vec::new();
+    // Suppressions scoped to the current block, closed together with it
+    let mut block_suppressions: Vec<Suppression> = Vec::new();
 
     // Context tracking
     let mut current_anchor_id: Option<String> = None;
@@ -39,6 +62,34 @@ pub fn extract_all(content: &str, file_path: &Path) -> (Vec<SpecBlock>, Vec<RefU
         match event {
             // Check for HTML anchor tag: <a id="XXX"></a>
             Event::Html(html) | Event::InlineHtml(html) => {
+                for (offset, end_offset, kind, codes) in parse_suppression_comments(&html) {
+                    let (line, col_start) = offset_to_line_col(range.start + offset);
+                    let (last_line, col_end) = offset_to_line_col(range.start + end_offset);
+                    let mut suppression = Suppression {
+                        codes,
+                        file_path: file_path.to_path_buf(),
+                        line,
+                        col_start,
+                        col_end,
+                        start_line: last_line + 1,
+                        end_line: last_line + 1,
+                    };
+                    match kind {
+                        SuppressionKind::NextLine => suppressions.push(suppression),
+                        // Inside a spec block: the whole block, including its anchor line
+                        SuppressionKind::Scope if current_anchor_id.is_some() => {
+                            suppression.start_line = current_anchor_line;
+                            block_suppressions.push(suppression);
+                        }
+                        // Before the first anchor: the whole file
+                        SuppressionKind::Scope => {
+                            suppression.start_line = 1;
+                            suppression.end_line = usize::MAX;
+                            suppressions.push(suppression);
+                        }
+                    }
+                }
+
                 // Determine if this is a block start anchor
                 if let Some(id) = parse_anchor_tag(&html) {
                     // If we were already in a block, close it
@@ -63,6 +114,12 @@ pub fn extract_all(content: &str, file_path: &Path) -> (Vec<SpecBlock>, Vec<RefU
                             line_end: end_line, // Ends at start of new anchor
                             content: block_content,
                         });
+
+                        // Block-scoped suppressions stop before the next anchor
+                        for mut suppression in block_suppressions.drain(..) {
+                            suppression.end_line = end_line.saturating_sub(1);
+                            suppressions.push(suppression);
+                        }
                     }
 
                     // Start new block
@@ -179,15 +236,58 @@ pub fn extract_all(content: &str, file_path: &Path) -> (Vec<SpecBlock>, Vec<RefU
             line_end: end_line,
             content: block_content,
         });
+
+        for mut suppression in block_suppressions {
+            suppression.end_line = end_line;
+            suppressions.push(suppression);
+        }
     }
 
-    (blocks, standalone_refs)
+    ParsedDocument {
+        blocks,
+        refs: standalone_refs,
+        suppressions,
+    }
+}
+
+enum SuppressionKind {
+    /// `docgraph-disable-next-line`
+    NextLine,
+    /// `docgraph-disable`, scoped to the enclosing spec block or the file
+    Scope,
+}
+
+/// Helper to find `<!-- docgraph-disable[-next-line] [CODES] -->` comments.
+/// Returns the byte range of each comment within `html`, its kind and the listed rule codes.
+fn parse_suppression_comments(html: &str) -> Vec<(usize, usize, SuppressionKind, Vec<String>)> {
+    let re = RE_SUPPRESSION.get_or_init(|| {
+        Regex::new(r"<!--\s*docgraph-disable(-next-line)?\b((?s:.)*?)-->").unwrap()
+    });
+    re.captures_iter(html)
+        .map(|c| {
+            let whole = c.get(0).unwrap();
+            let kind = if c.get(1).is_some() {
+                SuppressionKind::NextLine
+            } else {
+                SuppressionKind::Scope
+            };
+            let codes = c
+                .get(2)
+                .map(|m| m.as_str())
+                .unwrap_or_default()
+                .split(|ch: char| ch.is_whitespace() || ch == ',')
+                .filter(|code| !code.is_empty())
+                .map(|code| code.to_ascii_uppercase())
+                .collect();
+            (whole.start(), whole.end(), kind, codes)
+        })
+        .collect()
 }
 
 /// Helper to parse <a id="XXX"></a>
-fn parse_anchor_tag(html: &str) -> Option<String> {
+pub(crate) fn parse_anchor_tag(html: &str) -> Option<String> {
     // Relaxed regex to match <a id="..."> (start tag only is enough) including inside InlineHtml
-    let re = Regex::new(r#"<a\s+id=["']([^"']+)["']"#).ok()?;
+    let re = RE_ANCHOR.get_or_init(|| Regex::new(r#"<a\s+id=["']([^"']+)["']"#).unwrap());
     re.captures(html)
         .map(|c| c.get(1).unwrap().as_str().to_string())
 }
@@ -204,11 +304,11 @@ fn parse_link_fragment(dest: &str) -> Option<String> {
 }
 
 /// Helper to exact display text from full link string [text](url)
-fn parse_link_text(raw_link: &str) -> Option<String> {
+pub(crate) fn parse_link_text(raw_link: &str) -> Option<String> {
     // This is a rough estimation.
     // Ideally we would inspect the nested events, but that complicates the main loop.
     // Regex is safe enough for standard markdown links here.
-    let re = Regex::new(r"^\[([^\]]*)\]").ok()?;
+    let re = RE_LINK_TEXT.get_or_init(|| Regex::new(r"^\[([^\]]*)\]").unwrap());
     re.captures(raw_link)
         .map(|c| c.get(1).unwrap().as_str().to_string())
 }
@@ -218,6 +318,42 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_parse_suppression_comments() {
+        let content = r#"<!-- docgraph-disable DG004 -->
+
+<a id="REQ-01"></a>
+
+# First
+
+<!-- docgraph-disable DG006, DG007 -->
+
+<!-- docgraph-disable-next-line -->
+[x](#REQ-02)
+
+<a id="REQ-02"></a>
+
+# Second
+"#;
+        let doc = parse_document(content, &PathBuf::from("test.md"));
+        assert_eq!(doc.blocks.len(), 2);
+        assert_eq!(doc.suppressions.len(), 3);
+
+        let file = &doc.suppressions[0];
+        assert_eq!(file.codes, vec!["DG004"]);
+        assert_eq!((file.start_line, file.end_line), (1, usize::MAX));
+
+        let next_line = &doc.suppressions[1];
+        assert!(next_line.codes.is_empty());
+        assert_eq!((next_line.line, next_line.start_line), (9, 10));
+        assert_eq!(next_line.end_line, 10);
+
+        // Block scope covers REQ-01 from its anchor up to the line before REQ-02
+        let block = &doc.suppressions[2];
+        assert_eq!(block.codes, vec!["DG006", "DG007"]);
+        assert_eq!((block.start_line, block.end_line), (3, 11));
+    }
+
     #[test]
     fn test_extract_anchor_headings_with_scoped_refs() {
         let content = r#"
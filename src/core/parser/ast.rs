@@ -14,9 +14,37 @@ pub struct MatchClause {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatternPart {
+    /// Path variable of `p = (a)-->(b)`
+    pub variable: Option<String>,
+    pub shortest: Option<ShortestPath>,
     pub chains: Vec<PatternChain>,
 }
 
+impl PatternPart {
+    /// Names of the path, node and relationship variables of the pattern
+    pub fn variables(&self) -> Vec<&str> {
+        let mut variables: Vec<&str> = self.variable.iter().map(String::as_str).collect();
+        for chain in &self.chains {
+            match chain {
+                PatternChain::Node(node) => variables.extend(node.variable.as_deref()),
+                PatternChain::Relationship(rel, node) => {
+                    variables.extend(rel.variable.as_deref());
+                    variables.extend(node.variable.as_deref());
+                }
+            }
+        }
+        variables
+    }
+}
+
+/// `shortestPath(...)` matches one shortest path between each pair of end nodes,
+/// `allShortestPaths(...)` every path of the shortest length
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ShortestPath {
+    Single,
+    All,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PatternChain {
     Node(NodePattern),
@@ -44,6 +72,8 @@ pub enum Direction {
     Both,
 }
 
+/// Length of a variable-length relationship. A missing start means 1, a missing end means
+/// unbounded.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Range {
     pub start: Option<usize>,
@@ -59,12 +89,51 @@ pub struct WhereClause {
 pub enum Expression {
     Or(Vec<Expression>),
     And(Vec<Expression>),
+    Not(Box<Expression>),
+    /// Pattern predicate such as `(n)-->(:MOD)`, true when the pattern matches
+    Pattern(PatternPart),
     Comparison(Comparison),
 }
 
+impl Expression {
+    /// Whether an aggregate function appears anywhere in the expression
+    pub fn contains_aggregate(&self) -> bool {
+        match self {
+            Expression::Or(exprs) | Expression::And(exprs) => {
+                exprs.iter().any(Expression::contains_aggregate)
+            }
+            Expression::Not(inner) => inner.contains_aggregate(),
+            Expression::Pattern(_) => false,
+            Expression::Comparison(comp) => {
+                comp.left.contains_aggregate()
+                    || comp.right.as_ref().is_some_and(Term::contains_aggregate)
+            }
+        }
+    }
+
+    /// Pattern predicates appearing anywhere in the expression
+    pub fn pattern_predicates(&self) -> Vec<&PatternPart> {
+        match self {
+            Expression::Or(exprs) | Expression::And(exprs) => exprs
+                .iter()
+                .flat_map(Expression::pattern_predicates)
+                .collect(),
+            Expression::Not(inner) => inner.pattern_predicates(),
+            Expression::Pattern(part) => vec![part],
+            Expression::Comparison(comp) => {
+                let mut parts = comp.left.pattern_predicates();
+                parts.extend(comp.right.iter().flat_map(Term::pattern_predicates));
+                parts
+            }
+        }
+    }
+}
+
+/// A term, optionally compared with another. `IS NULL` and `IS NOT NULL` have no right
+/// term.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Comparison {
-    pub left: PropertyOrVariable,
+    pub left: Term,
     pub operator: Option<ComparisonOperator>,
     pub right: Option<Term>,
 }
@@ -79,9 +148,53 @@ pub struct PropertyOrVariable {
 pub enum Term {
     Literal(Literal),
     PropertyOrVariable(PropertyOrVariable),
+    FunctionCall(FunctionCall),
+}
+
+impl Term {
+    pub fn contains_aggregate(&self) -> bool {
+        match self {
+            Term::FunctionCall(call) => {
+                call.is_aggregate() || call.args.iter().any(Expression::contains_aggregate)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn pattern_predicates(&self) -> Vec<&PatternPart> {
+        match self {
+            Term::FunctionCall(call) => call
+                .args
+                .iter()
+                .flat_map(Expression::pattern_predicates)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Functions that combine the values of every row of a group
+pub const AGGREGATE_FUNCTIONS: &[&str] = &["count", "collect", "min", "max"];
+
+/// Functions applied to the values of a single row
+pub const SCALAR_FUNCTIONS: &[&str] = &["size", "length", "nodes", "relationships"];
+
+/// A call such as `count(DISTINCT n.id)`. `count(*)` has no arguments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCall {
+    /// Lowercase function name
+    pub name: String,
+    pub distinct: bool,
+    pub args: Vec<Expression>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl FunctionCall {
+    pub fn is_aggregate(&self) -> bool {
+        AGGREGATE_FUNCTIONS.contains(&self.name.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ComparisonOperator {
     Eq,
     NotEq,
@@ -90,17 +203,29 @@ pub enum ComparisonOperator {
     LtEq,
     GtEq,
     Contains,
+    StartsWith,
+    EndsWith,
+    /// `=~`, matching the whole string against a regular expression
+    Regex,
+    In,
+    IsNull,
+    IsNotNull,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Literal {
     String(String),
     Number(i64),
+    List(Vec<Literal>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReturnClause {
+    pub distinct: bool,
     pub items: Vec<ReturnItem>,
+    pub order_by: Vec<SortItem>,
+    pub skip: Option<usize>,
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,3 +233,9 @@ pub struct ReturnItem {
     pub expression: Expression,
     pub alias: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortItem {
+    pub expression: Expression,
+    pub descending: bool,
+}
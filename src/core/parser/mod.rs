@@ -2,6 +2,7 @@ use anyhow::{Result, anyhow};
 use pest::Parser;
 use pest::iterators::Pair;
 use pest_derive::Parser;
+use std::collections::HashSet;
 
 pub mod ast;
 
@@ -35,11 +36,41 @@ pub fn parse_query(query_str: &str) -> Result<ast::Query> {
         }
     }
 
-    Ok(ast::Query {
+    let query = ast::Query {
         match_clause: match_clause.ok_or_else(|| anyhow!("Missing MATCH clause"))?,
         where_clause,
         return_clause: return_clause.ok_or_else(|| anyhow!("Missing RETURN clause"))?,
-    })
+    };
+    check_pattern_predicates(&query)?;
+    Ok(query)
+}
+
+/// Pattern predicates only test the variables bound by MATCH; they cannot introduce new ones
+fn check_pattern_predicates(query: &ast::Query) -> Result<()> {
+    let bound: HashSet<&str> = query
+        .match_clause
+        .patterns
+        .iter()
+        .flat_map(ast::PatternPart::variables)
+        .collect();
+    let ret = &query.return_clause;
+    let expressions = query
+        .where_clause
+        .iter()
+        .map(|w| &w.expression)
+        .chain(ret.items.iter().map(|item| &item.expression))
+        .chain(ret.order_by.iter().map(|item| &item.expression));
+    for expr in expressions {
+        for part in expr.pattern_predicates() {
+            if let Some(var) = part.variables().into_iter().find(|v| !bound.contains(v)) {
+                return Err(anyhow!(
+                    "Variable '{}' is not defined: pattern predicates may only use variables bound by MATCH",
+                    var
+                ));
+            }
+        }
+    }
+    Ok(())
 }
 
 fn parse_match_clause(pair: Pair<Rule>) -> Result<ast::MatchClause> {
@@ -53,6 +84,38 @@ fn parse_match_clause(pair: Pair<Rule>) -> Result<ast::MatchClause> {
 }
 
 fn parse_pattern_part(pair: Pair<Rule>) -> Result<ast::PatternPart> {
+    let mut variable = None;
+    let mut shortest = None;
+    let mut chains = Vec::new();
+    for p in pair.into_inner() {
+        match p.as_rule() {
+            Rule::variable => variable = Some(p.as_str().to_string()),
+            Rule::pattern_element => chains = parse_pattern_element(p)?,
+            Rule::shortest_path => {
+                let mut inner = p.into_inner();
+                shortest = Some(match inner.next().unwrap().as_rule() {
+                    Rule::ALL_SHORTEST_PATHS => ast::ShortestPath::All,
+                    _ => ast::ShortestPath::Single,
+                });
+                chains = parse_pattern_element(inner.next().unwrap())?;
+                if chains.len() != 2 {
+                    return Err(anyhow!(
+                        "Shortest path patterns need exactly one relationship"
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(ast::PatternPart {
+        variable,
+        shortest,
+        chains,
+    })
+}
+
+fn parse_pattern_element(pair: Pair<Rule>) -> Result<Vec<ast::PatternChain>> {
     let mut chains = Vec::new();
     let mut inner = pair.into_inner();
 
@@ -71,7 +134,7 @@ fn parse_pattern_part(pair: Pair<Rule>) -> Result<ast::PatternPart> {
         }
     }
 
-    Ok(ast::PatternPart { chains })
+    Ok(chains)
 }
 
 fn parse_node_pattern(pair: Pair<Rule>) -> Result<ast::NodePattern> {
@@ -139,17 +202,65 @@ fn parse_relationship_pattern(pair: Pair<Rule>) -> Result<ast::RelationshipPatte
 fn parse_where_clause(pair: Pair<Rule>) -> Result<ast::WhereClause> {
     let expr_pair = pair.into_inner().next().unwrap();
     let expression = parse_expression(expr_pair)?;
+    if expression.contains_aggregate() {
+        return Err(anyhow!(
+            "Aggregate functions are only allowed in RETURN and ORDER BY, not in WHERE"
+        ));
+    }
     Ok(ast::WhereClause { expression })
 }
 
 fn parse_return_clause(pair: Pair<Rule>) -> Result<ast::ReturnClause> {
+    let mut distinct = false;
     let mut items = Vec::new();
+    let mut order_by = Vec::new();
+    let mut skip = None;
+    let mut limit = None;
     for p in pair.into_inner() {
-        if p.as_rule() == Rule::return_item {
-            items.push(parse_return_item(p)?);
+        match p.as_rule() {
+            Rule::DISTINCT => distinct = true,
+            Rule::return_item => items.push(parse_return_item(p)?),
+            Rule::order_clause => {
+                for item in p.into_inner() {
+                    if item.as_rule() == Rule::sort_item {
+                        order_by.push(parse_sort_item(item)?);
+                    }
+                }
+            }
+            Rule::skip_clause => skip = Some(parse_count(p)?),
+            Rule::limit_clause => limit = Some(parse_count(p)?),
+            _ => {}
         }
     }
-    Ok(ast::ReturnClause { items })
+    Ok(ast::ReturnClause {
+        distinct,
+        items,
+        order_by,
+        skip,
+        limit,
+    })
+}
+
+fn parse_sort_item(pair: Pair<Rule>) -> Result<ast::SortItem> {
+    let mut inner = pair.into_inner();
+    let expression = parse_expression(inner.next().unwrap())?;
+    let descending = inner.any(|p| p.as_rule() == Rule::DESCENDING);
+    Ok(ast::SortItem {
+        expression,
+        descending,
+    })
+}
+
+/// The row count of a SKIP or LIMIT clause
+fn parse_count(pair: Pair<Rule>) -> Result<usize> {
+    let number = pair
+        .into_inner()
+        .find(|p| p.as_rule() == Rule::number_literal)
+        .unwrap();
+    number
+        .as_str()
+        .parse()
+        .map_err(|_| anyhow!("Row count out of range: {}", number.as_str()))
 }
 
 fn parse_return_item(pair: Pair<Rule>) -> Result<ast::ReturnItem> {
@@ -217,12 +328,12 @@ fn parse_expression(pair: Pair<Rule>) -> Result<ast::Expression> {
     let mut or_parts = Vec::new();
 
     for and_expr in or_expr.into_inner() {
-        // and_expression -> comparison_expression (AND comparison_expression)*
+        // and_expression -> not_expression (AND not_expression)*
         if and_expr.as_rule() == Rule::and_expression {
             let mut and_parts = Vec::new();
-            for comp_expr in and_expr.into_inner() {
-                if comp_expr.as_rule() == Rule::comparison_expression {
-                    and_parts.push(parse_comparison_expression(comp_expr)?);
+            for not_expr in and_expr.into_inner() {
+                if not_expr.as_rule() == Rule::not_expression {
+                    and_parts.push(parse_not_expression(not_expr)?);
                 }
             }
             if and_parts.len() == 1 {
@@ -240,41 +351,90 @@ fn parse_expression(pair: Pair<Rule>) -> Result<ast::Expression> {
     }
 }
 
+fn parse_not_expression(pair: Pair<Rule>) -> Result<ast::Expression> {
+    // not_expression -> NOT not_expression | atom_expression
+    let mut inner = pair.into_inner();
+    let first = inner.next().unwrap();
+    if first.as_rule() == Rule::NOT {
+        let operand = parse_not_expression(inner.next().unwrap())?;
+        return Ok(ast::Expression::Not(Box::new(operand)));
+    }
+
+    // atom_expression -> pattern_predicate | "(" expression ")" | comparison_expression
+    let atom = first.into_inner().next().unwrap();
+    match atom.as_rule() {
+        Rule::pattern_predicate => Ok(ast::Expression::Pattern(ast::PatternPart {
+            variable: None,
+            shortest: None,
+            chains: parse_pattern_element(atom)?,
+        })),
+        Rule::expression => parse_expression(atom),
+        Rule::comparison_expression => parse_comparison_expression(atom),
+        _ => unreachable!(),
+    }
+}
+
 fn parse_comparison_expression(pair: Pair<Rule>) -> Result<ast::Expression> {
     let mut inner = pair.into_inner();
     let left_pair = inner.next().unwrap();
-    let left = parse_property_or_variable(left_pair)?;
-
-    // Check if there is an operator and a right term
-    // comparison_expression = { property_or_variable ~ (SP? ~ comp_op ~ SP? ~ term)? }
-
-    if let Some(op_pair) = inner.next() {
-        let operator = match op_pair.as_str().to_uppercase().as_str() {
-            "=" => ast::ComparisonOperator::Eq,
-            "<>" => ast::ComparisonOperator::NotEq,
-            "<" => ast::ComparisonOperator::Lt,
-            ">" => ast::ComparisonOperator::Gt,
-            "<=" => ast::ComparisonOperator::LtEq,
-            ">=" => ast::ComparisonOperator::GtEq,
-            "CONTAINS" => ast::ComparisonOperator::Contains,
-            _ => unreachable!(),
-        };
+    let left = parse_term(left_pair)?;
 
-        let right_pair = inner.next().unwrap();
-        let right = parse_term(right_pair)?;
+    // Check if there is a null check, or an operator and a right term
+    // comparison_expression = { term ~ (null_check | (SP? ~ comp_op ~ SP? ~ term))? }
 
-        Ok(ast::Expression::Comparison(ast::Comparison {
-            left,
-            operator: Some(operator),
-            right: Some(right),
-        }))
-    } else {
-        Ok(ast::Expression::Comparison(ast::Comparison {
+    let Some(op_pair) = inner.next() else {
+        return Ok(ast::Expression::Comparison(ast::Comparison {
             left,
             operator: None,
             right: None,
-        }))
+        }));
+    };
+
+    if op_pair.as_rule() == Rule::null_check {
+        let negated = op_pair.into_inner().any(|p| p.as_rule() == Rule::NOT);
+        return Ok(ast::Expression::Comparison(ast::Comparison {
+            left,
+            operator: Some(if negated {
+                ast::ComparisonOperator::IsNotNull
+            } else {
+                ast::ComparisonOperator::IsNull
+            }),
+            right: None,
+        }));
     }
+
+    let keyword = op_pair.clone().into_inner().next().map(|p| p.as_rule());
+    let operator = match (keyword, op_pair.as_str()) {
+        (Some(Rule::CONTAINS), _) => ast::ComparisonOperator::Contains,
+        (Some(Rule::STARTS_WITH), _) => ast::ComparisonOperator::StartsWith,
+        (Some(Rule::ENDS_WITH), _) => ast::ComparisonOperator::EndsWith,
+        (Some(Rule::IN), _) => ast::ComparisonOperator::In,
+        (_, "=") => ast::ComparisonOperator::Eq,
+        (_, "<>") => ast::ComparisonOperator::NotEq,
+        (_, "<") => ast::ComparisonOperator::Lt,
+        (_, ">") => ast::ComparisonOperator::Gt,
+        (_, "<=") => ast::ComparisonOperator::LtEq,
+        (_, ">=") => ast::ComparisonOperator::GtEq,
+        (_, "=~") => ast::ComparisonOperator::Regex,
+        _ => unreachable!(),
+    };
+
+    let right_pair = inner.next().unwrap();
+    let right = parse_term(right_pair)?;
+
+    // Report a malformed regular expression now rather than matching nothing
+    if let (ast::ComparisonOperator::Regex, ast::Term::Literal(ast::Literal::String(pattern))) =
+        (&operator, &right)
+    {
+        regex::Regex::new(pattern)
+            .map_err(|e| anyhow!("Invalid regular expression '{}': {}", pattern, e))?;
+    }
+
+    Ok(ast::Expression::Comparison(ast::Comparison {
+        left,
+        operator: Some(operator),
+        right: Some(right),
+    }))
 }
 
 fn parse_property_or_variable(pair: Pair<Rule>) -> Result<ast::PropertyOrVariable> {
@@ -287,28 +447,81 @@ fn parse_property_or_variable(pair: Pair<Rule>) -> Result<ast::PropertyOrVariabl
 fn parse_term(pair: Pair<Rule>) -> Result<ast::Term> {
     let inner = pair.into_inner().next().unwrap();
     match inner.as_rule() {
-        Rule::literal => {
-            let lit = inner.into_inner().next().unwrap();
-            match lit.as_rule() {
-                Rule::string_literal => {
-                    let s = lit.as_str();
-                    Ok(ast::Term::Literal(ast::Literal::String(
-                        s[1..s.len() - 1].to_string(),
-                    )))
-                }
-                Rule::number_literal => Ok(ast::Term::Literal(ast::Literal::Number(
-                    lit.as_str().parse().unwrap(),
-                ))),
-                _ => unreachable!(),
-            }
-        }
+        Rule::literal => Ok(ast::Term::Literal(parse_literal(inner)?)),
         Rule::property_or_variable => Ok(ast::Term::PropertyOrVariable(
             parse_property_or_variable(inner)?,
         )),
+        Rule::function_call => Ok(ast::Term::FunctionCall(parse_function_call(inner)?)),
+        _ => unreachable!(),
+    }
+}
+
+fn parse_literal(pair: Pair<Rule>) -> Result<ast::Literal> {
+    let lit = pair.into_inner().next().unwrap();
+    match lit.as_rule() {
+        Rule::string_literal => {
+            // Strip the double or single quotes
+            let s = lit.as_str();
+            Ok(ast::Literal::String(s[1..s.len() - 1].to_string()))
+        }
+        Rule::number_literal => lit
+            .as_str()
+            .parse()
+            .map(ast::Literal::Number)
+            .map_err(|_| anyhow!("Number out of range: {}", lit.as_str())),
+        Rule::list_literal => Ok(ast::Literal::List(
+            lit.into_inner()
+                .map(parse_literal)
+                .collect::<Result<Vec<_>>>()?,
+        )),
         _ => unreachable!(),
     }
 }
 
+fn parse_function_call(pair: Pair<Rule>) -> Result<ast::FunctionCall> {
+    let mut inner = pair.into_inner();
+    let name = inner.next().unwrap().as_str().to_lowercase();
+    let aggregate = ast::AGGREGATE_FUNCTIONS.contains(&name.as_str());
+    if !aggregate && !ast::SCALAR_FUNCTIONS.contains(&name.as_str()) {
+        return Err(anyhow!("Unknown function '{}'", name));
+    }
+
+    let mut distinct = false;
+    let mut star = false;
+    let mut args = Vec::new();
+    for p in inner {
+        match p.as_rule() {
+            Rule::DISTINCT => distinct = true,
+            Rule::count_star => star = true,
+            Rule::expression => args.push(parse_expression(p)?),
+            _ => {}
+        }
+    }
+
+    if star {
+        if name != "count" {
+            return Err(anyhow!("Only count accepts '*', not {}", name));
+        }
+    } else if args.len() != 1 {
+        return Err(anyhow!("{}() takes exactly one argument", name));
+    }
+    if distinct && !aggregate {
+        return Err(anyhow!("DISTINCT is only allowed in aggregate functions"));
+    }
+    if aggregate && args.iter().any(ast::Expression::contains_aggregate) {
+        return Err(anyhow!(
+            "Aggregate functions cannot be nested in {}()",
+            name
+        ));
+    }
+
+    Ok(ast::FunctionCall {
+        name,
+        distinct,
+        args,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -329,6 +542,42 @@ mod tests {
         // patterns[0] has chains.
     }
 
+    #[test]
+    fn test_parse_named_path() {
+        let parsed = parse_query("MATCH p = (a:UC)-[*]->(b) RETURN p, length(p)").unwrap();
+        let part = &parsed.match_clause.patterns[0];
+        assert_eq!(part.variable, Some("p".to_string()));
+        assert_eq!(part.shortest, None);
+        assert_eq!(part.chains.len(), 2);
+        match &part.chains[1] {
+            ast::PatternChain::Relationship(rel, _) => {
+                assert_eq!(
+                    rel.range,
+                    Some(ast::Range {
+                        start: None,
+                        end: None
+                    })
+                );
+            }
+            other => panic!("expected a relationship, got {:?}", other),
+        }
+
+        let parsed = parse_query("MATCH p=allShortestPaths((a)-[*..5]-(b)), (c) RETURN p").unwrap();
+        let parts = &parsed.match_clause.patterns;
+        assert_eq!(parts[0].shortest, Some(ast::ShortestPath::All));
+        assert_eq!(parts[0].chains.len(), 2);
+        assert_eq!(parts[1].variable, None);
+
+        let parsed = parse_query("MATCH shortestPath((a)-->(b)) RETURN a").unwrap();
+        assert_eq!(
+            parsed.match_clause.patterns[0].shortest,
+            Some(ast::ShortestPath::Single)
+        );
+
+        assert!(parse_query("MATCH p = shortestPath((a)-->(b)-->(c)) RETURN p").is_err());
+        assert!(parse_query("MATCH p = shortestPath((a)) RETURN p").is_err());
+    }
+
     #[test]
     fn test_parse_where() {
         let q = "MATCH (n) WHERE n.id = \"UC_001\" RETURN n";
@@ -336,6 +585,98 @@ mod tests {
         assert!(parsed.where_clause.is_some());
     }
 
+    #[test]
+    fn test_parse_where_predicates() {
+        let parsed = parse_query(
+            "MATCH (n:FR) WHERE NOT (n)-->(:MOD) AND NOT (n.name IS NOT NULL OR n.id IN ['FR_1', 2]) RETURN n",
+        )
+        .unwrap();
+        let ast::Expression::And(exprs) = parsed.where_clause.unwrap().expression else {
+            panic!("expected AND");
+        };
+        match &exprs[0] {
+            ast::Expression::Not(inner) => match inner.as_ref() {
+                ast::Expression::Pattern(part) => assert_eq!(part.chains.len(), 2),
+                other => panic!("expected a pattern, got {:?}", other),
+            },
+            other => panic!("expected NOT, got {:?}", other),
+        }
+        let ast::Expression::Not(inner) = &exprs[1] else {
+            panic!("expected NOT");
+        };
+        let ast::Expression::Or(alternatives) = inner.as_ref() else {
+            panic!("expected OR");
+        };
+        assert!(matches!(
+            &alternatives[0],
+            ast::Expression::Comparison(ast::Comparison {
+                left: ast::Term::PropertyOrVariable(pv),
+                operator: Some(ast::ComparisonOperator::IsNotNull),
+                right: None,
+            }) if pv.property.as_deref() == Some("name")
+        ));
+        match &alternatives[1] {
+            ast::Expression::Comparison(comp) => {
+                assert_eq!(comp.operator, Some(ast::ComparisonOperator::In));
+                assert!(matches!(
+                    &comp.right,
+                    Some(ast::Term::Literal(ast::Literal::List(items))) if *items == vec![
+                        ast::Literal::String("FR_1".to_string()),
+                        ast::Literal::Number(2),
+                    ]
+                ));
+            }
+            other => panic!("expected a comparison, got {:?}", other),
+        }
+
+        let operator = |q: &str| match parse_query(q).unwrap().where_clause.unwrap().expression {
+            ast::Expression::Comparison(comp) => comp.operator,
+            other => panic!("expected a comparison, got {:?}", other),
+        };
+        assert_eq!(
+            operator("MATCH (n) WHERE n.id STARTS  WITH 'FR' RETURN n"),
+            Some(ast::ComparisonOperator::StartsWith)
+        );
+        assert_eq!(
+            operator("MATCH (n) WHERE n.id ends with \"1\" RETURN n"),
+            Some(ast::ComparisonOperator::EndsWith)
+        );
+        assert_eq!(
+            operator("MATCH (n) WHERE n.id =~ 'FR-.*' RETURN n"),
+            Some(ast::ComparisonOperator::Regex)
+        );
+        assert_eq!(
+            operator("MATCH (n) WHERE n.line <= 10 RETURN n"),
+            Some(ast::ComparisonOperator::LtEq)
+        );
+        assert_eq!(
+            operator("MATCH (n) WHERE n.name IS NULL RETURN n"),
+            Some(ast::ComparisonOperator::IsNull)
+        );
+        // An identifier starting with a keyword is still an identifier
+        assert_eq!(
+            operator("MATCH (n) WHERE n.id = notes RETURN n"),
+            Some(ast::ComparisonOperator::Eq)
+        );
+
+        assert!(parse_query("MATCH (n) WHERE n.id =~ 'FR-(' RETURN n").is_err());
+        assert!(parse_query("MATCH (n) WHERE n.id IN [n.name] RETURN n").is_err());
+    }
+
+    #[test]
+    fn test_parse_pattern_predicate_variables() {
+        assert!(parse_query("MATCH p = (n)-[r]->(m) WHERE (n)-[r]->(m) RETURN p").is_ok());
+        for query in [
+            "MATCH (n) WHERE (x)-->(:IF) RETURN n",
+            "MATCH (n) WHERE (n)-->(m:IF) RETURN n",
+            "MATCH (n) WHERE (n)-[r]->() RETURN n",
+            "MATCH (n) RETURN size(collect((n)-->(m)))",
+        ] {
+            let err = parse_query(query).unwrap_err().to_string();
+            assert!(err.contains("bound by MATCH"), "{}: {}", query, err);
+        }
+    }
+
     #[test]
     fn test_parse_alias() {
         let q = "MATCH (n) RETURN n.id AS identifier";
@@ -343,4 +684,64 @@ mod tests {
         let item = &parsed.return_clause.items[0];
         assert_eq!(item.alias, Some("identifier".to_string()));
     }
+
+    #[test]
+    fn test_parse_order_skip_limit() {
+        let q = "MATCH (n) RETURN DISTINCT n.type AS t ORDER BY t DESC, n.id SKIP 2 LIMIT 5";
+        let parsed = parse_query(q).unwrap();
+        let ret = &parsed.return_clause;
+        assert!(ret.distinct);
+        assert_eq!(ret.items.len(), 1);
+        assert_eq!(ret.order_by.len(), 2);
+        assert!(ret.order_by[0].descending);
+        assert!(!ret.order_by[1].descending);
+        assert_eq!(ret.skip, Some(2));
+        assert_eq!(ret.limit, Some(5));
+
+        // Keywords need a word boundary, so identifiers may start with them
+        let parsed =
+            parse_query("MATCH (distinctive) RETURN distinctive ORDER BY distinctive ASC").unwrap();
+        assert!(!parsed.return_clause.distinct);
+        assert_eq!(parsed.return_clause.order_by.len(), 1);
+
+        // Trailing input is rejected instead of silently ignored
+        assert!(parse_query("MATCH (n) RETURN n LIMT 5").is_err());
+    }
+
+    #[test]
+    fn test_parse_function_call() {
+        let parsed =
+            parse_query("MATCH (n) RETURN COUNT(*), count(DISTINCT n.id), size(collect(n.id))")
+                .unwrap();
+        let calls: Vec<_> = parsed
+            .return_clause
+            .items
+            .iter()
+            .map(|item| match &item.expression {
+                ast::Expression::Comparison(ast::Comparison {
+                    left: ast::Term::FunctionCall(call),
+                    ..
+                }) => call.clone(),
+                other => panic!("expected a function call, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(calls[0].name, "count");
+        assert!(calls[0].args.is_empty());
+        assert!(calls[1].distinct);
+        assert_eq!(calls[1].args.len(), 1);
+        assert!(!calls[2].is_aggregate());
+
+        assert!(parse_query("MATCH (n) RETURN avg(n.line)").is_err());
+        assert!(parse_query("MATCH (n) RETURN collect(*)").is_err());
+        assert!(parse_query("MATCH (n) RETURN size(DISTINCT n.id)").is_err());
+        assert!(parse_query("MATCH (n) RETURN min()").is_err());
+
+        // Aggregates are only evaluated over the groups of RETURN and ORDER BY
+        let err = parse_query("MATCH (n) WHERE count(*) > 1 RETURN n.id").unwrap_err();
+        assert!(err.to_string().contains("not in WHERE"));
+        assert!(parse_query("MATCH (n) WHERE NOT size(collect(n.id)) = 1 RETURN n").is_err());
+        let err = parse_query("MATCH (n) RETURN count(collect(n.id))").unwrap_err();
+        assert!(err.to_string().contains("cannot be nested"));
+        assert!(parse_query("MATCH (n) RETURN size(collect(n.id)) ORDER BY max(n.line)").is_ok());
+    }
 }
@@ -0,0 +1,828 @@
+use crate::core::config::Config;
+use crate::core::error::{Error, Result};
+use crate::core::fix;
+use crate::core::index::WorkspaceIndex;
+use crate::core::scaffold::relative_path;
+use crate::core::types::{Range, RefUse, SpecBlock, TextEdit};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Check that the node `old_id` can be renamed to `new_id` without breaking the graph
+pub fn validate_rename(
+    config: &Config,
+    blocks: &[SpecBlock],
+    old_id: &str,
+    new_id: &str,
+) -> Result<()> {
+    if !blocks.iter().any(|b| b.id == old_id) {
+        return Err(Error::Validation(format!("unknown ID '{}'", old_id)));
+    }
+    if new_id == old_id {
+        return Err(Error::Validation(format!(
+            "'{}' is already the ID of this node",
+            new_id
+        )));
+    }
+    validate_new_id(config, blocks, new_id)
+}
+
+/// Check that `new_id` is a valid, unused ID with a registered node type prefix
+pub fn validate_new_id(config: &Config, blocks: &[SpecBlock], new_id: &str) -> Result<()> {
+    if new_id.is_empty() || !new_id.chars().all(is_id_char) {
+        return Err(Error::Validation(format!(
+            "'{}' is not a valid ID: use letters, digits, '-' and '_' only",
+            new_id
+        )));
+    }
+    if let Some(existing) = blocks.iter().find(|b| b.id == new_id) {
+        return Err(Error::Validation(format!(
+            "ID '{}' already exists at {}:{}",
+            new_id,
+            existing.file_path.display(),
+            existing.line_start
+        )));
+    }
+    // Same check as DG005
+    let prefix = new_id.split(['-', '_']).next().unwrap_or(new_id);
+    if !config.nodes.contains_key(prefix) {
+        return Err(Error::Validation(format!(
+            "unknown node type prefix '{}' in ID '{}' (DG005)",
+            prefix, new_id
+        )));
+    }
+    Ok(())
+}
+
+/// Edits renaming the node `old_id` to `new_id` everywhere: its anchor, its heading and the
+/// text and fragment of every link to it
+pub fn rename_edits(index: &WorkspaceIndex, old_id: &str, new_id: &str) -> Result<Vec<TextEdit>> {
+    let blocks = index.blocks();
+    validate_rename(index.config(), &blocks, old_id, new_id)?;
+
+    let mut edits = Vec::new();
+    for block in blocks.iter().filter(|b| b.id == old_id) {
+        let Some(content) = index.content(&block.file_path) else {
+            continue;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let anchor = block.line_start;
+        // The heading usually embeds the ID, e.g. "# REQ-001 Login"
+        let heading = (anchor..=block.line_end.min(lines.len()))
+            .find(|&l| lines[l - 1].trim_start().starts_with('#'));
+        for line_no in std::iter::once(anchor).chain(heading) {
+            let line = lines[line_no - 1];
+            rename_in(
+                &mut edits,
+                &block.file_path,
+                line,
+                line_no,
+                0..line.len(),
+                old_id,
+                new_id,
+            );
+        }
+    }
+
+    for link in links(&blocks, index.refs()) {
+        if link.target_id != old_id {
+            continue;
+        }
+        let Some(line) = line_of(index, &link.path, link.line) else {
+            continue;
+        };
+        // The file part of the destination is left alone, even when the file is named
+        // after the ID. Link text spanning several lines is renamed to the end of this one.
+        let text = link_text_span(line, &link).or_else(|| link_span(line, &link));
+        let fragment = link_dest_span(line, &link)
+            .and_then(|dest| Some(dest.start + line[dest.clone()].find('#')? + 1..dest.end));
+        for span in text.into_iter().chain(fragment) {
+            rename_in(
+                &mut edits, &link.path, line, link.line, span, old_id, new_id,
+            );
+        }
+    }
+
+    edits.sort_by(|a, b| {
+        (&a.path, a.range.start_line, a.range.start_col).cmp(&(
+            &b.path,
+            b.range.start_line,
+            b.range.start_col,
+        ))
+    });
+    edits.dedup();
+    Ok(edits)
+}
+
+/// Edits moving the node `id` to the end of the file `to`, created if needed, and
+/// updating the path of every link to it and from it
+pub fn move_edits(index: &WorkspaceIndex, id: &str, to: &Path) -> Result<Vec<TextEdit>> {
+    let blocks = index.blocks();
+    let block = find_block(&blocks, id)?;
+    let to = markdown_target(index, to)?;
+    if same_file(&to, &block.file_path) {
+        return Err(Error::Validation(format!(
+            "'{}' is already in {}",
+            id,
+            block.file_path.display()
+        )));
+    }
+
+    let span = block_span(index, block);
+    let relocations = [Relocation {
+        from: block.file_path.clone(),
+        cut: span.clone(),
+        keep: span,
+        to,
+        at: None,
+    }];
+    let edits = relink(index, &blocks, &relocations, None);
+    Ok(relocate(index, edits, &relocations))
+}
+
+/// Edits merging the node `from_id` into `into_id`: the body of `from_id` is appended to
+/// `into_id`, its anchor and heading are dropped and every link to it points to `into_id`
+pub fn merge_edits(index: &WorkspaceIndex, into_id: &str, from_id: &str) -> Result<Vec<TextEdit>> {
+    let blocks = index.blocks();
+    let into = find_block(&blocks, into_id)?;
+    let from = find_block(&blocks, from_id)?;
+    if into_id == from_id {
+        return Err(Error::Validation(format!(
+            "cannot merge '{}' into itself",
+            into_id
+        )));
+    }
+
+    let from_span = block_span(index, from);
+    let from_lines: Vec<&str> = index
+        .content(&from.file_path)
+        .unwrap_or_default()
+        .lines()
+        .collect();
+    // The body starts after the heading naming the node, if any
+    let body_start = from
+        .name
+        .as_ref()
+        .and_then(|_| {
+            (from.line_start..from_span.end)
+                .find(|&l| from_lines[l - 1].trim_start().starts_with('#'))
+        })
+        .unwrap_or(from.line_start)
+        + 1;
+
+    let into_span = block_span(index, into);
+    let into_lines: Vec<&str> = index
+        .content(&into.file_path)
+        .unwrap_or_default()
+        .lines()
+        .collect();
+    let last = into_span
+        .clone()
+        .rev()
+        .find(|&l| !into_lines[l - 1].trim().is_empty())
+        .unwrap_or(into.line_start);
+
+    let relocations = [Relocation {
+        from: from.file_path.clone(),
+        cut: from_span.clone(),
+        keep: body_start.max(from_span.start)..from_span.end,
+        to: into.file_path.clone(),
+        at: Some(last + 1),
+    }];
+    let mut edits = relink(index, &blocks, &relocations, Some((from_id, into_id)));
+
+    // Link text naming the merged node names the remaining one instead
+    for link in links(&blocks, index.refs())
+        .into_iter()
+        .filter(|l| l.target_id == from_id)
+    {
+        let Some(line) = line_of(index, &link.path, link.line) else {
+            continue;
+        };
+        let Some(text) = link_text_span(line, &link) else {
+            continue;
+        };
+        if line[text.clone()] == canonical_text(from) {
+            edits.push(TextEdit {
+                path: link.path.clone(),
+                range: Range {
+                    start_line: link.line,
+                    start_col: text.start + 1,
+                    end_line: link.line,
+                    end_col: text.end + 1,
+                },
+                new_text: canonical_text(into),
+            });
+        } else {
+            rename_in(
+                &mut edits, &link.path, line, link.line, text, from_id, into_id,
+            );
+        }
+    }
+    Ok(relocate(index, edits, &relocations))
+}
+
+/// Edits splitting the file of the node `id` in two: `id` and the nodes after it move to
+/// the new file `to`, by default `<ID>.md` next to the current file
+pub fn split_edits(index: &WorkspaceIndex, id: &str, to: Option<&Path>) -> Result<Vec<TextEdit>> {
+    let blocks = index.blocks();
+    let block = find_block(&blocks, id)?;
+    let default_to = block.file_path.with_file_name(format!("{}.md", id));
+    let to = markdown_target(index, to.unwrap_or(&default_to))?;
+    if index.contains(&to) || to.exists() {
+        return Err(Error::Validation(format!(
+            "{} already exists; use move to add nodes to an existing file",
+            to.display()
+        )));
+    }
+    let first = index
+        .document(&block.file_path)
+        .and_then(|d| d.blocks.first())
+        .is_some_and(|b| b.id == id);
+    if first {
+        return Err(Error::Validation(format!(
+            "'{}' is the first node of {}; nothing would be left to split from",
+            id,
+            block.file_path.display()
+        )));
+    }
+
+    let line_count = index
+        .content(&block.file_path)
+        .unwrap_or_default()
+        .lines()
+        .count();
+    let span = block.line_start..line_count + 1;
+    let relocations = [Relocation {
+        from: block.file_path.clone(),
+        cut: span.clone(),
+        keep: span,
+        to,
+        at: None,
+    }];
+    let edits = relink(index, &blocks, &relocations, None);
+    Ok(relocate(index, edits, &relocations))
+}
+
+/// Lines cut from one file and pasted into another
+struct Relocation {
+    from: PathBuf,
+    /// Lines removed from `from`, 1-based with the end excluded
+    cut: std::ops::Range<usize>,
+    /// Lines of the cut that are pasted; the others are dropped
+    keep: std::ops::Range<usize>,
+    to: PathBuf,
+    /// Line of `to` before which the lines are pasted, or `None` to append them
+    at: Option<usize>,
+}
+
+/// The file a line ends up in once the relocations are done
+fn new_file(relocations: &[Relocation], path: &Path, line: usize) -> PathBuf {
+    relocations
+        .iter()
+        .find(|r| r.from == path && r.keep.contains(&line))
+        .map_or_else(|| path.to_path_buf(), |r| r.to.clone())
+}
+
+/// A link to a node, from a spec block or standalone
+struct Link {
+    target_id: String,
+    path: PathBuf,
+    line: usize,
+    col_start: usize,
+    col_end: usize,
+}
+
+fn links(blocks: &[SpecBlock], refs: Vec<RefUse>) -> Vec<Link> {
+    let edges = blocks.iter().flat_map(|b| {
+        b.edges.iter().map(|e| Link {
+            target_id: e.id.clone(),
+            path: b.file_path.clone(),
+            line: e.line,
+            col_start: e.col_start,
+            col_end: e.col_end,
+        })
+    });
+    let refs = refs.into_iter().map(|r| Link {
+        target_id: r.target_id,
+        path: r.file_path,
+        line: r.line,
+        col_start: r.col_start,
+        col_end: r.col_end,
+    });
+    edges.chain(refs).collect()
+}
+
+/// Destination edits for every link whose own file or whose target's file changes with the
+/// relocations, or whose target is renamed by `retarget`
+fn relink(
+    index: &WorkspaceIndex,
+    blocks: &[SpecBlock],
+    relocations: &[Relocation],
+    retarget: Option<(&str, &str)>,
+) -> Vec<TextEdit> {
+    let location = |id: &str| {
+        blocks.iter().find(|b| b.id == id).map(|b| {
+            (
+                b.file_path.clone(),
+                new_file(relocations, &b.file_path, b.line_start),
+            )
+        })
+    };
+
+    let mut edits = Vec::new();
+    for link in links(blocks, index.refs()) {
+        let target_id = match retarget {
+            Some((old, new)) if old == link.target_id => new,
+            _ => link.target_id.as_str(),
+        };
+        let (Some((old_target_file, _)), Some((_, target_file))) =
+            (location(&link.target_id), location(target_id))
+        else {
+            continue;
+        };
+        let from = new_file(relocations, &link.path, link.line);
+        if from == link.path && target_file == old_target_file && target_id == link.target_id {
+            continue;
+        }
+
+        let dest = if same_file(&from, &target_file) {
+            format!("#{}", target_id)
+        } else {
+            let from_dir = absolute(&from);
+            let from_dir = from_dir.parent().unwrap_or(Path::new(""));
+            format!(
+                "{}#{}",
+                relative_path(from_dir, &absolute(&target_file)),
+                target_id
+            )
+        };
+        let Some(line) = line_of(index, &link.path, link.line) else {
+            continue;
+        };
+        if let Some(range) = link_dest_span(line, &link)
+            && line[range.clone()] != dest
+        {
+            edits.push(TextEdit {
+                path: link.path.clone(),
+                range: Range {
+                    start_line: link.line,
+                    start_col: range.start + 1,
+                    end_line: link.line,
+                    end_col: range.end + 1,
+                },
+                new_text: dest,
+            });
+        }
+    }
+    edits
+}
+
+/// Apply the relocations on top of the line edits. Files whose lines move are replaced as a
+/// whole; the other files keep their line edits.
+fn relocate(
+    index: &WorkspaceIndex,
+    line_edits: Vec<TextEdit>,
+    relocations: &[Relocation],
+) -> Vec<TextEdit> {
+    let moved: BTreeSet<PathBuf> = relocations
+        .iter()
+        .flat_map(|r| [r.from.clone(), r.to.clone()])
+        .collect();
+    let (mut edits, moved_edits): (Vec<TextEdit>, Vec<TextEdit>) = line_edits
+        .into_iter()
+        .partition(|e| !moved.contains(&e.path));
+
+    // Line edits never add or remove lines, so relocations still use the original lines
+    let mut files: BTreeMap<PathBuf, (String, Vec<String>)> = BTreeMap::new();
+    for path in moved {
+        let original = index
+            .content(&path)
+            .map(str::to_string)
+            .or_else(|| std::fs::read_to_string(&path).ok())
+            .unwrap_or_default();
+        let file_edits: Vec<&TextEdit> = moved_edits.iter().filter(|e| e.path == path).collect();
+        let (updated, _, _) = fix::apply_edits(&original, &file_edits);
+        let lines = updated.lines().map(str::to_string).collect();
+        files.insert(path, (original, lines));
+    }
+
+    // Take the pasted lines before anything is cut
+    let pasted: Vec<Vec<String>> = relocations
+        .iter()
+        .map(|r| {
+            let lines = &files[&r.from].1;
+            let end = (r.keep.end - 1).min(lines.len());
+            trim_blank_lines(lines.get(r.keep.start - 1..end).unwrap_or_default()).to_vec()
+        })
+        .collect();
+
+    // Cut and paste bottom-up so the lines of the remaining operations stay in place;
+    // on the same line the cut goes first
+    let mut ops: Vec<(usize, bool, usize)> = Vec::new();
+    for (i, r) in relocations.iter().enumerate() {
+        ops.push((r.cut.start, true, i));
+        if let Some(at) = r.at {
+            ops.push((at, false, i));
+        }
+    }
+    ops.sort_by_key(|&(line, is_cut, _)| std::cmp::Reverse((line, is_cut)));
+    for (line, is_cut, i) in ops {
+        let r = &relocations[i];
+        if is_cut {
+            let lines = &mut files.get_mut(&r.from).unwrap().1;
+            let end = (r.cut.end - 1).min(lines.len());
+            lines.drain((line - 1).min(end)..end);
+        } else {
+            let lines = &mut files.get_mut(&r.to).unwrap().1;
+            let at = (line - 1).min(lines.len());
+            let text = std::iter::once(String::new()).chain(pasted[i].iter().cloned());
+            lines.splice(at..at, text);
+        }
+    }
+    for (i, r) in relocations.iter().enumerate() {
+        if r.at.is_none() {
+            let lines = &mut files.get_mut(&r.to).unwrap().1;
+            trim_trailing_blank_lines(lines);
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.extend(pasted[i].iter().cloned());
+        }
+    }
+
+    for (path, (original, mut lines)) in files {
+        // A cut at the end of a file leaves the separator of the previous block behind
+        trim_trailing_blank_lines(&mut lines);
+        let updated = if lines.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", lines.join("\n"))
+        };
+        if updated != original || index.content(&path).is_none() {
+            edits.push(TextEdit {
+                path,
+                range: Range {
+                    start_line: 1,
+                    start_col: 1,
+                    end_line: original.lines().count() + 1,
+                    end_col: 1,
+                },
+                new_text: updated,
+            });
+        }
+    }
+    edits
+}
+
+/// Lines of a block: from its anchor up to, not including, the next anchor of its file
+fn block_span(index: &WorkspaceIndex, block: &SpecBlock) -> std::ops::Range<usize> {
+    let end = index
+        .document(&block.file_path)
+        .and_then(|d| d.blocks.iter().find(|b| b.line_start > block.line_start))
+        .map(|b| b.line_start)
+        .unwrap_or_else(|| {
+            index
+                .content(&block.file_path)
+                .unwrap_or_default()
+                .lines()
+                .count()
+                + 1
+        });
+    block.line_start..end
+}
+
+fn trim_blank_lines(lines: &[String]) -> &[String] {
+    let start = lines
+        .iter()
+        .position(|l| !l.trim().is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .map_or(start, |i| i + 1);
+    &lines[start..end]
+}
+
+fn trim_trailing_blank_lines(lines: &mut Vec<String>) {
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+}
+
+fn find_block<'a>(blocks: &'a [SpecBlock], id: &str) -> Result<&'a SpecBlock> {
+    blocks
+        .iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| Error::Validation(format!("unknown ID '{}'", id)))
+}
+
+/// The index's own path for `to` if it is already part of the workspace
+pub(crate) fn markdown_target(index: &WorkspaceIndex, to: &Path) -> Result<PathBuf> {
+    if to.extension().is_none_or(|e| e != "md") {
+        return Err(Error::Validation(format!(
+            "{} is not a Markdown file",
+            to.display()
+        )));
+    }
+    Ok(index
+        .paths()
+        .find(|p| same_file(p, to))
+        .map_or_else(|| absolute(to), Path::to_path_buf))
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    absolute(a) == absolute(b)
+}
+
+fn line_of<'a>(index: &'a WorkspaceIndex, path: &Path, line: usize) -> Option<&'a str> {
+    index.content(path)?.lines().nth(line - 1)
+}
+
+/// The part of a link's line it covers; links spanning several lines run to the end of it
+fn link_span(line: &str, link: &Link) -> Option<std::ops::Range<usize>> {
+    let end = if link.col_end > link.col_start {
+        (link.col_end - 1).min(line.len())
+    } else {
+        line.len()
+    };
+    line.get(link.col_start - 1..end)?;
+    Some(link.col_start - 1..end)
+}
+
+/// Byte range of the text between the brackets of a link
+fn link_text_span(line: &str, link: &Link) -> Option<std::ops::Range<usize>> {
+    let span = link_span(line, link)?;
+    let close = span.start + line[span.clone()].rfind("](")?;
+    Some(span.start + 1..close)
+}
+
+/// Byte range of the destination between the parentheses of a link
+fn link_dest_span(line: &str, link: &Link) -> Option<std::ops::Range<usize>> {
+    let span = link_span(line, link)?;
+    let open = span.start + line[span.clone()].rfind("](")? + 2;
+    let close = open + line[open..].find(')')?;
+    Some(open..close)
+}
+
+/// Link text in the `ID (Title)` form expected by DG004
+fn canonical_text(block: &SpecBlock) -> String {
+    match &block.name {
+        Some(name) => format!("{} ({})", block.id, name),
+        None => block.id.clone(),
+    }
+}
+
+/// Replace every occurrence of `old_id` within `span` of a line
+fn rename_in(
+    edits: &mut Vec<TextEdit>,
+    path: &Path,
+    line: &str,
+    line_no: usize,
+    span: std::ops::Range<usize>,
+    old_id: &str,
+    new_id: &str,
+) {
+    for found in id_occurrences(line, old_id) {
+        if found.start >= span.start && found.end <= span.end {
+            edits.push(TextEdit {
+                path: path.to_path_buf(),
+                range: Range {
+                    start_line: line_no,
+                    start_col: found.start + 1,
+                    end_line: line_no,
+                    end_col: found.end + 1,
+                },
+                new_text: new_id.to_string(),
+            });
+        }
+    }
+}
+
+/// Byte ranges of `id` in `line` that are not part of a longer ID
+pub fn id_occurrences(line: &str, id: &str) -> Vec<std::ops::Range<usize>> {
+    line.match_indices(id)
+        .map(|(start, _)| start..start + id.len())
+        .filter(|r| {
+            !line[..r.start].chars().next_back().is_some_and(is_id_char)
+                && !line[r.end..].chars().next().is_some_and(is_id_char)
+        })
+        .collect()
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fix;
+    use tempfile::tempdir;
+
+    fn config() -> Config {
+        toml::from_str("[nodes.REQ]\ndesc = \"Requirement\"\n[nodes.SPEC]\ndesc = \"Spec\"\n")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rename_edits() {
+        let dir = tempdir().unwrap();
+        let defs = dir.path().join("defs.md");
+        let links = dir.path().join("links.md");
+        std::fs::write(
+            &defs,
+            "<a id=\"REQ-1\"></a>\n# REQ-1 Login\n\n<a id=\"REQ-10\"></a>\n# Other\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &links,
+            "<a id=\"SPEC-1\"></a>\n# Spec\n- [REQ-1 (Login)](defs.md#REQ-1)\n- [REQ-10](defs.md#REQ-10)\n",
+        )
+        .unwrap();
+        let index = WorkspaceIndex::load(dir.path(), config(), None);
+
+        let edits = rename_edits(&index, "REQ-1", "SPEC-2").unwrap();
+        assert_eq!(edits.len(), 4);
+        let changes = fix::plan_changes(&edits, None).unwrap();
+        assert_eq!(
+            changes[0].updated,
+            "<a id=\"SPEC-2\"></a>\n# SPEC-2 Login\n\n<a id=\"REQ-10\"></a>\n# Other\n"
+        );
+        assert_eq!(
+            changes[1].updated,
+            "<a id=\"SPEC-1\"></a>\n# Spec\n- [SPEC-2 (Login)](defs.md#SPEC-2)\n- [REQ-10](defs.md#REQ-10)\n"
+        );
+    }
+
+    #[test]
+    fn test_rename_edits_keeps_file_named_after_id() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("REQ-2.md"),
+            "<a id=\"REQ-2\"></a>\n# Logout\n",
+        )
+        .unwrap();
+        let links = dir.path().join("links.md");
+        std::fs::write(
+            &links,
+            "<a id=\"SPEC-1\"></a>\n# Spec\n- [REQ-2 (Logout)](REQ-2.md#REQ-2)\n",
+        )
+        .unwrap();
+        let index = WorkspaceIndex::load(dir.path(), config(), None);
+
+        let edits = rename_edits(&index, "REQ-2", "REQ-3").unwrap();
+        let changes = apply(dir.path(), &edits);
+        assert_eq!(
+            changes[1],
+            (
+                "links.md".to_string(),
+                "<a id=\"SPEC-1\"></a>\n# Spec\n- [REQ-3 (Logout)](REQ-2.md#REQ-3)\n".to_string()
+            )
+        );
+    }
+
+    /// Apply edits and return the new content of each changed file, relative to `root`
+    fn apply(root: &Path, edits: &[TextEdit]) -> Vec<(String, String)> {
+        fix::plan_changes(edits, None)
+            .unwrap()
+            .into_iter()
+            .map(|c| {
+                let path = c.path.strip_prefix(root).unwrap_or(&c.path);
+                (path.to_string_lossy().to_string(), c.updated)
+            })
+            .collect()
+    }
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("spec")).unwrap();
+        std::fs::write(
+            dir.path().join("req.md"),
+            "<a id=\"REQ-1\"></a>\n# Login\n\n- [REQ-2 (Logout)](#REQ-2)\n\n\
+             <a id=\"REQ-2\"></a>\n# Logout\nUsers can log out.\n- [SPEC-1 (Spec)](spec/spec.md#SPEC-1)\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("spec/spec.md"),
+            "<a id=\"SPEC-1\"></a>\n# Spec\n\n- [REQ-2 (Logout)](../req.md#REQ-2)\n",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_move_edits() {
+        let dir = workspace();
+        let root = std::path::absolute(dir.path()).unwrap();
+        let index = WorkspaceIndex::load(&root, config(), None);
+
+        let edits = move_edits(&index, "REQ-2", &root.join("spec/spec.md")).unwrap();
+        assert_eq!(
+            apply(&root, &edits),
+            vec![
+                (
+                    "req.md".to_string(),
+                    "<a id=\"REQ-1\"></a>\n# Login\n\n- [REQ-2 (Logout)](spec/spec.md#REQ-2)\n"
+                        .to_string()
+                ),
+                (
+                    "spec/spec.md".to_string(),
+                    "<a id=\"SPEC-1\"></a>\n# Spec\n\n- [REQ-2 (Logout)](#REQ-2)\n\n\
+                     <a id=\"REQ-2\"></a>\n# Logout\nUsers can log out.\n- [SPEC-1 (Spec)](#SPEC-1)\n"
+                        .to_string()
+                ),
+            ]
+        );
+
+        assert!(move_edits(&index, "REQ-2", &root.join("req.md")).is_err());
+        assert!(move_edits(&index, "REQ-2", &root.join("notes.txt")).is_err());
+    }
+
+    #[test]
+    fn test_merge_edits() {
+        let dir = workspace();
+        let root = std::path::absolute(dir.path()).unwrap();
+        let index = WorkspaceIndex::load(&root, config(), None);
+
+        let edits = merge_edits(&index, "REQ-1", "REQ-2").unwrap();
+        assert_eq!(
+            apply(&root, &edits),
+            vec![
+                (
+                    "req.md".to_string(),
+                    "<a id=\"REQ-1\"></a>\n# Login\n\n- [REQ-1 (Login)](#REQ-1)\n\n\
+                     Users can log out.\n- [SPEC-1 (Spec)](spec/spec.md#SPEC-1)\n"
+                        .to_string()
+                ),
+                (
+                    "spec/spec.md".to_string(),
+                    "<a id=\"SPEC-1\"></a>\n# Spec\n\n- [REQ-1 (Login)](../req.md#REQ-1)\n"
+                        .to_string()
+                ),
+            ]
+        );
+        assert!(merge_edits(&index, "REQ-1", "REQ-1").is_err());
+    }
+
+    #[test]
+    fn test_split_edits() {
+        let dir = workspace();
+        let root = std::path::absolute(dir.path()).unwrap();
+        let index = WorkspaceIndex::load(&root, config(), None);
+
+        let edits = split_edits(&index, "REQ-2", None).unwrap();
+        assert_eq!(
+            apply(&root, &edits),
+            vec![
+                (
+                    "REQ-2.md".to_string(),
+                    "<a id=\"REQ-2\"></a>\n# Logout\nUsers can log out.\n- [SPEC-1 (Spec)](spec/spec.md#SPEC-1)\n"
+                        .to_string()
+                ),
+                (
+                    "req.md".to_string(),
+                    "<a id=\"REQ-1\"></a>\n# Login\n\n- [REQ-2 (Logout)](REQ-2.md#REQ-2)\n"
+                        .to_string()
+                ),
+                (
+                    "spec/spec.md".to_string(),
+                    "<a id=\"SPEC-1\"></a>\n# Spec\n\n- [REQ-2 (Logout)](../REQ-2.md#REQ-2)\n"
+                        .to_string()
+                ),
+            ]
+        );
+        assert!(split_edits(&index, "REQ-1", None).is_err());
+    }
+
+    #[test]
+    fn test_validate_rename() {
+        let block = |id: &str| SpecBlock {
+            id: id.to_string(),
+            line_start: 1,
+            ..Default::default()
+        };
+        let blocks = vec![block("REQ-1"), block("REQ-2")];
+        let check = |new_id| validate_rename(&config(), &blocks, "REQ-1", new_id);
+
+        assert!(check("SPEC-1").is_ok());
+        assert!(
+            check("REQ-2")
+                .unwrap_err()
+                .to_string()
+                .contains("already exists")
+        );
+        assert!(check("FOO-1").unwrap_err().to_string().contains("DG005"));
+        assert!(
+            check("REQ 3")
+                .unwrap_err()
+                .to_string()
+                .contains("not a valid ID")
+        );
+        assert!(check("REQ-1").is_err());
+        assert!(validate_rename(&config(), &blocks, "REQ-9", "REQ-3").is_err());
+    }
+}
@@ -0,0 +1,279 @@
+use crate::core::config::{Config, RuleLevel};
+use crate::core::export::xml_escape;
+use crate::core::rules::get_all_rules;
+use crate::core::types::{Diagnostic, Severity};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Render diagnostics as a SARIF 2.1.0 log, with rule metadata for every registered rule and
+/// the level configured for it in [rules].
+/// File locations are made relative to `root` so code-scanning tools can map them to the repository.
+pub fn to_sarif(diagnostics: &[Diagnostic], root: &Path, config: &Config) -> Value {
+    let rules = get_all_rules();
+
+    let rule_descriptors: Vec<Value> = rules
+        .iter()
+        .map(|r| {
+            // Rules report errors unless [rules] sets another level
+            let configuration = match config.rule_level(r.code) {
+                Some(RuleLevel::Off) => json!({ "enabled": false, "level": "none" }),
+                level => {
+                    let severity = level.and_then(RuleLevel::severity);
+                    json!({ "level": level_name(&severity.unwrap_or(Severity::Error)) })
+                }
+            };
+            json!({
+                "id": r.code,
+                "name": r.code,
+                "shortDescription": { "text": r.summary },
+                "fullDescription": { "text": r.description },
+                "defaultConfiguration": configuration,
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|d| {
+            let mut result = json!({
+                "ruleId": d.code,
+                "level": level_name(&d.severity),
+                "message": { "text": d.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": relative_uri(&d.path, root) },
+                        "region": {
+                            "startLine": d.range.start_line,
+                            "startColumn": d.range.start_col,
+                            "endLine": d.range.end_line,
+                            "endColumn": d.range.end_col.max(d.range.start_col),
+                        }
+                    }
+                }],
+            });
+            if let Some(index) = rules.iter().position(|r| r.code == d.code) {
+                result["ruleIndex"] = json!(index);
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "docgraph",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/sonesuke/docgraph",
+                    "rules": rule_descriptors,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// The level of a severity in every report format: "error", "warning" or "note"
+fn level_name(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Hint => "note",
+    }
+}
+
+/// Render diagnostics as a JUnit XML report.
+///
+/// Each rule becomes a test suite. Every diagnostic is a test case named after its location,
+/// failed for errors and warnings; a rule without diagnostics contributes one passing test case.
+pub fn to_junit(diagnostics: &[Diagnostic], root: &Path) -> String {
+    let mut by_rule: BTreeMap<String, Vec<&Diagnostic>> = get_all_rules()
+        .iter()
+        .map(|r| (r.code.to_string(), Vec::new()))
+        .collect();
+    for d in diagnostics {
+        by_rule.entry(d.code.clone()).or_default().push(d);
+    }
+
+    let is_failure = |d: &Diagnostic| matches!(d.severity, Severity::Error | Severity::Warning);
+
+    let total_tests: usize = by_rule.values().map(|v| v.len().max(1)).sum();
+    let total_failures = diagnostics.iter().filter(|d| is_failure(d)).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"docgraph\" tests=\"{}\" failures=\"{}\">\n",
+        total_tests, total_failures
+    ));
+
+    for (code, diags) in &by_rule {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            xml_escape(code),
+            diags.len().max(1),
+            diags.iter().filter(|d| is_failure(d)).count()
+        ));
+        if diags.is_empty() {
+            xml.push_str(&format!(
+                "    <testcase classname=\"docgraph.{0}\" name=\"{0}\"/>\n",
+                xml_escape(code)
+            ));
+        }
+        for d in diags {
+            let location = format!(
+                "{}:{}:{}",
+                relative_uri(&d.path, root),
+                d.range.start_line,
+                d.range.start_col
+            );
+            xml.push_str(&format!(
+                "    <testcase classname=\"docgraph.{}\" name=\"{}\" file=\"{}\" line=\"{}\">\n",
+                xml_escape(code),
+                xml_escape(&location),
+                xml_escape(&relative_uri(&d.path, root)),
+                d.range.start_line
+            ));
+            if is_failure(d) {
+                let first_line = d.message.lines().next().unwrap_or_default();
+                xml.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                    level_name(&d.severity),
+                    xml_escape(first_line),
+                    xml_escape(&d.message)
+                ));
+            } else {
+                xml.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    xml_escape(&d.message)
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Render diagnostics as GitHub Actions workflow commands (`::error file=...::message`)
+pub fn to_github(diagnostics: &[Diagnostic], root: &Path) -> String {
+    let mut out = String::new();
+    for d in diagnostics {
+        // GitHub calls notes notices
+        let command = match level_name(&d.severity) {
+            "note" => "notice",
+            level => level,
+        };
+        out.push_str(&format!(
+            "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}\n",
+            command,
+            github_property(&relative_uri(&d.path, root)),
+            d.range.start_line,
+            d.range.start_col,
+            d.range.end_line,
+            d.range.end_col.max(d.range.start_col),
+            github_property(&d.code),
+            github_data(&d.message)
+        ));
+    }
+    out
+}
+
+/// Path relative to `root`, with forward slashes
+fn relative_uri(path: &Path, root: &Path) -> String {
+    let rel = path.strip_prefix(root).unwrap_or(path);
+    let rel = rel.strip_prefix(".").unwrap_or(rel);
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn github_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn github_property(s: &str) -> String {
+    github_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Range;
+    use std::path::PathBuf;
+
+    fn diag(code: &str, message: &str) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code: code.to_string(),
+            message: message.to_string(),
+            path: PathBuf::from("/repo/doc/a.md"),
+            range: Range {
+                start_line: 3,
+                start_col: 1,
+                end_line: 3,
+                end_col: 1,
+            },
+            fix: None,
+        }
+    }
+
+    #[test]
+    fn test_sarif_output() {
+        let config = Config::parse("[rules]\nDG004 = \"warning\"\nDG007 = \"off\"\n").unwrap();
+        let sarif = to_sarif(
+            &[diag("DG003", "Link to unknown ID 'X'")],
+            Path::new("/repo"),
+            &config,
+        );
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"].as_array().unwrap().len(),
+            get_all_rules().len()
+        );
+        let rules = &run["tool"]["driver"]["rules"];
+        assert_eq!(rules[0]["defaultConfiguration"]["level"], "error");
+        assert_eq!(rules[3]["defaultConfiguration"]["level"], "warning");
+        assert_eq!(
+            rules[6]["defaultConfiguration"],
+            serde_json::json!({ "enabled": false, "level": "none" })
+        );
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "DG003");
+        assert_eq!(result["ruleIndex"], 2);
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "doc/a.md");
+        assert_eq!(location["region"]["startLine"], 3);
+    }
+
+    #[test]
+    fn test_junit_output() {
+        let xml = to_junit(&[diag("DG004", "Link text <bad>")], Path::new("/repo"));
+        assert!(xml.contains("<testsuite name=\"DG004\" tests=\"1\" failures=\"1\">"));
+        assert!(xml.contains("name=\"doc/a.md:3:1\""));
+        assert!(xml.contains("Link text &lt;bad&gt;"));
+        // Rules without diagnostics pass
+        assert!(xml.contains("<testcase classname=\"docgraph.DG001\" name=\"DG001\"/>"));
+    }
+
+    #[test]
+    fn test_github_output() {
+        let out = to_github(
+            &[diag("DG006", "REQUIRED: x\nReason: y")],
+            Path::new("/repo"),
+        );
+        assert_eq!(
+            out,
+            "::error file=doc/a.md,line=3,col=1,endLine=3,endColumn=1,title=DG006::REQUIRED: x%0AReason: y\n"
+        );
+    }
+}
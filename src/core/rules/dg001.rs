@@ -1,6 +1,4 @@
-use crate::core::types::{Diagnostic, Range, RuleMetadata, Severity, SpecBlock};
-
-use std::path::PathBuf;
+use crate::core::types::{Diagnostic, Fix, Range, RuleMetadata, Severity, SpecBlock, TextEdit};
 
 pub fn metadata() -> RuleMetadata {
     RuleMetadata {
@@ -10,7 +8,7 @@ pub fn metadata() -> RuleMetadata {
     }
 }
 
-pub fn check_anchor_headings(_files: &[PathBuf], blocks: &[SpecBlock]) -> Vec<Diagnostic> {
+pub fn check_anchor_headings(blocks: &[SpecBlock]) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
     for block in blocks {
@@ -26,6 +24,7 @@ pub fn check_anchor_headings(_files: &[PathBuf], blocks: &[SpecBlock]) -> Vec<Di
                     end_col: 1,
                 },
                 severity: Severity::Error,
+                fix: Some(insert_heading_fix(block)),
             });
         }
     }
@@ -33,10 +32,59 @@ pub fn check_anchor_headings(_files: &[PathBuf], blocks: &[SpecBlock]) -> Vec<Di
     diagnostics
 }
 
+/// Insert a heading derived from the ID on the line following the anchor
+fn insert_heading_fix(block: &SpecBlock) -> Fix {
+    let title = title_from_id(&block.id);
+    let position = Range {
+        start_line: block.line_start + 1,
+        start_col: 1,
+        end_line: block.line_start + 1,
+        end_col: 1,
+    };
+    Fix {
+        description: format!("Insert heading '{}'", title),
+        edits: vec![TextEdit {
+            path: block.file_path.clone(),
+            range: position,
+            new_text: format!("# {}\n", title),
+        }],
+    }
+}
+
+/// "FR_EXPORT_DATA" -> "Export Data". The heading must not be the bare ID,
+/// because the parser strips the ID prefix when deriving the node name.
+pub(crate) fn title_from_id(id: &str) -> String {
+    let mut parts = id.split(['-', '_']).filter(|p| !p.is_empty());
+    let prefix = parts.next().unwrap_or(id);
+    let words: Vec<String> = parts
+        .map(|w| {
+            let lower = w.to_lowercase();
+            let mut chars = lower.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+
+    if words.is_empty() {
+        "Untitled".to_string()
+    } else if words.iter().all(|w| w.chars().all(|c| c.is_ascii_digit())) {
+        // Purely numeric suffixes ("REQ-001") read better with the type prefix kept
+        std::iter::once(prefix.to_string())
+            .chain(words)
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        words.join(" ")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::parse::extract_all;
+    use std::path::PathBuf;
 
     #[test]
     fn test_dg001_missing_heading() {
@@ -47,9 +95,21 @@ Some text but no heading."#;
         let (blocks, _) = extract_all(content, &path);
 
         assert_eq!(blocks.len(), 1);
-        let diags = check_anchor_headings(std::slice::from_ref(&path), &blocks);
+        let diags = check_anchor_headings(&blocks);
         assert_eq!(diags.len(), 1);
         assert_eq!(diags[0].code, "DG001");
+
+        let fix = diags[0].fix.as_ref().expect("DG001 should be fixable");
+        assert_eq!(fix.edits[0].range.start_line, 2);
+        assert_eq!(fix.edits[0].new_text, "# REQ 001\n");
+    }
+
+    #[test]
+    fn test_dg001_title_from_id() {
+        assert_eq!(title_from_id("FR_EXPORT_DATA"), "Export Data");
+        assert_eq!(title_from_id("UC-LOGIN"), "Login");
+        assert_eq!(title_from_id("REQ-001"), "REQ 001");
+        assert_eq!(title_from_id("ADR"), "Untitled");
     }
 
     #[test]
@@ -61,7 +121,7 @@ Some text but no heading."#;
         let (blocks, _) = extract_all(content, &path);
 
         assert_eq!(blocks.len(), 1);
-        let diags = check_anchor_headings(std::slice::from_ref(&path), &blocks);
+        let diags = check_anchor_headings(&blocks);
         assert_eq!(diags.len(), 0);
     }
 }
@@ -33,6 +33,7 @@ pub fn check_duplicate_anchors(blocks: &[SpecBlock]) -> Vec<Diagnostic> {
                         end_col: 1,
                     },
                     severity: Severity::Error,
+                    fix: None,
                 });
             }
         }
@@ -28,6 +28,7 @@ pub fn check_broken_links(blocks: &[SpecBlock], standalone_refs: &[RefUse]) -> V
                         end_col: edge.col_end,
                     },
                     severity: Severity::Error,
+                    fix: None,
                 });
             }
         }
@@ -47,6 +48,7 @@ pub fn check_broken_links(blocks: &[SpecBlock], standalone_refs: &[RefUse]) -> V
                     end_col: rf.col_end,
                 },
                 severity: Severity::Error,
+                fix: None,
             });
         }
     }
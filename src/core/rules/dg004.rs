@@ -1,8 +1,7 @@
-use crate::core::types::{Diagnostic, Range, RuleMetadata, Severity, SpecBlock};
+use crate::core::types::{Diagnostic, Fix, Range, RuleMetadata, Severity, SpecBlock, TextEdit};
 use pulldown_cmark::{Event, LinkType, Options, Parser, Tag};
 use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 use std::sync::OnceLock;
 
 static RE_WS_SINGLE: OnceLock<regex::Regex> = OnceLock::new();
@@ -15,146 +14,165 @@ pub fn metadata() -> RuleMetadata {
     }
 }
 
-pub fn check_link_text(files: &[PathBuf], blocks: &[SpecBlock]) -> Vec<Diagnostic> {
-    RE_WS_SINGLE.get_or_init(|| regex::Regex::new(r" +").unwrap());
-    let mut diagnostics = Vec::new();
+/// Check the links of already loaded documents, given as path and content
+pub fn check_link_text(documents: &[(&Path, &str)], blocks: &[SpecBlock]) -> Vec<Diagnostic> {
+    let titles = title_map(blocks);
+    documents
+        .iter()
+        .flat_map(|(file_path, content)| check_content(file_path, content, &titles))
+        .collect()
+}
 
-    // Build a map of ID to Title from SpecBlocks (O(1) lookup)
-    let mut id_to_title = HashMap::new();
-    for block in blocks {
-        if let Some(name) = &block.name {
-            id_to_title.insert(block.id.clone(), name.clone());
-        }
-    }
+/// Map each node ID to its title (O(1) lookup)
+pub fn title_map(blocks: &[SpecBlock]) -> HashMap<String, String> {
+    blocks
+        .iter()
+        .filter_map(|b| b.name.as_ref().map(|name| (b.id.clone(), name.clone())))
+        .collect()
+}
 
-    // Iterate files once (O(N) I/O)
-    for file_path in files {
-        if let Ok(content) = fs::read_to_string(file_path) {
-            // Helper to map byte offset to line/col
-            let line_starts: Vec<usize> = std::iter::once(0)
-                .chain(content.match_indices('\n').map(|(i, _)| i + 1))
-                .collect();
-
-            let get_pos = |offset: usize| -> (usize, usize) {
-                let line_idx = line_starts
-                    .partition_point(|&x| x <= offset)
-                    .saturating_sub(1);
-                let line_start = line_starts[line_idx];
-                (line_idx + 1, offset - line_start + 1)
-            };
-
-            let parser = Parser::new_ext(&content, Options::all()).into_offset_iter();
-
-            for (event, range) in parser {
-                if let Event::Start(Tag::Link {
-                    link_type,
-                    dest_url,
-                    ..
-                }) = event
-                {
-                    // Only check inline, reference, or shortcut links with fragments
-                    if matches!(
-                        link_type,
-                        LinkType::Inline | LinkType::Reference | LinkType::Shortcut
-                    ) {
-                        let dest = dest_url.as_ref();
-                        // Check if it's a link to an ID (either #ID or path/to/file#ID)
-                        if let Some(hash_idx) = dest.find('#') {
-                            let target_id = &dest[hash_idx + 1..];
-                            if target_id.is_empty() {
-                                continue;
-                            }
+/// Check the links of a single document against the node titles of the workspace
+pub fn check_content(
+    file_path: &Path,
+    content: &str,
+    titles: &HashMap<String, String>,
+) -> Vec<Diagnostic> {
+    RE_WS_SINGLE.get_or_init(|| regex::Regex::new(r" +").unwrap());
+    let mut diagnostics = Vec::new();
 
-                            if let Some(title) = id_to_title.get(target_id) {
-                                // Extract the display text from the range
-                                // Note: pulldown-cmark range covers the whole link [text](url) or [text][ref]
-                                // We need to extract just the text part.
-                                // For standard links [text](url), regex helper is safest/easiest as pulldown doesn't give inner text range directly in Start event
-                                let full_link_str = &content[range.clone()];
-                                // Remove all occurrences of target_id from the title string
-                                let clean_title = title.replace(target_id, "");
-                                // Remove empty bracket pairs that remain after ID removal
-                                let clean_title = clean_title
-                                    .replace("[]", "")
-                                    .replace("()", "")
-                                    .replace("{}", "");
-                                // Remove remaining dangling separators and extra whitespace
-                                let clean_title = clean_title.trim();
-                                let clean_title = clean_title.trim_start_matches(|c: char| {
-                                    c == ' '
-                                        || c == ':'
-                                        || c == '-'
-                                        || c == '('
-                                        || c == '['
-                                        || c == ']'
-                                        || c == ')'
-                                });
-                                let clean_title = clean_title.trim_end_matches(|c: char| {
-                                    c == ' '
-                                        || c == ':'
-                                        || c == '-'
-                                        || c == ')'
-                                        || c == ']'
-                                        || c == '('
-                                        || c == '['
-                                });
-                                let clean_title = clean_title.trim().to_string();
+    // Helper to map byte offset to line/col
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+
+    let get_pos = |offset: usize| -> (usize, usize) {
+        let line_idx = line_starts
+            .partition_point(|&x| x <= offset)
+            .saturating_sub(1);
+        let line_start = line_starts[line_idx];
+        (line_idx + 1, offset - line_start + 1)
+    };
+
+    let parser = Parser::new_ext(content, Options::all()).into_offset_iter();
+
+    for (event, range) in parser {
+        if let Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            ..
+        }) = event
+        {
+            // Only check inline, reference, or shortcut links with fragments
+            if matches!(
+                link_type,
+                LinkType::Inline | LinkType::Reference | LinkType::Shortcut
+            ) {
+                let dest = dest_url.as_ref();
+                // Check if it's a link to an ID (either #ID or path/to/file#ID)
+                if let Some(hash_idx) = dest.find('#') {
+                    let target_id = &dest[hash_idx + 1..];
+                    if target_id.is_empty() {
+                        continue;
+                    }
 
-                                let expected_text = if clean_title.is_empty() {
-                                    target_id.to_string()
-                                } else {
-                                    format!("{} ({})", target_id, clean_title)
+                    if let Some(title) = titles.get(target_id) {
+                        // Extract the display text from the range
+                        // Note: pulldown-cmark range covers the whole link [text](url) or [text][ref]
+                        // We need to extract just the text part.
+                        // For standard links [text](url), regex helper is safest/easiest as pulldown doesn't give inner text range directly in Start event
+                        let full_link_str = &content[range.clone()];
+                        // Remove all occurrences of target_id from the title string
+                        let clean_title = title.replace(target_id, "");
+                        // Remove empty bracket pairs that remain after ID removal
+                        let clean_title = clean_title
+                            .replace("[]", "")
+                            .replace("()", "")
+                            .replace("{}", "");
+                        // Remove remaining dangling separators and extra whitespace
+                        let clean_title = clean_title.trim();
+                        let clean_title = clean_title.trim_start_matches(|c: char| {
+                            c == ' '
+                                || c == ':'
+                                || c == '-'
+                                || c == '('
+                                || c == '['
+                                || c == ']'
+                                || c == ')'
+                        });
+                        let clean_title = clean_title.trim_end_matches(|c: char| {
+                            c == ' '
+                                || c == ':'
+                                || c == '-'
+                                || c == ')'
+                                || c == ']'
+                                || c == '('
+                                || c == '['
+                        });
+                        let clean_title = clean_title.trim().to_string();
+
+                        let expected_text = if clean_title.is_empty() {
+                            target_id.to_string()
+                        } else {
+                            format!("{} ({})", target_id, clean_title)
+                        };
+
+                        if let Some(open_bracket) = full_link_str.find('[')
+                            && let Some(close_bracket) = full_link_str.rfind(']')
+                        {
+                            let current_text = &full_link_str[open_bracket + 1..close_bracket];
+
+                            let clean_current = current_text
+                                .chars()
+                                .map(|c| if c.is_whitespace() { ' ' } else { c })
+                                .collect::<String>();
+                            let clean_expected = expected_text
+                                .chars()
+                                .map(|c| if c.is_whitespace() { ' ' } else { c })
+                                .collect::<String>();
+
+                            let re_ws_single = RE_WS_SINGLE.get().unwrap();
+                            let c_norm = re_ws_single
+                                .replace_all(clean_current.trim(), " ")
+                                .to_lowercase();
+                            let e_norm = re_ws_single
+                                .replace_all(clean_expected.trim(), " ")
+                                .to_lowercase();
+
+                            if c_norm != e_norm
+                                && !c_norm.contains(&e_norm)
+                                && !e_norm.contains(&c_norm)
+                            {
+                                let (start_line, start_col) =
+                                    get_pos(range.start + open_bracket + 1);
+                                let (end_line, end_col) = get_pos(range.start + close_bracket);
+
+                                let range = Range {
+                                    start_line,
+                                    start_col,
+                                    end_line,
+                                    end_col,
                                 };
-
-                                if let Some(open_bracket) = full_link_str.find('[')
-                                    && let Some(close_bracket) = full_link_str.rfind(']')
-                                {
-                                    let current_text =
-                                        &full_link_str[open_bracket + 1..close_bracket];
-
-                                    let clean_current = current_text
-                                        .chars()
-                                        .map(|c| if c.is_whitespace() { ' ' } else { c })
-                                        .collect::<String>();
-                                    let clean_expected = expected_text
-                                        .chars()
-                                        .map(|c| if c.is_whitespace() { ' ' } else { c })
-                                        .collect::<String>();
-
-                                    let re_ws_single = RE_WS_SINGLE.get().unwrap();
-                                    let c_norm = re_ws_single
-                                        .replace_all(clean_current.trim(), " ")
-                                        .to_lowercase();
-                                    let e_norm = re_ws_single
-                                        .replace_all(clean_expected.trim(), " ")
-                                        .to_lowercase();
-
-                                    if c_norm != e_norm
-                                        && !c_norm.contains(&e_norm)
-                                        && !e_norm.contains(&c_norm)
-                                    {
-                                        let (start_line, start_col) =
-                                            get_pos(range.start + open_bracket + 1);
-                                        let (end_line, end_col) =
-                                            get_pos(range.start + close_bracket);
-
-                                        diagnostics.push(Diagnostic {
-                                            code: "DG004".to_string(),
-                                            message: format!(
-                                                "Link text for '{}' should be '{}' but found '{}'",
-                                                target_id, expected_text, current_text
-                                            ),
-                                            path: file_path.clone(),
-                                            range: Range {
-                                                start_line,
-                                                start_col,
-                                                end_line,
-                                                end_col,
-                                            },
-                                            severity: Severity::Error,
-                                        });
-                                    }
-                                }
+                                diagnostics.push(Diagnostic {
+                                    code: "DG004".to_string(),
+                                    message: format!(
+                                        "Link text for '{}' should be '{}' but found '{}'",
+                                        target_id, expected_text, current_text
+                                    ),
+                                    path: file_path.to_path_buf(),
+                                    range: range.clone(),
+                                    severity: Severity::Error,
+                                    fix: Some(Fix {
+                                        description: format!(
+                                            "Replace link text with '{}'",
+                                            expected_text
+                                        ),
+                                        edits: vec![TextEdit {
+                                            path: file_path.to_path_buf(),
+                                            range,
+                                            new_text: expected_text.clone(),
+                                        }],
+                                    }),
+                                });
                             }
                         }
                     }
@@ -196,8 +214,7 @@ mod tests {
         all_blocks.extend(blocks2);
 
         // Run checking
-        let files = vec![p2.clone()];
-        let warnings = check_link_text(&files, &all_blocks);
+        let warnings = check_link_text(&[(p2.as_path(), c2)], &all_blocks);
 
         // Should have 1 warning for "Click here"
         assert_eq!(warnings.len(), 1);
@@ -206,6 +223,11 @@ mod tests {
                 .message
                 .contains("Link text for 'ID-1' should be 'ID-1 (Start)'")
         );
+
+        let fix = warnings[0].fix.as_ref().expect("DG004 should be fixable");
+        assert_eq!(fix.edits.len(), 1);
+        assert_eq!(fix.edits[0].new_text, "ID-1 (Start)");
+        assert_eq!(fix.edits[0].range, warnings[0].range);
     }
 
     #[test]
@@ -229,8 +251,7 @@ mod tests {
         all_blocks.extend(blocks1);
         all_blocks.extend(blocks2);
 
-        let files = vec![p2.clone()];
-        let warnings = check_link_text(&files, &all_blocks);
+        let warnings = check_link_text(&[(p2.as_path(), c2)], &all_blocks);
 
         assert!(warnings.is_empty());
     }
@@ -35,6 +35,7 @@ pub fn check_node_types(config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnostic
                     end_line: block.line_start,
                     end_col: 1,
                 },
+                fix: None,
             });
         }
     }
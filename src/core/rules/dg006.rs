@@ -1,4 +1,4 @@
-use crate::core::config::Config;
+use crate::core::config::{Config, RuleConfig};
 use crate::core::types::{Diagnostic, Range, RuleMetadata, Severity, SpecBlock};
 use std::collections::HashMap;
 
@@ -15,23 +15,7 @@ pub fn metadata() -> RuleMetadata {
 pub fn check_relationships(config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
 
-    // Map of target_id -> list of source node types that refer to it
-    let mut incoming_types: HashMap<String, Vec<String>> = HashMap::new();
-
-    for block in blocks {
-        let source_type = block
-            .id
-            .split(['-', '_'])
-            .next()
-            .unwrap_or(&block.id)
-            .to_string();
-        for edge in &block.edges {
-            incoming_types
-                .entry(edge.id.clone())
-                .or_default()
-                .push(source_type.clone());
-        }
-    }
+    let incoming_types = incoming_types(blocks);
 
     for block in blocks {
         let prefix = block.id.split(['-', '_']).next().unwrap_or(&block.id);
@@ -42,96 +26,75 @@ pub fn check_relationships(config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnos
             for rule in &node_config.rules {
                 match rule.dir.as_str() {
                     "from" => {
-                        let sources = incoming_types.get(&block.id).cloned().unwrap_or_default();
+                        let count = rule_count(rule, block, &incoming_types);
                         // Check min count
-                        if let Some(min) = rule.min {
-                            let count = sources
-                                .iter()
-                                .filter(|t| {
-                                    rule.targets.contains(t)
-                                        || rule.targets.contains(&"*".to_string())
-                                })
-                                .count();
-                            if count < min {
-                                let label = rule.rel.as_deref().unwrap_or("be referenced by");
-                                let mut message = format!(
-                                    "REQUIRED: Node '{}' (type {}) must {} at least {} {}. (Found {})",
-                                    block.id,
-                                    prefix,
-                                    label,
-                                    min,
-                                    if min > 1 { "nodes" } else { "node" },
-                                    count
-                                );
-                                if let Some(desc) = &rule.desc {
-                                    message.push_str(&format!("\nReason: {}", desc));
-                                }
-                                diagnostics.push(Diagnostic {
-                                    severity: Severity::Error,
-                                    code: "DG006".to_string(),
-                                    message,
-                                    path: block.file_path.clone(),
-                                    range: Range {
-                                        start_line: block.line_start,
-                                        start_col: 1,
-                                        end_line: block.line_start,
-                                        end_col: 1,
-                                    },
-                                });
+                        if let Some(min) = rule.min
+                            && count < min
+                        {
+                            let label = rule.rel.as_deref().unwrap_or("be referenced by");
+                            let mut message = format!(
+                                "REQUIRED: Node '{}' (type {}) must {} at least {} {}. (Found {})",
+                                block.id,
+                                prefix,
+                                label,
+                                min,
+                                if min > 1 { "nodes" } else { "node" },
+                                count
+                            );
+                            if let Some(desc) = &rule.desc {
+                                message.push_str(&format!("\nReason: {}", desc));
                             }
+                            diagnostics.push(Diagnostic {
+                                severity: Severity::Error,
+                                code: "DG006".to_string(),
+                                message,
+                                path: block.file_path.clone(),
+                                range: Range {
+                                    start_line: block.line_start,
+                                    start_col: 1,
+                                    end_line: block.line_start,
+                                    end_col: 1,
+                                },
+                                fix: None,
+                            });
                         }
 
                         // Check max count
-                        if let Some(max) = rule.max {
-                            let count = sources
-                                .iter()
-                                .filter(|t| {
-                                    rule.targets.contains(t)
-                                        || rule.targets.contains(&"*".to_string())
-                                })
-                                .count();
-                            if count > max {
-                                let label = rule.rel.as_deref().unwrap_or("be referenced by");
-                                let mut message = format!(
-                                    "LIMIT EXCEEDED: Node '{}' (type {}) can {} at most {} {}. (Found {})",
-                                    block.id,
-                                    prefix,
-                                    label,
-                                    max,
-                                    if max > 1 { "nodes" } else { "node" },
-                                    count
-                                );
-                                if let Some(desc) = &rule.desc {
-                                    message.push_str(&format!("\nReason: {}", desc));
-                                }
-                                diagnostics.push(Diagnostic {
-                                    severity: Severity::Error,
-                                    code: "DG006".to_string(),
-                                    message,
-                                    path: block.file_path.clone(),
-                                    range: Range {
-                                        start_line: block.line_start,
-                                        start_col: 1,
-                                        end_line: block.line_start,
-                                        end_col: 1,
-                                    },
-                                });
+                        if let Some(max) = rule.max
+                            && count > max
+                        {
+                            let label = rule.rel.as_deref().unwrap_or("be referenced by");
+                            let mut message = format!(
+                                "LIMIT EXCEEDED: Node '{}' (type {}) can {} at most {} {}. (Found {})",
+                                block.id,
+                                prefix,
+                                label,
+                                max,
+                                if max > 1 { "nodes" } else { "node" },
+                                count
+                            );
+                            if let Some(desc) = &rule.desc {
+                                message.push_str(&format!("\nReason: {}", desc));
                             }
+                            diagnostics.push(Diagnostic {
+                                severity: Severity::Error,
+                                code: "DG006".to_string(),
+                                message,
+                                path: block.file_path.clone(),
+                                range: Range {
+                                    start_line: block.line_start,
+                                    start_col: 1,
+                                    end_line: block.line_start,
+                                    end_col: 1,
+                                },
+                                fix: None,
+                            });
                         }
                     }
                     "to" => {
                         allowed_outgoing_types.extend(rule.targets.iter().cloned());
 
-                        let count = block
-                            .edges
-                            .iter()
-                            .filter(|e| {
-                                let target_type = e.id.split(['-', '_']).next().unwrap_or(&e.id);
-                                // Count if it matches one of the allowed types or wildcard
-                                rule.targets.contains(&target_type.to_string())
-                                    || rule.targets.contains(&"*".to_string())
-                            })
-                            .count();
+                        let count = rule_count(rule, block, &incoming_types);
 
                         // Check min count
                         if let Some(min) = rule.min
@@ -161,6 +124,7 @@ pub fn check_relationships(config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnos
                                     end_line: block.line_start,
                                     end_col: 1,
                                 },
+                                fix: None,
                             });
                         }
 
@@ -192,6 +156,7 @@ pub fn check_relationships(config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnos
                                     end_line: block.line_start,
                                     end_col: 1,
                                 },
+                                fix: None,
                             });
                         }
                     }
@@ -286,6 +251,7 @@ pub fn check_relationships(config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnos
                             end_line: block.line_start,
                             end_col: 1,
                         },
+                        fix: None,
                     });
                 }
             }
@@ -295,6 +261,110 @@ pub fn check_relationships(config: &Config, blocks: &[SpecBlock]) -> Vec<Diagnos
     diagnostics
 }
 
+/// Map of target ID -> node types of the blocks linking to it (one entry per link)
+pub fn incoming_types(blocks: &[SpecBlock]) -> HashMap<String, Vec<String>> {
+    let mut incoming: HashMap<String, Vec<String>> = HashMap::new();
+    for block in blocks {
+        let source_type = block
+            .id
+            .split(['-', '_'])
+            .next()
+            .unwrap_or(&block.id)
+            .to_string();
+        for edge in &block.edges {
+            incoming
+                .entry(edge.id.clone())
+                .or_default()
+                .push(source_type.clone());
+        }
+    }
+    incoming
+}
+
+/// Number of relations of `block` matching `rule`, as counted by the min/max constraints:
+/// incoming links from the target types for "from" rules, outgoing links to them for "to" rules
+pub fn rule_count(
+    rule: &RuleConfig,
+    block: &SpecBlock,
+    incoming_types: &HashMap<String, Vec<String>>,
+) -> usize {
+    let matches = |t: &str| {
+        rule.targets
+            .iter()
+            .any(|target| target == t || target == "*")
+    };
+    match rule.dir.as_str() {
+        "from" => incoming_types
+            .get(&block.id)
+            .map_or(0, |sources| sources.iter().filter(|t| matches(t)).count()),
+        "to" => block
+            .edges
+            .iter()
+            .filter(|e| matches(e.id.split(['-', '_']).next().unwrap_or(&e.id)))
+            .count(),
+        _ => 0,
+    }
+}
+
+/// Nodes that would satisfy an unmet "to" rule of `block`: blocks of the rule's target types
+/// that `block` does not link to yet, sorted by ID
+pub fn link_candidates<'a>(
+    config: &Config,
+    blocks: &'a [SpecBlock],
+    block: &SpecBlock,
+) -> Vec<&'a SpecBlock> {
+    let Some(node_config) = config.nodes.get(&block.node_type) else {
+        return Vec::new();
+    };
+    let unmet: Vec<&RuleConfig> = node_config
+        .rules
+        .iter()
+        .filter(|r| r.dir == "to")
+        .filter(|r| {
+            r.min
+                .is_some_and(|min| rule_count(r, block, &HashMap::new()) < min)
+        })
+        .collect();
+
+    let mut candidates: Vec<&SpecBlock> = blocks
+        .iter()
+        .filter(|b| b.id != block.id && !block.edges.iter().any(|e| e.id == b.id))
+        .filter(|b| {
+            unmet
+                .iter()
+                .any(|r| r.targets.iter().any(|t| *t == b.node_type || t == "*"))
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.id.cmp(&b.id));
+    candidates.dedup_by(|a, b| a.id == b.id);
+    candidates
+}
+
+/// Target types of the rules whose minimum `block` does not meet yet, in rule order
+pub fn missing_targets(
+    config: &Config,
+    block: &SpecBlock,
+    incoming_types: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    let Some(node_config) = config.nodes.get(&block.node_type) else {
+        return Vec::new();
+    };
+    let mut missing: Vec<String> = Vec::new();
+    for rule in &node_config.rules {
+        if rule
+            .min
+            .is_some_and(|min| rule_count(rule, block, incoming_types) < min)
+        {
+            for target in &rule.targets {
+                if !missing.contains(target) {
+                    missing.push(target.clone());
+                }
+            }
+        }
+    }
+    missing
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -477,4 +547,32 @@ mod tests {
         ));
         assert!(diags[0].message.contains("Reason: Important Business Rule"));
     }
+
+    #[test]
+    fn test_dg006_missing_targets() {
+        let mut config = Config::default();
+        let mut node_config = NodeConfig::default();
+        for (dir, target) in [("to", "MOD"), ("from", "UC"), ("to", "SYS")] {
+            node_config.rules.push(RuleConfig {
+                dir: dir.to_string(),
+                targets: vec![target.to_string()],
+                min: Some(1),
+                max: None,
+                desc: None,
+                rel: None,
+            });
+        }
+        config.nodes.insert("FR".to_string(), node_config);
+
+        let blocks = vec![
+            create_block("FR-01", vec!["SYS-01"]),
+            create_block("SYS-01", vec![]),
+        ];
+        let incoming = incoming_types(&blocks);
+        assert_eq!(
+            missing_targets(&config, &blocks[0], &incoming),
+            vec!["MOD".to_string(), "UC".to_string()]
+        );
+        assert!(missing_targets(&config, &blocks[1], &incoming).is_empty());
+    }
 }
@@ -111,6 +111,7 @@ pub fn check(root: &Path, config: &Config, spec_blocks: &[SpecBlock]) -> Vec<Dia
                         code: "DG007".to_string(),
                         message: format!("Template validation failed for {}: {}", block.id, msg),
                         severity: Severity::Error,
+                        fix: None,
                     });
                 }
             }
@@ -421,12 +422,10 @@ fn validate_text(events: &[Event], event_idx: &mut usize, pattern: &str) -> Resu
     let mut found = false;
     for (i, event) in events.iter().enumerate().skip(*event_idx) {
         match event {
-            Event::Text(t) | Event::Code(t) => {
-                if match_text(pattern, t) {
-                    found = true;
-                    *event_idx = i + 1;
-                    break;
-                }
+            Event::Text(t) | Event::Code(t) if match_text(pattern, t) => {
+                found = true;
+                *event_idx = i + 1;
+                break;
             }
             Event::Start(Tag::Heading { .. }) => break,
             _ => {}
@@ -467,11 +466,7 @@ fn validate_table(
             match &events[*event_idx] {
                 Event::Start(Tag::TableHead) => in_head = true,
                 Event::End(TagEnd::TableHead) => in_head = false,
-                Event::Start(Tag::TableRow) => {
-                    if !in_head {
-                        row_idx += 1;
-                    }
-                }
+                Event::Start(Tag::TableRow) if !in_head => row_idx += 1,
                 Event::Start(Tag::TableCell) => {
                     *event_idx += 1;
                     let actual_cell = get_events_text_from_vec(events, event_idx);
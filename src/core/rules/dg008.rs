@@ -0,0 +1,182 @@
+use crate::core::rules::get_all_rules;
+use crate::core::types::{Diagnostic, Range, RuleMetadata, Severity, Suppression};
+
+/// DG008: Unused Suppressions
+/// Reports `docgraph-disable` comments that did not silence any diagnostic
+pub fn metadata() -> RuleMetadata {
+    RuleMetadata {
+        code: "DG008",
+        summary: "Suppression comments must silence a diagnostic",
+        description: "A <!-- docgraph-disable-next-line CODE --> or <!-- docgraph-disable CODE --> comment that no longer silences any diagnostic is reported, so stale suppressions do not hide future violations. Unknown rule codes in suppression comments are reported as well.",
+    }
+}
+
+/// Drop the diagnostics silenced by suppression comments.
+///
+/// When `report_unused` is set, every listed code that silenced nothing is reported, but only
+/// if `rule_ran` says the rule was executed (a filtered-out rule cannot prove a suppression stale).
+pub fn apply_suppressions(
+    diagnostics: Vec<Diagnostic>,
+    suppressions: &[Suppression],
+    rule_ran: impl Fn(&str) -> bool,
+    report_unused: bool,
+) -> Vec<Diagnostic> {
+    // One flag per listed code, or a single flag for a suppression of all rules
+    let mut used: Vec<Vec<bool>> = suppressions
+        .iter()
+        .map(|s| vec![false; s.codes.len().max(1)])
+        .collect();
+
+    let mut kept = Vec::new();
+    for d in diagnostics {
+        let mut suppressed = false;
+        for (i, s) in suppressions.iter().enumerate() {
+            if s.file_path != d.path
+                || d.range.start_line < s.start_line
+                || d.range.start_line > s.end_line
+            {
+                continue;
+            }
+            if s.codes.is_empty() {
+                used[i][0] = true;
+                suppressed = true;
+            } else if let Some(j) = s.codes.iter().position(|c| *c == d.code) {
+                used[i][j] = true;
+                suppressed = true;
+            }
+        }
+        if !suppressed {
+            kept.push(d);
+        }
+    }
+
+    if !report_unused {
+        return kept;
+    }
+
+    let known: Vec<&str> = get_all_rules().iter().map(|r| r.code).collect();
+    for (s, flags) in suppressions.iter().zip(&used) {
+        if s.codes.is_empty() {
+            let all_ran = known.iter().filter(|c| **c != "DG008").all(|c| rule_ran(c));
+            if all_ran && !flags[0] {
+                kept.push(unused_diagnostic(
+                    s,
+                    "Unused suppression: no diagnostics were silenced".to_string(),
+                ));
+            }
+            continue;
+        }
+        for (code, was_used) in s.codes.iter().zip(flags) {
+            if !known.contains(&code.as_str()) {
+                kept.push(unused_diagnostic(
+                    s,
+                    format!("Unknown rule '{}' in suppression comment", code),
+                ));
+            } else if rule_ran(code) && !was_used {
+                kept.push(unused_diagnostic(
+                    s,
+                    format!(
+                        "Unused suppression for {}: no diagnostics were silenced",
+                        code
+                    ),
+                ));
+            }
+        }
+    }
+
+    kept
+}
+
+fn unused_diagnostic(suppression: &Suppression, message: String) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Warning,
+        code: "DG008".to_string(),
+        message,
+        path: suppression.file_path.clone(),
+        range: Range {
+            start_line: suppression.line,
+            start_col: suppression.col_start,
+            end_line: suppression.line,
+            end_col: suppression.col_end.max(suppression.col_start),
+        },
+        fix: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn diag(code: &str, line: usize) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            code: code.to_string(),
+            message: String::new(),
+            path: PathBuf::from("test.md"),
+            range: Range {
+                start_line: line,
+                start_col: 1,
+                end_line: line,
+                end_col: 1,
+            },
+            fix: None,
+        }
+    }
+
+    fn suppression(codes: &[&str], start_line: usize, end_line: usize) -> Suppression {
+        Suppression {
+            codes: codes.iter().map(|c| c.to_string()).collect(),
+            file_path: PathBuf::from("test.md"),
+            line: start_line.saturating_sub(1),
+            col_start: 1,
+            col_end: 30,
+            start_line,
+            end_line,
+        }
+    }
+
+    #[test]
+    fn test_dg008_suppresses_matching_codes_in_range() {
+        let diags = vec![diag("DG006", 3), diag("DG007", 3), diag("DG006", 10)];
+        let sups = vec![suppression(&["DG006"], 3, 5)];
+
+        let kept = apply_suppressions(diags, &sups, |_| true, true);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().any(|d| d.code == "DG007"));
+        assert!(
+            kept.iter()
+                .any(|d| d.code == "DG006" && d.range.start_line == 10)
+        );
+    }
+
+    #[test]
+    fn test_dg008_reports_unused_and_unknown() {
+        let sups = vec![
+            suppression(&["DG004", "DG099"], 3, 3),
+            suppression(&[], 5, 5),
+        ];
+
+        let kept = apply_suppressions(vec![], &sups, |_| true, true);
+        let messages: Vec<&str> = kept.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(kept.len(), 3);
+        assert!(kept.iter().all(|d| d.code == "DG008"));
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("Unused suppression for DG004"))
+        );
+        assert!(messages.iter().any(|m| m.contains("Unknown rule 'DG099'")));
+    }
+
+    #[test]
+    fn test_dg008_skips_rules_that_did_not_run() {
+        let sups = vec![suppression(&["DG004"], 3, 3), suppression(&[], 5, 5)];
+
+        let kept = apply_suppressions(vec![], &sups, |code| code != "DG004", true);
+        assert!(kept.is_empty());
+
+        let kept = apply_suppressions(vec![], &sups, |_| true, false);
+        assert!(kept.is_empty());
+    }
+}
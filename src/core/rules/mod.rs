@@ -5,6 +5,7 @@ pub mod dg004;
 pub mod dg005;
 pub mod dg006;
 pub mod dg007;
+pub mod dg008;
 
 use crate::core::types::RuleMetadata;
 
@@ -17,6 +18,7 @@ pub fn get_all_rules() -> Vec<RuleMetadata> {
         dg005::metadata(),
         dg006::metadata(),
         dg007::metadata(),
+        dg008::metadata(),
     ]
 }
 
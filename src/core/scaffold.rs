@@ -0,0 +1,325 @@
+use crate::core::config::Config;
+use crate::core::error::{Error, Result};
+use crate::core::index::WorkspaceIndex;
+use crate::core::parse::parse_anchor_tag;
+use crate::core::refactor;
+use crate::core::rules::dg001::title_from_id;
+use crate::core::types::{Range, SpecBlock, TextEdit};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Markdown of a new node with the given ID.
+///
+/// When the node type has a template, its anchor gets the ID, `{Title}` the title (derived
+/// from the ID if not given), and wildcard link examples such as `- [UC* (*)](*#UC_*)` are
+/// dropped. Other placeholders are kept for the author to fill in.
+pub fn stub_node(root: &Path, config: &Config, id: &str, title: Option<&str>) -> String {
+    let title = title.map_or_else(|| title_from_id(id), str::to_string);
+    let node_type = id.split(['-', '_']).next().unwrap_or(id);
+    let template = config
+        .nodes
+        .get(node_type)
+        .and_then(|n| n.template.as_ref())
+        .and_then(|t| {
+            let path = if t.exists() { t.clone() } else { root.join(t) };
+            fs::read_to_string(path).ok()
+        });
+
+    let Some(template) = template else {
+        return format!("<a id=\"{}\"></a>\n\n# {}\n", id, title);
+    };
+
+    let mut out = String::new();
+    for line in template.lines() {
+        let trimmed = line.trim_start();
+        let line = if trimmed.starts_with("<a ") && trimmed.contains("id=") {
+            format!("<a id=\"{}\"></a>", id)
+        } else if is_wildcard_link(trimmed) {
+            continue;
+        } else {
+            line.replace("{Title}", &title)
+        };
+        // Dropped lines must not leave runs of blank lines behind
+        if line.trim().is_empty() && (out.is_empty() || out.ends_with("\n\n")) {
+            continue;
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+    out
+}
+
+/// Edit creating a node of `node_type` at the end of `file`, and the ID it gets.
+///
+/// Without `id`, the next free ID of the type is used. The ID must be unused and belong to
+/// `node_type`.
+pub fn new_node(
+    index: &WorkspaceIndex,
+    node_type: &str,
+    id: Option<&str>,
+    title: Option<&str>,
+    file: &Path,
+) -> Result<(String, TextEdit)> {
+    if !index.config().nodes.contains_key(node_type) {
+        return Err(Error::Validation(format!(
+            "unknown node type '{}'",
+            node_type
+        )));
+    }
+    let blocks = index.blocks();
+    let id = match id {
+        Some(id) => id.to_string(),
+        None => next_id(&blocks, node_type, title),
+    };
+    if id.split(['-', '_']).next() != Some(node_type) {
+        return Err(Error::Validation(format!(
+            "ID '{}' is not of node type '{}'",
+            id, node_type
+        )));
+    }
+    refactor::validate_new_id(index.config(), &blocks, &id)?;
+
+    let file = refactor::markdown_target(index, file)?;
+    let content = match index.content(&file) {
+        Some(content) => content.to_string(),
+        None => fs::read_to_string(&file).unwrap_or_default(),
+    };
+    let stub = stub_node(index.root(), index.config(), &id, title);
+    let edit = append_node(&file, &content, &stub);
+    Ok((id, edit))
+}
+
+/// The next free ID of a node type.
+///
+/// When IDs of the type are numbered, this is the number after the highest one in the same
+/// style (`FR-007` -> `FR-008`). Otherwise the ID is derived from the title
+/// (`Export Data` -> `FR_EXPORT_DATA`), or numbered from 001 without a title.
+pub fn next_id(blocks: &[SpecBlock], node_type: &str, title: Option<&str>) -> String {
+    let highest = blocks
+        .iter()
+        .filter_map(|b| {
+            let rest = b.id.strip_prefix(node_type)?;
+            let separator = rest.chars().next().filter(|c| *c == '-' || *c == '_')?;
+            let digits = &rest[1..];
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            Some((digits.parse::<u64>().ok()?, separator, digits.len()))
+        })
+        .max();
+    let exists = |id: &str| blocks.iter().any(|b| b.id == id);
+
+    if let Some((n, separator, width)) = highest {
+        return format!("{}{}{:0width$}", node_type, separator, n + 1, width = width);
+    }
+    let Some(title) = title else {
+        return (1..)
+            .map(|n| format!("{}-{:03}", node_type, n))
+            .find(|id| !exists(id))
+            .unwrap_or_default();
+    };
+
+    let words: Vec<String> = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_uppercase())
+        .collect();
+    let base = if words.is_empty() {
+        format!("{}-001", node_type)
+    } else {
+        format!("{}_{}", node_type, words.join("_"))
+    };
+    std::iter::once(base.clone())
+        .chain((2..).map(|n| format!("{}_{}", base, n)))
+        .find(|id| !exists(id))
+        .unwrap_or(base)
+}
+
+/// `- [FR* (*)](*#FR_*)` lines of templates describe allowed links rather than real ones
+fn is_wildcard_link(line: &str) -> bool {
+    line.starts_with(['-', '*'])
+        && line.contains("](")
+        && line
+            .split("](")
+            .nth(1)
+            .is_some_and(|dest| dest.contains('*'))
+}
+
+/// Edit appending a node at the end of a file, separated by a blank line
+pub fn append_node(path: &Path, content: &str, stub: &str) -> TextEdit {
+    let line_count = content.lines().count();
+    let separator = if content.is_empty() || content.ends_with("\n\n") {
+        ""
+    } else if content.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    let (line, col) = if content.is_empty() || content.ends_with('\n') {
+        (line_count + 1, 1)
+    } else {
+        let last = content.lines().last().unwrap_or("");
+        (line_count, last.len() + 1)
+    };
+    TextEdit {
+        path: path.to_path_buf(),
+        range: Range {
+            start_line: line,
+            start_col: col,
+            end_line: line,
+            end_col: col,
+        },
+        new_text: format!("{}{}", separator, stub),
+    }
+}
+
+/// Edit adding a link to `target` as a list item after the last non-empty line of `block`
+pub fn insert_link(content: &str, block: &SpecBlock, target: &SpecBlock) -> TextEdit {
+    let lines: Vec<&str> = content.lines().collect();
+    // `line_end` is the line of the next anchor, if any
+    let end = (block.line_start + 1..=block.line_end.min(lines.len()))
+        .find(|l| parse_anchor_tag(lines[l - 1]).is_some())
+        .map_or(block.line_end.min(lines.len()), |l| l - 1);
+    let last = (block.line_start..=end)
+        .rev()
+        .find(|l| !lines[l - 1].trim().is_empty())
+        .unwrap_or(block.line_start);
+    let in_list = lines
+        .get(last - 1)
+        .is_some_and(|l| l.trim_start().starts_with("- "));
+
+    let link = format!("- {}\n", link_to(&block.file_path, target));
+    let mut new_text = if in_list { link } else { format!("\n{}", link) };
+    // Past the end of a file without trailing newline, start a new line first
+    if last >= lines.len() && !content.ends_with('\n') {
+        new_text.insert(0, '\n');
+    }
+    TextEdit {
+        path: block.file_path.clone(),
+        range: Range {
+            start_line: last + 1,
+            start_col: 1,
+            end_line: last + 1,
+            end_col: 1,
+        },
+        new_text,
+    }
+}
+
+/// Markdown link to a node from a file, in the `[ID (Title)](path#ID)` form expected by DG004
+pub fn link_to(from_file: &Path, target: &SpecBlock) -> String {
+    let text = match &target.name {
+        Some(name) => format!("{} ({})", target.id, name),
+        None => target.id.clone(),
+    };
+    let dest = if target.file_path == from_file {
+        String::new()
+    } else {
+        relative_path(
+            from_file.parent().unwrap_or(Path::new("")),
+            &target.file_path,
+        )
+    };
+    format!("[{}]({}#{})", text, dest, target.id)
+}
+
+/// Path of `to` relative to the directory `from_dir`, with `/` separators
+pub(crate) fn relative_path(from_dir: &Path, to: &Path) -> String {
+    let from: Vec<Component> = from_dir.components().collect();
+    let to_components: Vec<Component> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(&to_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut rel = PathBuf::new();
+    for _ in common..from.len() {
+        rel.push("..");
+    }
+    for c in &to_components[common..] {
+        rel.push(c);
+    }
+    rel.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::NodeConfig;
+    use tempfile::tempdir;
+
+    fn block(id: &str, name: &str, path: &str, lines: (usize, usize)) -> SpecBlock {
+        SpecBlock {
+            id: id.to_string(),
+            node_type: id.split(['-', '_']).next().unwrap().to_string(),
+            name: Some(name.to_string()),
+            file_path: PathBuf::from(path),
+            line_start: lines.0,
+            line_end: lines.1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_stub_node_from_template() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("fr.md"),
+            "<a id=\"FR_*\"></a>\n\n## {Title}\n\n{Description}\n\n### Derived from\n\n- [UC* (*)](*#UC_*)\n- [CON* (*)](*#CON_*)\n",
+        )
+        .unwrap();
+        let mut config = Config::default();
+        config.nodes.insert(
+            "FR".to_string(),
+            NodeConfig {
+                template: Some(PathBuf::from("fr.md")),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            stub_node(dir.path(), &config, "FR_EXPORT_DATA", None),
+            "<a id=\"FR_EXPORT_DATA\"></a>\n\n## Export Data\n\n{Description}\n\n### Derived from\n"
+        );
+        assert_eq!(
+            stub_node(dir.path(), &config, "UC-01", None),
+            "<a id=\"UC-01\"></a>\n\n# UC 01\n"
+        );
+        assert_eq!(
+            stub_node(dir.path(), &config, "FR_EXPORT", Some("Export")),
+            "<a id=\"FR_EXPORT\"></a>\n\n## Export\n\n{Description}\n\n### Derived from\n"
+        );
+    }
+
+    #[test]
+    fn test_next_id() {
+        let blocks = vec![
+            block("FR-007", "Export", "fr.md", (1, 3)),
+            block("FR-012", "Import", "fr.md", (4, 6)),
+            block("UC_LOGIN", "Login", "uc.md", (1, 3)),
+        ];
+        assert_eq!(next_id(&blocks, "FR", Some("Share")), "FR-013");
+        assert_eq!(next_id(&blocks, "UC", Some("Log in")), "UC_LOG_IN");
+        assert_eq!(next_id(&blocks, "UC", Some("Login")), "UC_LOGIN_2");
+        assert_eq!(next_id(&blocks, "UC", None), "UC-001");
+    }
+
+    #[test]
+    fn test_insert_link_and_append_node() {
+        let content = "<a id=\"FR-01\"></a>\n\n## Export\n\n### Derived from\n\n- [UC-01 (Browse)](uc.md#UC-01)\n\n<a id=\"FR-02\"></a>\n";
+        let fr = block("FR-01", "Export", "doc/req/fr.md", (1, 9));
+        let uc = block("UC-02", "Search", "doc/uc/uc.md", (1, 3));
+
+        let edit = insert_link(content, &fr, &uc);
+        assert_eq!(edit.range.start_line, 8);
+        assert_eq!(edit.new_text, "- [UC-02 (Search)](../uc/uc.md#UC-02)\n");
+
+        let edit = append_node(Path::new("fr.md"), content, "<a id=\"FR-03\"></a>\n");
+        assert_eq!(edit.range.start_line, 10);
+        assert_eq!(edit.new_text, "\n<a id=\"FR-03\"></a>\n");
+    }
+}
@@ -0,0 +1,565 @@
+use crate::core::config::Config;
+use crate::core::error::{Error, Result};
+use crate::core::export::{self, ExportGraph, xml_escape};
+use crate::core::parse::parse_anchor_tag;
+use crate::core::types::SpecBlock;
+use crate::core::{collect, walk};
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Directory of the generated type index pages and graph view, kept apart from the pages
+/// rendered from Markdown files
+const GENERATED_DIR: &str = "_docgraph";
+
+/// A generated HTML page, with its path relative to the output directory
+#[derive(Debug, Clone)]
+pub struct SitePage {
+    pub path: PathBuf,
+    pub html: String,
+}
+
+/// Where a spec block is published
+struct Target<'a> {
+    page: PathBuf,
+    block: &'a SpecBlock,
+}
+
+struct SiteContext<'a> {
+    config: &'a Config,
+    targets: HashMap<&'a str, Target<'a>>,
+    /// target ID -> (source ID, rel)
+    incoming: HashMap<&'a str, Vec<(&'a str, &'a str)>>,
+    /// Configured node types, in sorted order
+    types: Vec<&'a str>,
+}
+
+/// Render the workspace as a static HTML site: one page per Markdown file, one index page
+/// per configured node type, a home page and an interactive graph view.
+pub fn build_site(root: &Path, config: &Config) -> Result<Vec<SitePage>> {
+    let (blocks, _refs) = collect::collect_workspace_cached(root, config);
+    let graph = ExportGraph::build(&blocks, config);
+
+    let mut targets = HashMap::new();
+    for block in &blocks {
+        targets.entry(block.id.as_str()).or_insert_with(|| Target {
+            page: page_path(root, &block.file_path),
+            block,
+        });
+    }
+    let mut incoming: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    for edge in &graph.edges {
+        incoming
+            .entry(edge.to.as_str())
+            .or_default()
+            .push((edge.from.as_str(), edge.rel.as_str()));
+    }
+    let mut types: Vec<&str> = config.nodes.keys().map(|k| k.as_str()).collect();
+    types.sort();
+
+    let ctx = SiteContext {
+        config,
+        targets,
+        incoming,
+        types,
+    };
+
+    let mut pages = Vec::new();
+    let mut documents = Vec::new();
+    for file in walk::find_markdown_files(root, &config.graph.ignore) {
+        let content = fs::read_to_string(&file)?;
+        let page = page_path(root, &file);
+        let body = render_markdown(&content, &page, &ctx);
+        let title = page.with_extension("").to_string_lossy().replace('\\', "/");
+        pages.push(SitePage {
+            html: layout(&title, &page, &ctx, &body),
+            path: page.clone(),
+        });
+        documents.push((title, page));
+    }
+
+    for node_type in &ctx.types {
+        pages.push(type_index_page(node_type, &blocks, &ctx));
+    }
+    pages.push(home_page(&documents, &blocks, &ctx));
+    pages.push(graph_page(&graph, &ctx));
+
+    // Markdown pages come first, so a collision is always with a generated page
+    let mut paths = HashSet::new();
+    if let Some(page) = pages.iter().find(|p| !paths.insert(&p.path)) {
+        return Err(Error::Other(format!(
+            "'{}' would overwrite the generated page '{}'; rename it or add it to graph.ignore",
+            page.path.with_extension("md").display(),
+            page.path.display()
+        )));
+    }
+
+    Ok(pages)
+}
+
+/// Write the pages below `out_dir`, creating directories as needed
+pub fn write_site(pages: &[SitePage], out_dir: &Path) -> Result<()> {
+    for page in pages {
+        let path = out_dir.join(&page.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &page.html)?;
+    }
+    Ok(())
+}
+
+/// Output path of the page rendered from a Markdown file
+fn page_path(root: &Path, file: &Path) -> PathBuf {
+    let rel = file.strip_prefix(root).unwrap_or(file);
+    let rel: PathBuf = rel
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect();
+    rel.with_extension("html")
+}
+
+/// Relative URL from one page to another, both relative to the output directory
+fn relative_href(from_page: &Path, to_page: &Path) -> String {
+    let depth = from_page.parent().map_or(0, |p| p.components().count());
+    let mut href = "../".repeat(depth);
+    href.push_str(&to_page.to_string_lossy().replace('\\', "/"));
+    href
+}
+
+fn graph_page_path() -> PathBuf {
+    Path::new(GENERATED_DIR).join("graph.html")
+}
+
+fn type_page(node_type: &str) -> PathBuf {
+    Path::new(GENERATED_DIR)
+        .join("types")
+        .join(format!("{}.html", node_type))
+}
+
+impl SiteContext<'_> {
+    /// URL of a spec block as seen from `page`
+    fn block_href(&self, id: &str, page: &Path) -> Option<String> {
+        self.targets.get(id).map(|t| {
+            if t.page == page {
+                format!("#{}", id)
+            } else {
+                format!("{}#{}", relative_href(page, &t.page), id)
+            }
+        })
+    }
+
+    /// Resolve `#ID` links to the page defining the ID and point `.md` links at the rendered page
+    fn rewrite_link(&self, dest: &str, page: &Path) -> Option<String> {
+        if dest.contains("://") || dest.starts_with("mailto:") {
+            return None;
+        }
+        let (path_part, fragment) = match dest.split_once('#') {
+            Some((p, f)) => (p, Some(f)),
+            None => (dest, None),
+        };
+        if let Some(id) = fragment
+            && let Some(href) = self.block_href(id, page)
+        {
+            return Some(href);
+        }
+        let stem = path_part.strip_suffix(".md")?;
+        Some(match fragment {
+            Some(f) => format!("{}.html#{}", stem, f),
+            None => format!("{}.html", stem),
+        })
+    }
+
+    fn link_text(&self, id: &str) -> String {
+        match self.targets.get(id).and_then(|t| t.block.name.as_deref()) {
+            Some(name) => format!("{} ({})", id, name),
+            None => id.to_string(),
+        }
+    }
+
+    /// "Referenced by" panel for a block, empty when nothing links to it
+    fn backlinks(&self, id: &str, page: &Path) -> String {
+        let Some(sources) = self.incoming.get(id) else {
+            return String::new();
+        };
+        let mut out = String::from("\n<aside class=\"backlinks\">\n<h4>Referenced by</h4>\n<ul>\n");
+        for (from, rel) in sources {
+            out.push_str(&format!(
+                "<li><a href=\"{}\">{}</a> <span class=\"rel\">{}</span></li>\n",
+                xml_escape(&self.block_href(from, page).unwrap_or_default()),
+                xml_escape(&self.link_text(from)),
+                xml_escape(rel)
+            ));
+        }
+        out.push_str("</ul>\n</aside>\n");
+        out
+    }
+}
+
+fn render_markdown(content: &str, page: &Path, ctx: &SiteContext) -> String {
+    let mut events = Vec::new();
+    let mut open_block: Option<String> = None;
+
+    for event in Parser::new_ext(content, Options::all()) {
+        match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                let dest_url = match ctx.rewrite_link(&dest_url, page) {
+                    Some(href) => CowStr::from(href),
+                    None => dest_url,
+                };
+                events.push(Event::Start(Tag::Link {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }));
+            }
+            Event::Html(ref raw) | Event::InlineHtml(ref raw) => {
+                // A new anchor closes the previous block: emit its backlinks first
+                if let Some(id) = parse_anchor_tag(raw)
+                    && let Some(prev) = open_block.replace(id)
+                {
+                    events.push(Event::Html(ctx.backlinks(&prev, page).into()));
+                }
+                events.push(event);
+            }
+            other => events.push(other),
+        }
+    }
+    if let Some(prev) = open_block {
+        events.push(Event::Html(ctx.backlinks(&prev, page).into()));
+    }
+
+    let mut out = String::new();
+    html::push_html(&mut out, events.into_iter());
+    out
+}
+
+fn type_index_page(node_type: &str, blocks: &[SpecBlock], ctx: &SiteContext) -> SitePage {
+    let path = type_page(node_type);
+    let desc = ctx
+        .config
+        .nodes
+        .get(node_type)
+        .map(|n| n.desc.as_str())
+        .unwrap_or_default();
+
+    let mut body = format!(
+        "<h1>{}</h1>\n<p>{}</p>\n",
+        xml_escape(node_type),
+        xml_escape(desc)
+    );
+    body.push_str("<table>\n<thead><tr><th>ID</th><th>Name</th><th>Referenced by</th><th>References</th></tr></thead>\n<tbody>\n");
+    let mut members: Vec<&SpecBlock> = blocks.iter().filter(|b| b.node_type == node_type).collect();
+    members.sort_by(|a, b| a.id.cmp(&b.id));
+    for block in members {
+        body.push_str(&format!(
+            "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            xml_escape(&ctx.block_href(&block.id, &path).unwrap_or_default()),
+            xml_escape(&block.id),
+            xml_escape(block.name.as_deref().unwrap_or_default()),
+            ctx.incoming.get(block.id.as_str()).map_or(0, |v| v.len()),
+            block.edges.len()
+        ));
+    }
+    body.push_str("</tbody>\n</table>\n");
+
+    SitePage {
+        html: layout(node_type, &path, ctx, &body),
+        path,
+    }
+}
+
+fn home_page(documents: &[(String, PathBuf)], blocks: &[SpecBlock], ctx: &SiteContext) -> SitePage {
+    let path = PathBuf::from("index.html");
+    let mut body = String::from("<h1>Documentation</h1>\n<h2>Node types</h2>\n<ul>\n");
+    for node_type in &ctx.types {
+        let count = blocks.iter().filter(|b| b.node_type == *node_type).count();
+        let desc = &ctx.config.nodes[*node_type].desc;
+        body.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> — {} ({} node(s))</li>\n",
+            xml_escape(&relative_href(&path, &type_page(node_type))),
+            xml_escape(node_type),
+            xml_escape(desc),
+            count
+        ));
+    }
+    body.push_str("</ul>\n<h2>Documents</h2>\n<ul>\n");
+    let sorted: BTreeMap<&str, &PathBuf> = documents
+        .iter()
+        .map(|(title, page)| (title.as_str(), page))
+        .collect();
+    for (title, page) in sorted {
+        body.push_str(&format!(
+            "<li><a href=\"{}\">{}</a></li>\n",
+            xml_escape(&relative_href(&path, page)),
+            xml_escape(title)
+        ));
+    }
+    body.push_str("</ul>\n");
+
+    SitePage {
+        html: layout("Documentation", &path, ctx, &body),
+        path,
+    }
+}
+
+fn graph_page(graph: &ExportGraph, ctx: &SiteContext) -> SitePage {
+    let path = graph_page_path();
+    let mut data = export::to_cytoscape(graph);
+    if let Some(nodes) = data["elements"]["nodes"].as_array_mut() {
+        for node in nodes {
+            let id = node["data"]["id"].as_str().unwrap_or_default().to_string();
+            node["data"]["href"] = ctx.block_href(&id, &path).unwrap_or_default().into();
+        }
+    }
+    // Keep "</script>" in names from closing the data block
+    let data = data.to_string().replace("</", "<\\/");
+
+    let body = format!(
+        "<h1>Graph</h1>\n<input id=\"search\" type=\"search\" placeholder=\"Highlight ID or name\">\n<div id=\"legend\"></div>\n<svg id=\"graph\"></svg>\n<script>\nconst DATA = {};\n{}</script>\n",
+        data, GRAPH_SCRIPT
+    );
+    SitePage {
+        html: layout("Graph", &path, ctx, &body),
+        path,
+    }
+}
+
+fn layout(title: &str, page: &Path, ctx: &SiteContext, body: &str) -> String {
+    let mut nav = format!(
+        "<a href=\"{}\">Home</a> <a href=\"{}\">Graph</a>",
+        relative_href(page, Path::new("index.html")),
+        relative_href(page, &graph_page_path())
+    );
+    for node_type in &ctx.types {
+        nav.push_str(&format!(
+            " <a href=\"{}\">{}</a>",
+            xml_escape(&relative_href(page, &type_page(node_type))),
+            xml_escape(node_type)
+        ));
+    }
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<nav>{}</nav>\n<main>\n{}</main>\n</body>\n</html>\n",
+        xml_escape(title),
+        STYLE,
+        nav,
+        body
+    )
+}
+
+const STYLE: &str = r#"body { font-family: system-ui, sans-serif; margin: 0; color: #222; }
+nav { background: #f4f4f4; padding: 0.6em 1em; border-bottom: 1px solid #ddd; }
+nav a { margin-right: 1em; }
+main { max-width: 960px; margin: 0 auto; padding: 1em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ddd; padding: 0.3em 0.6em; text-align: left; }
+.backlinks { background: #f8f8ff; border-left: 3px solid #88a; padding: 0.3em 1em; margin: 1em 0; }
+.backlinks h4 { margin: 0.3em 0; }
+.rel { color: #777; font-size: 0.9em; }
+#graph { width: 100%; height: 70vh; border: 1px solid #ddd; }
+#legend span { display: inline-block; margin-right: 1em; }
+#legend i { display: inline-block; width: 0.8em; height: 0.8em; margin-right: 0.3em; }
+"#;
+
+/// Dependency-free force-directed layout rendering `DATA` into the `#graph` SVG
+const GRAPH_SCRIPT: &str = r##"(function () {
+  const NS = "http://www.w3.org/2000/svg";
+  const svg = document.getElementById("graph");
+  const width = svg.clientWidth || 900, height = svg.clientHeight || 600;
+  svg.setAttribute("viewBox", "0 0 " + width + " " + height);
+  const nodes = DATA.elements.nodes.map((n, i) => {
+    const a = (2 * Math.PI * i) / Math.max(1, DATA.elements.nodes.length);
+    return Object.assign({}, n.data, {
+      x: width / 2 + (width / 3) * Math.cos(a), y: height / 2 + (height / 3) * Math.sin(a), vx: 0, vy: 0
+    });
+  });
+  const byId = new Map(nodes.map((n) => [n.id, n]));
+  const edges = DATA.elements.edges.map((e) => ({ s: byId.get(e.data.source), t: byId.get(e.data.target), rel: e.data.rel }));
+
+  const legend = document.getElementById("legend");
+  const types = new Map(nodes.map((n) => [n.type, n.color]));
+  types.forEach((color, type) => {
+    const item = document.createElement("span");
+    item.innerHTML = '<i style="background:' + color + '"></i>';
+    item.appendChild(document.createTextNode(type));
+    legend.appendChild(item);
+  });
+
+  const lines = edges.map((e) => {
+    const line = document.createElementNS(NS, "line");
+    line.setAttribute("stroke", "#999");
+    const title = document.createElementNS(NS, "title");
+    title.textContent = e.s.id + " " + e.rel + " " + e.t.id;
+    line.appendChild(title);
+    svg.appendChild(line);
+    return line;
+  });
+  let dragged = null;
+  const groups = nodes.map((n) => {
+    const g = document.createElementNS(NS, "g");
+    const circle = document.createElementNS(NS, "circle");
+    circle.setAttribute("r", 8);
+    circle.setAttribute("fill", n.color);
+    circle.setAttribute("stroke", "#555");
+    const label = document.createElementNS(NS, "text");
+    label.setAttribute("x", 11);
+    label.setAttribute("y", 4);
+    label.setAttribute("font-size", 11);
+    label.textContent = n.id;
+    const title = document.createElementNS(NS, "title");
+    title.textContent = n.name ? n.id + " (" + n.name + ")" : n.id;
+    g.append(circle, label, title);
+    g.style.cursor = "pointer";
+    g.addEventListener("pointerdown", (ev) => { dragged = n; n.moved = false; ev.preventDefault(); });
+    g.addEventListener("click", () => { if (!n.moved && n.href) window.location.href = n.href; });
+    svg.appendChild(g);
+    return g;
+  });
+  svg.addEventListener("pointermove", (ev) => {
+    if (!dragged) return;
+    const box = svg.getBoundingClientRect();
+    dragged.x = ((ev.clientX - box.left) / box.width) * width;
+    dragged.y = ((ev.clientY - box.top) / box.height) * height;
+    dragged.moved = true;
+    alpha = Math.max(alpha, 0.3);
+  });
+  window.addEventListener("pointerup", () => { dragged = null; });
+
+  document.getElementById("search").addEventListener("input", (ev) => {
+    const q = ev.target.value.toLowerCase();
+    nodes.forEach((n, i) => {
+      const hit = !q || n.id.toLowerCase().includes(q) || (n.name || "").toLowerCase().includes(q);
+      groups[i].style.opacity = hit ? 1 : 0.15;
+    });
+  });
+
+  let alpha = 1;
+  function tick() {
+    for (let i = 0; i < nodes.length; i++) {
+      for (let j = i + 1; j < nodes.length; j++) {
+        const a = nodes[i], b = nodes[j];
+        let dx = a.x - b.x, dy = a.y - b.y;
+        const d2 = Math.max(dx * dx + dy * dy, 1);
+        const f = (800 * alpha) / d2;
+        dx *= f; dy *= f;
+        a.vx += dx; a.vy += dy; b.vx -= dx; b.vy -= dy;
+      }
+    }
+    edges.forEach((e) => {
+      const dx = e.t.x - e.s.x, dy = e.t.y - e.s.y;
+      const d = Math.sqrt(dx * dx + dy * dy) || 1;
+      const f = ((d - 80) / d) * 0.05 * alpha;
+      e.s.vx += dx * f; e.s.vy += dy * f; e.t.vx -= dx * f; e.t.vy -= dy * f;
+    });
+    nodes.forEach((n) => {
+      n.vx += (width / 2 - n.x) * 0.002 * alpha;
+      n.vy += (height / 2 - n.y) * 0.002 * alpha;
+      if (n !== dragged) { n.x += n.vx; n.y += n.vy; }
+      n.vx *= 0.6; n.vy *= 0.6;
+      n.x = Math.max(10, Math.min(width - 10, n.x));
+      n.y = Math.max(10, Math.min(height - 10, n.y));
+    });
+    edges.forEach((e, i) => {
+      lines[i].setAttribute("x1", e.s.x); lines[i].setAttribute("y1", e.s.y);
+      lines[i].setAttribute("x2", e.t.x); lines[i].setAttribute("y2", e.t.y);
+    });
+    nodes.forEach((n, i) => groups[i].setAttribute("transform", "translate(" + n.x + "," + n.y + ")"));
+    alpha = Math.max(alpha * 0.99, 0.02);
+    requestAnimationFrame(tick);
+  }
+  tick();
+})();
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::NodeConfig;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_relative_href() {
+        assert_eq!(
+            relative_href(Path::new("a.html"), Path::new("sub/b.html")),
+            "sub/b.html"
+        );
+        assert_eq!(
+            relative_href(Path::new("sub/deep/b.html"), Path::new("a.html")),
+            "../../a.html"
+        );
+    }
+
+    #[test]
+    fn test_build_site() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("req")).unwrap();
+        fs::write(
+            dir.path().join("req/login.md"),
+            "<a id=\"REQ-01\"></a>\n\n# Login\n\n<a id=\"REQ-02\"></a>\n\n# Logout\n\nSee [REQ-01 (Login)](#REQ-01).\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("overview.md"),
+            "Start at [REQ-01 (Login)](req/login.md#REQ-01) or read [the notes](notes.md).\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.nodes.insert(
+            "REQ".to_string(),
+            NodeConfig {
+                desc: "Requirement".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let pages = build_site(dir.path(), &config).unwrap();
+        let page = |p: &str| {
+            pages
+                .iter()
+                .find(|page| page.path == Path::new(p))
+                .unwrap_or_else(|| panic!("missing page {}", p))
+        };
+
+        let overview = &page("overview.html").html;
+        assert!(overview.contains("href=\"req/login.html#REQ-01\""));
+        assert!(overview.contains("href=\"notes.html\""));
+
+        // The backlink panel of REQ-01 comes before the REQ-02 anchor
+        let login = &page("req/login.html").html;
+        let panel = login.find("Referenced by").unwrap();
+        assert!(panel < login.find("<a id=\"REQ-02\">").unwrap());
+        assert!(login.contains(
+            "<a href=\"#REQ-02\">REQ-02 (Logout)</a> <span class=\"rel\">references</span>"
+        ));
+        assert_eq!(login.matches("Referenced by").count(), 1);
+
+        let index = &page("_docgraph/types/REQ.html").html;
+        assert!(index.contains("<a href=\"../../req/login.html#REQ-01\">REQ-01</a>"));
+
+        assert!(page("index.html").html.contains("_docgraph/types/REQ.html"));
+        assert!(
+            page("_docgraph/graph.html")
+                .html
+                .contains("\"href\":\"../req/login.html#REQ-01\"")
+        );
+
+        // Documents named like the generated pages render next to them
+        fs::create_dir(dir.path().join("types")).unwrap();
+        fs::write(dir.path().join("types/REQ.md"), "# Types\n").unwrap();
+        fs::write(dir.path().join("graph.md"), "# Graph\n").unwrap();
+        let pages = build_site(dir.path(), &config).unwrap();
+        assert!(pages.iter().any(|p| p.path == Path::new("types/REQ.html")));
+
+        fs::write(dir.path().join("index.md"), "# Home\n").unwrap();
+        let err = build_site(dir.path(), &config).unwrap_err();
+        assert!(err.to_string().contains("'index.md' would overwrite"));
+    }
+}
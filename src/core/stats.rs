@@ -0,0 +1,276 @@
+use crate::core::config::Config;
+use crate::core::export::ExportGraph;
+use crate::core::rules::dg006;
+use crate::core::types::SpecBlock;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Summary metrics of the documentation graph
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GraphStats {
+    pub total_nodes: usize,
+    /// Links between two existing nodes, merged per pair of nodes
+    pub total_edges: usize,
+    /// Links to IDs that are not defined anywhere
+    pub broken_links: usize,
+    pub nodes_per_type: BTreeMap<String, usize>,
+    /// Nodes without any incoming or outgoing link, sorted by ID
+    pub orphans: Vec<String>,
+    /// Edges per node. Each edge is the fan-out of one node and the fan-in of another, so this
+    /// is both the average fan-in and the average fan-out.
+    pub avg_degree: f64,
+    /// Most incoming links of a single node
+    pub max_fan_in: usize,
+    /// Most outgoing links of a single node
+    pub max_fan_out: usize,
+    /// One entry per relationship rule in docgraph.toml
+    pub rules: Vec<RuleStats>,
+}
+
+/// How well the nodes of a type follow one of its relationship rules
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RuleStats {
+    pub node_type: String,
+    pub dir: String,
+    pub targets: Vec<String>,
+    pub rel: Option<String>,
+    pub min: Option<usize>,
+    /// Number of nodes of `node_type`
+    pub nodes: usize,
+    /// Nodes with at least one matching relation
+    pub linked: usize,
+    /// Percentage of nodes with at least one matching relation
+    pub coverage_pct: f64,
+    /// Nodes meeting the `min` constraint, as checked by DG006
+    pub satisfied: Option<usize>,
+    pub satisfaction_pct: Option<f64>,
+}
+
+/// Compute the metrics of the graph formed by `blocks`
+pub fn compute_stats(blocks: &[SpecBlock], config: &Config) -> GraphStats {
+    let graph = ExportGraph::build(blocks, config);
+    let ids: HashSet<&str> = blocks.iter().map(|b| b.id.as_str()).collect();
+
+    let mut nodes_per_type = BTreeMap::new();
+    for block in blocks {
+        *nodes_per_type.entry(block.node_type.clone()).or_insert(0) += 1;
+    }
+
+    let linked: HashSet<&str> = graph
+        .edges
+        .iter()
+        .flat_map(|e| [e.from.as_str(), e.to.as_str()])
+        .collect();
+    let mut orphans: Vec<String> = blocks
+        .iter()
+        .filter(|b| !linked.contains(b.id.as_str()))
+        .map(|b| b.id.clone())
+        .collect();
+    orphans.sort();
+    orphans.dedup();
+
+    let broken_links = blocks
+        .iter()
+        .flat_map(|b| &b.edges)
+        .filter(|e| !ids.contains(e.id.as_str()))
+        .count();
+
+    let mut fan_in: HashMap<&str, usize> = HashMap::new();
+    let mut fan_out: HashMap<&str, usize> = HashMap::new();
+    for edge in &graph.edges {
+        *fan_in.entry(edge.to.as_str()).or_insert(0) += 1;
+        *fan_out.entry(edge.from.as_str()).or_insert(0) += 1;
+    }
+
+    let incoming_types = dg006::incoming_types(blocks);
+    let mut types: Vec<(&String, _)> = config.nodes.iter().collect();
+    types.sort_by_key(|(k, _)| k.as_str());
+
+    let mut rules = Vec::new();
+    for (node_type, node_config) in types {
+        let members: Vec<&SpecBlock> = blocks
+            .iter()
+            .filter(|b| b.node_type == *node_type)
+            .collect();
+        for rule in &node_config.rules {
+            let counts: Vec<usize> = members
+                .iter()
+                .map(|b| dg006::rule_count(rule, b, &incoming_types))
+                .collect();
+            let linked = counts.iter().filter(|c| **c > 0).count();
+            let satisfied = rule
+                .min
+                .map(|min| counts.iter().filter(|c| **c >= min).count());
+            rules.push(RuleStats {
+                node_type: node_type.clone(),
+                dir: rule.dir.clone(),
+                targets: rule.targets.clone(),
+                rel: rule.rel.clone(),
+                min: rule.min,
+                nodes: members.len(),
+                linked,
+                coverage_pct: percent(linked, members.len()),
+                satisfied,
+                satisfaction_pct: satisfied.map(|s| percent(s, members.len())),
+            });
+        }
+    }
+
+    GraphStats {
+        total_nodes: blocks.len(),
+        total_edges: graph.edges.len(),
+        broken_links,
+        nodes_per_type,
+        orphans,
+        avg_degree: round(graph.edges.len() as f64 / blocks.len().max(1) as f64),
+        max_fan_in: fan_in.into_values().max().unwrap_or(0),
+        max_fan_out: fan_out.into_values().max().unwrap_or(0),
+        rules,
+    }
+}
+
+/// Percentage rounded to two decimals; 100 when there is nothing to cover
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        round(part as f64 * 100.0 / total as f64)
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Render the metrics as human-readable text
+pub fn to_text(stats: &GraphStats) -> String {
+    let mut out = format!(
+        "Nodes: {}\nEdges: {}\nBroken links: {}\n",
+        stats.total_nodes, stats.total_edges, stats.broken_links
+    );
+    out.push_str(&format!(
+        "Average degree: {:.2}\nMax fan-in: {}\nMax fan-out: {}\n",
+        stats.avg_degree, stats.max_fan_in, stats.max_fan_out
+    ));
+
+    out.push_str("\nNodes per type:\n");
+    let width = stats
+        .nodes_per_type
+        .keys()
+        .map(|k| k.len())
+        .max()
+        .unwrap_or(0);
+    for (node_type, count) in &stats.nodes_per_type {
+        out.push_str(&format!("  {:width$}  {}\n", node_type, count));
+    }
+
+    out.push_str(&format!("\nOrphan nodes: {}\n", stats.orphans.len()));
+    for id in &stats.orphans {
+        out.push_str(&format!("  {}\n", id));
+    }
+
+    if !stats.rules.is_empty() {
+        out.push_str("\nRules:\n");
+    }
+    for rule in &stats.rules {
+        let arrow = if rule.dir == "from" { "<-" } else { "->" };
+        let mut line = format!("  {} {} {}", rule.node_type, arrow, rule.targets.join("/"));
+        if let Some(rel) = &rule.rel {
+            line.push_str(&format!(" ({})", rel));
+        }
+        line.push_str(&format!(
+            ": {}/{} linked ({:.1}%)",
+            rule.linked, rule.nodes, rule.coverage_pct
+        ));
+        if let (Some(min), Some(satisfied), Some(pct)) =
+            (rule.min, rule.satisfied, rule.satisfaction_pct)
+        {
+            line.push_str(&format!(
+                ", min {} met by {}/{} ({:.1}%)",
+                min, satisfied, rule.nodes, pct
+            ));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{NodeConfig, RuleConfig};
+    use crate::core::types::EdgeUse;
+    use std::path::PathBuf;
+
+    fn block(id: &str, targets: &[&str]) -> SpecBlock {
+        SpecBlock {
+            id: id.to_string(),
+            node_type: id.split(['-', '_']).next().unwrap().to_string(),
+            name: None,
+            edges: targets
+                .iter()
+                .map(|t| EdgeUse {
+                    id: t.to_string(),
+                    name: None,
+                    line: 1,
+                    col_start: 1,
+                    col_end: 1,
+                })
+                .collect(),
+            file_path: PathBuf::from("test.md"),
+            line_start: 1,
+            line_end: 1,
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let blocks = vec![
+            block("FR-01", &[]),
+            block("FR-02", &[]),
+            block("MOD-01", &["FR-01", "FR-02", "XX-99"]),
+            block("MOD-02", &["FR-01"]),
+            block("UC-01", &[]),
+        ];
+        let mut config = Config::default();
+        config.nodes.insert(
+            "FR".to_string(),
+            NodeConfig {
+                rules: vec![RuleConfig {
+                    dir: "from".to_string(),
+                    targets: vec!["MOD".to_string()],
+                    min: Some(1),
+                    rel: Some("realized_by".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        );
+
+        let stats = compute_stats(&blocks, &config);
+        assert_eq!(stats.total_nodes, 5);
+        assert_eq!(stats.total_edges, 3);
+        assert_eq!(stats.broken_links, 1);
+        assert_eq!(stats.nodes_per_type["FR"], 2);
+        assert_eq!(stats.orphans, vec!["UC-01"]);
+        assert_eq!(stats.avg_degree, 0.6);
+        assert_eq!((stats.max_fan_in, stats.max_fan_out), (2, 2));
+
+        let rule = &stats.rules[0];
+        assert_eq!((rule.nodes, rule.linked), (2, 2));
+        assert_eq!(rule.coverage_pct, 100.0);
+        assert_eq!(rule.satisfied, Some(2));
+        assert_eq!(rule.satisfaction_pct, Some(100.0));
+
+        let text = to_text(&stats);
+        assert!(text.contains("Average degree: 0.60\nMax fan-in: 2\nMax fan-out: 2\n"));
+        assert!(text.contains("Orphan nodes: 1\n  UC-01\n"));
+        assert!(
+            text.contains(
+                "  FR <- MOD (realized_by): 2/2 linked (100.0%), min 1 met by 2/2 (100.0%)"
+            )
+        );
+    }
+}
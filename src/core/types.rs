@@ -31,6 +31,21 @@ pub struct RefUse {
     pub col_end: usize,
 }
 
+/// A `docgraph-disable` comment silencing diagnostics within a range of lines
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Suppression {
+    /// Rule codes to silence; empty silences every rule
+    pub codes: Vec<String>,
+    pub file_path: PathBuf,
+    /// Location of the comment itself
+    pub line: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+    /// First and last line (inclusive) covered by the suppression
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Diagnostic {
     pub severity: Severity,
@@ -38,15 +53,34 @@ pub struct Diagnostic {
     pub message: String,
     pub path: PathBuf,
     pub range: Range,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+/// A machine-applicable fix attached to a diagnostic
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Fix {
+    pub description: String,
+    pub edits: Vec<TextEdit>,
+}
+
+/// Replacement of a range of a file. An empty range inserts `new_text`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TextEdit {
+    pub path: PathBuf,
+    pub range: Range,
+    pub new_text: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Severity {
     Error,
     Warning,
+    Info,
+    Hint,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Range {
     pub start_line: usize,
     pub start_col: usize,
@@ -1,10 +1,50 @@
-use ignore::WalkBuilder;
 use ignore::gitignore::GitignoreBuilder;
+use ignore::{WalkBuilder, WalkState};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
+/// Every Markdown file of the workspace, sorted by path
 pub fn find_markdown_files(root: &Path, ignore_patterns: &[String]) -> Vec<PathBuf> {
-    let mut files = Vec::new();
+    map_markdown_files(root, ignore_patterns, |_| Some(()))
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Walk the workspace on several threads and run `visit` on every Markdown file as it is
+/// found. Returns the files for which `visit` produced a value, sorted by path.
+pub fn map_markdown_files<T, F>(
+    root: &Path,
+    ignore_patterns: &[String],
+    visit: F,
+) -> Vec<(PathBuf, T)>
+where
+    T: Send,
+    F: Fn(&Path) -> Option<T> + Sync,
+{
+    let results = Mutex::new(Vec::new());
+    walker(root, ignore_patterns).build_parallel().run(|| {
+        let results = &results;
+        let visit = &visit;
+        Box::new(move |result| {
+            // Silence traversal errors in core
+            if let Ok(entry) = result {
+                let path = entry.path();
+                let is_markdown = path.is_file() && path.extension().is_some_and(|ext| ext == "md");
+                if is_markdown && let Some(value) = visit(path) {
+                    results.lock().unwrap().push((path.to_path_buf(), value));
+                }
+            }
+            WalkState::Continue
+        })
+    });
 
+    let mut results = results.into_inner().unwrap();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    results
+}
+
+fn walker(root: &Path, ignore_patterns: &[String]) -> WalkBuilder {
     // Build gitignore matcher from config patterns
     let mut builder = GitignoreBuilder::new(root);
     for pattern in ignore_patterns {
@@ -13,7 +53,8 @@ pub fn find_markdown_files(root: &Path, ignore_patterns: &[String]) -> Vec<PathB
     let ignore_matcher = builder.build().ok();
 
     let root_owned = root.to_path_buf();
-    let walker = WalkBuilder::new(root)
+    let mut walker = WalkBuilder::new(root);
+    walker
         .hidden(false) // Look into hidden folders if needed
         .git_ignore(true)
         .filter_entry(move |entry| {
@@ -27,24 +68,8 @@ pub fn find_markdown_files(root: &Path, ignore_patterns: &[String]) -> Vec<PathB
                 return false;
             }
             true
-        })
-        .build();
-
-    for result in walker {
-        match result {
-            Ok(entry) => {
-                let path = entry.path();
-                if path.is_file()
-                    && let Some(ext) = path.extension()
-                    && ext == "md"
-                {
-                    files.push(path.to_path_buf());
-                }
-            }
-            Err(_err) => {} // Silence traversal errors in core
-        }
-    }
-    files
+        });
+    walker
 }
 
 #[cfg(test)]
@@ -118,4 +143,19 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].file_name().unwrap(), "b.md");
     }
+
+    #[test]
+    fn test_map_markdown_files_sorted() {
+        let dir = tempdir().unwrap();
+        for name in ["c.md", "a.md", "b.md", "skip.md"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+
+        let results = map_markdown_files(dir.path(), &[], |path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            (content != "skip.md").then_some(content)
+        });
+        let contents: Vec<_> = results.iter().map(|(_, c)| c.as_str()).collect();
+        assert_eq!(contents, vec!["a.md", "b.md", "c.md"]);
+    }
 }
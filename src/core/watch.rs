@@ -0,0 +1,198 @@
+use crate::core::config::Config;
+use crate::core::index::WorkspaceIndex;
+use crate::core::types::Diagnostic;
+use crate::core::walk::find_markdown_files;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modification time of every file a check of the workspace depends on
+pub type Stamps = BTreeMap<PathBuf, SystemTime>;
+
+/// What to do about a set of modified files
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    /// The config, the ignore rules or a template changed: every file must be re-checked
+    Reload,
+    /// A Markdown file was created or modified
+    Updated(PathBuf),
+    /// A Markdown file was deleted
+    Removed(PathBuf),
+}
+
+/// Stamp the Markdown files of the workspace, `docgraph.toml`, `.gitignore` and the node
+/// templates
+pub fn stamps(root: &Path, config: &Config) -> Stamps {
+    find_markdown_files(root, &config.graph.ignore)
+        .into_iter()
+        .chain(setting_files(root, config))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Compare two sets of stamps
+pub fn changes(root: &Path, config: &Config, before: &Stamps, after: &Stamps) -> Vec<Change> {
+    let settings = setting_files(root, config);
+    let is_setting = |path: &Path| settings.iter().any(|s| s == path);
+
+    let mut changes = Vec::new();
+    for (path, modified) in after {
+        if before.get(path) == Some(modified) {
+            continue;
+        }
+        if is_setting(path) {
+            return vec![Change::Reload];
+        }
+        changes.push(Change::Updated(path.clone()));
+    }
+    for path in before.keys().filter(|p| !after.contains_key(*p)) {
+        if is_setting(path) {
+            return vec![Change::Reload];
+        }
+        changes.push(Change::Removed(path.clone()));
+    }
+    changes
+}
+
+/// Re-parse the changed files. Returns false when the index must be reloaded instead.
+pub fn apply(index: &mut WorkspaceIndex, changes: &[Change]) -> bool {
+    for change in changes {
+        match change {
+            Change::Reload => return false,
+            Change::Updated(path) => {
+                // A file deleted since it was stamped is removed on the next poll
+                if let Ok(content) = fs::read_to_string(path) {
+                    index.update_file(path, content);
+                }
+            }
+            Change::Removed(path) => {
+                index.remove_file(path);
+            }
+        }
+    }
+    true
+}
+
+/// Drop diagnostics reported more than once for the same place, keeping the first
+pub fn dedup(diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    let mut unique: Vec<Diagnostic> = Vec::with_capacity(diagnostics.len());
+    for d in diagnostics {
+        let seen = unique.iter().any(|u| {
+            u.code == d.code && u.path == d.path && u.range == d.range && u.message == d.message
+        });
+        if !seen {
+            unique.push(d);
+        }
+    }
+    unique
+}
+
+/// Time of day of `time` as `HH:MM:SS` UTC
+pub fn clock(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Config, ignore rules and node templates: files whose changes require a full reload, as
+/// they change the result of checking every file
+pub fn setting_files(root: &Path, config: &Config) -> Vec<PathBuf> {
+    let mut files = vec![root.join("docgraph.toml"), root.join(".gitignore")];
+    for template in config.nodes.values().filter_map(|n| n.template.as_ref()) {
+        // Same resolution as DG007
+        let path = if template.exists() {
+            template.clone()
+        } else {
+            root.join(template)
+        };
+        files.push(path);
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::NodeConfig;
+    use std::time::Duration;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_changes() {
+        let root = Path::new("/ws");
+        let mut config = Config::default();
+        config.nodes.insert(
+            "FR".to_string(),
+            NodeConfig {
+                template: Some(PathBuf::from("templates/fr.md")),
+                ..Default::default()
+            },
+        );
+        let before: Stamps = [
+            (PathBuf::from("/ws/a.md"), at(1)),
+            (PathBuf::from("/ws/b.md"), at(1)),
+            (PathBuf::from("/ws/templates/fr.md"), at(1)),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut after = before.clone();
+        after.insert(PathBuf::from("/ws/a.md"), at(2));
+        after.insert(PathBuf::from("/ws/c.md"), at(2));
+        after.remove(Path::new("/ws/b.md"));
+        assert_eq!(
+            changes(root, &config, &before, &after),
+            vec![
+                Change::Updated(PathBuf::from("/ws/a.md")),
+                Change::Updated(PathBuf::from("/ws/c.md")),
+                Change::Removed(PathBuf::from("/ws/b.md")),
+            ]
+        );
+
+        after.insert(PathBuf::from("/ws/templates/fr.md"), at(2));
+        assert_eq!(
+            changes(root, &config, &before, &after),
+            vec![Change::Reload]
+        );
+        assert!(changes(root, &config, &before, &before).is_empty());
+    }
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(at(0)), "00:00:00");
+        assert_eq!(clock(at(86400 + 13 * 3600 + 5 * 60 + 9)), "13:05:09");
+    }
+
+    #[test]
+    fn test_apply_and_dedup() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        fs::write(&a, "<a id=\"REQ-1\"></a>\n# One\n").unwrap();
+        let mut index = WorkspaceIndex::load(dir.path(), Config::default(), None);
+        let generation = index.generation();
+
+        fs::write(&a, "<a id=\"REQ-1\"></a>\n# Two\n").unwrap();
+        assert!(apply(&mut index, &[Change::Updated(a.clone())]));
+        assert!(index.generation() > generation);
+        assert_eq!(index.blocks()[0].name.as_deref(), Some("Two"));
+        assert!(!apply(&mut index, &[Change::Reload]));
+
+        let diagnostics = index.diagnostics(None);
+        let mut doubled = diagnostics.clone();
+        doubled.extend(diagnostics.clone());
+        assert_eq!(dedup(doubled).len(), diagnostics.len());
+    }
+}
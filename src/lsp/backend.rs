@@ -1,25 +1,35 @@
-use crossbeam_channel::Sender;
+use crossbeam_channel::{RecvTimeoutError, Sender};
 use dashmap::DashMap;
-use lsp_server::{Connection, Message, Notification, RequestId, Response};
+use lsp_server::{Connection, ErrorCode, Message, Notification, RequestId, Response};
 use lsp_types::{
-    Diagnostic, FileSystemWatcher, InitializeParams, Position, PublishDiagnosticsParams, Range,
-    Registration, RegistrationParams, Uri, WatchKind, WorkspaceFolder,
+    ApplyWorkspaceEditParams, ApplyWorkspaceEditResponse, Diagnostic, FileChangeType,
+    FileSystemWatcher, InitializeParams, Position, PublishDiagnosticsParams, Range, Registration,
+    RegistrationParams, Uri, WatchKind, WorkspaceEdit, WorkspaceFolder,
     notification::{
         DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
-        DidSaveTextDocument, Initialized, Notification as _, PublishDiagnostics,
+        DidSaveTextDocument, Notification as _, PublishDiagnostics,
     },
     request::{
-        CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls, CallHierarchyPrepare, Completion,
-        DocumentSymbolRequest, GotoDefinition, HoverRequest, References, RegisterCapability,
-        Rename, Request, WorkspaceSymbolRequest,
+        ApplyWorkspaceEdit, CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls,
+        CallHierarchyPrepare, CodeActionRequest, CodeLensRequest, Completion,
+        DocumentSymbolRequest, ExecuteCommand, GotoDefinition, HoverRequest, InlayHintRequest,
+        PrepareRenameRequest, References, RegisterCapability, Rename, Request,
+        SemanticTokensFullRequest, SemanticTokensRangeRequest, WorkspaceSymbolRequest,
     },
 };
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use url::Url;
 
-use super::handlers;
-use crate::core::{collect, config, lint, types};
+use super::{handlers, text};
+use crate::core::index::WorkspaceIndex;
+use crate::core::{config, types, watch};
+
+/// Quiet period after the last edit before diagnostics are recomputed and published
+const LINT_DEBOUNCE: Duration = Duration::from_millis(250);
 
 pub struct Backend {
     pub sender: Sender<Message>,
@@ -27,6 +37,17 @@ pub struct Backend {
     pub blocks: Arc<Mutex<Vec<types::SpecBlock>>>,
     pub standalone_refs: Arc<Mutex<Vec<types::RefUse>>>,
     pub documents: Arc<DashMap<String, String>>,
+    index: Arc<Mutex<Option<WorkspaceIndex>>>,
+    /// Generation of the index that `blocks` and `standalone_refs` were copied from
+    snapshot_generation: Arc<Mutex<u64>>,
+    /// Diagnostics last sent for each file, so unchanged files are not published again
+    published: Arc<Mutex<HashMap<PathBuf, Vec<Diagnostic>>>>,
+    /// Pending debounced lint, run once no edit arrived before this instant
+    lint_deadline: Arc<Mutex<Option<Instant>>>,
+    /// Number of requests sent to the client, used for their IDs
+    requests_sent: Arc<AtomicU64>,
+    /// Commands whose workspace edit the client has not answered yet, by request ID
+    pending_edits: Arc<Mutex<HashMap<RequestId, String>>>,
 }
 
 impl Backend {
@@ -56,6 +77,12 @@ impl Backend {
             blocks: Arc::new(Mutex::new(Vec::new())),
             standalone_refs: Arc::new(Mutex::new(Vec::new())),
             documents: Arc::new(DashMap::new()),
+            index: Arc::new(Mutex::new(None)),
+            snapshot_generation: Arc::new(Mutex::new(0)),
+            published: Arc::new(Mutex::new(HashMap::new())),
+            lint_deadline: Arc::new(Mutex::new(None)),
+            requests_sent: Arc::new(AtomicU64::new(0)),
+            pending_edits: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -64,7 +91,30 @@ impl Backend {
         connection: Connection,
         _params: InitializeParams,
     ) -> anyhow::Result<()> {
-        for msg in &connection.receiver {
+        // `Connection::initialize` has already consumed the `initialized` notification
+        self.log_message("docgraph language server initialized");
+        if let Err(e) = self.register_file_watchers() {
+            self.log_message(format!("Failed to register file watchers: {}", e));
+        }
+        self.run_lint();
+
+        loop {
+            // Wait for the next message, or for the end of the debounce period after an edit
+            let deadline = *self.lint_deadline.lock().unwrap();
+            let msg = match deadline {
+                Some(deadline) => match connection.receiver.recv_deadline(deadline) {
+                    Ok(msg) => msg,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.publish_all_diagnostics();
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                },
+                None => match connection.receiver.recv() {
+                    Ok(msg) => msg,
+                    Err(_) => break,
+                },
+            };
             match msg {
                 Message::Request(req) => {
                     if connection.handle_shutdown(&req)? {
@@ -75,13 +125,14 @@ impl Backend {
                 Message::Notification(not) => {
                     self.dispatch_notification(not)?;
                 }
-                Message::Response(_) => {}
+                Message::Response(resp) => self.handle_response(resp),
             }
         }
         Ok(())
     }
 
     fn dispatch_request(&self, req: lsp_server::Request) -> anyhow::Result<()> {
+        self.refresh_snapshot();
         match req.method.as_str() {
             "textDocument/definition" => {
                 let (id, params) = cast_req::<GotoDefinition>(req)?;
@@ -99,8 +150,10 @@ impl Backend {
             }
             "textDocument/completion" => {
                 let (id, params) = cast_req::<Completion>(req)?;
-                let blocks = self.blocks.lock().unwrap();
-                let result = handlers::completion(&blocks, params)?;
+                let result = match self.index.lock().unwrap().as_ref() {
+                    Some(index) => handlers::completion(index, params)?,
+                    None => None,
+                };
                 self.send_response(id, result)?;
             }
             "textDocument/references" => {
@@ -110,12 +163,24 @@ impl Backend {
                 let result = handlers::references(&blocks, &refs, params)?;
                 self.send_response(id, result)?;
             }
+            "textDocument/prepareRename" => {
+                let (id, params) = cast_req::<PrepareRenameRequest>(req)?;
+                let result = match self.index.lock().unwrap().as_ref() {
+                    Some(index) => handlers::prepare_rename(index, params)?,
+                    None => None,
+                };
+                self.send_response(id, result)?;
+            }
             "textDocument/rename" => {
                 let (id, params) = cast_req::<Rename>(req)?;
-                let blocks = self.blocks.lock().unwrap();
-                let refs = self.standalone_refs.lock().unwrap();
-                let result = handlers::rename(&blocks, &refs, params)?;
-                self.send_response(id, result)?;
+                let result = match self.index.lock().unwrap().as_ref() {
+                    Some(index) => handlers::rename(index, params),
+                    None => Ok(None),
+                };
+                match result {
+                    Ok(result) => self.send_response(id, result)?,
+                    Err(e) => self.send_error(id, ErrorCode::RequestFailed, e.to_string())?,
+                }
             }
             "textDocument/prepareCallHierarchy" => {
                 let (id, params) = cast_req::<CallHierarchyPrepare>(req)?;
@@ -138,6 +203,63 @@ impl Backend {
                 let result = handlers::document_symbol(&blocks, params)?;
                 self.send_response(id, result)?;
             }
+            "textDocument/codeAction" => {
+                let (id, params) = cast_req::<CodeActionRequest>(req)?;
+                let result = match self.index.lock().unwrap().as_mut() {
+                    Some(index) => handlers::code_action(index, params)?,
+                    None => None,
+                };
+                self.send_response(id, result)?;
+            }
+            "textDocument/codeLens" => {
+                let (id, params) = cast_req::<CodeLensRequest>(req)?;
+                let result = match self.index.lock().unwrap().as_mut() {
+                    Some(index) => handlers::code_lens(index, params)?,
+                    None => None,
+                };
+                self.send_response(id, result)?;
+            }
+            "textDocument/semanticTokens/full" => {
+                let (id, params) = cast_req::<SemanticTokensFullRequest>(req)?;
+                let result = match self.index.lock().unwrap().as_ref() {
+                    Some(index) => handlers::semantic_tokens_full(index, params)?,
+                    None => None,
+                };
+                self.send_response(id, result)?;
+            }
+            "textDocument/semanticTokens/range" => {
+                let (id, params) = cast_req::<SemanticTokensRangeRequest>(req)?;
+                let result = match self.index.lock().unwrap().as_ref() {
+                    Some(index) => handlers::semantic_tokens_range(index, params)?,
+                    None => None,
+                };
+                self.send_response(id, result)?;
+            }
+            "textDocument/inlayHint" => {
+                let (id, params) = cast_req::<InlayHintRequest>(req)?;
+                let result = match self.index.lock().unwrap().as_ref() {
+                    Some(index) => handlers::inlay_hint(index, params)?,
+                    None => None,
+                };
+                self.send_response(id, result)?;
+            }
+            "workspace/executeCommand" => {
+                let (id, params) = cast_req::<ExecuteCommand>(req)?;
+                let command = params.command.clone();
+                let result = match self.index.lock().unwrap().as_ref() {
+                    Some(index) => handlers::execute_command(index, params),
+                    None => Ok(None),
+                };
+                match result {
+                    Ok(edit) => {
+                        if let Some(edit) = edit {
+                            self.apply_edit(&command, edit)?;
+                        }
+                        self.send_response(id, serde_json::Value::Null)?;
+                    }
+                    Err(e) => self.send_error(id, ErrorCode::RequestFailed, e.to_string())?,
+                }
+            }
             "workspace/symbol" => {
                 let (id, params) = cast_req::<WorkspaceSymbolRequest>(req)?;
                 let blocks = self.blocks.lock().unwrap();
@@ -153,44 +275,54 @@ impl Backend {
 
     fn dispatch_notification(&self, not: Notification) -> anyhow::Result<()> {
         match not.method.as_str() {
-            "initialized" => {
-                let _ = cast_not::<Initialized>(not)?;
-                self.log_message("docgraph language server initialized");
-                if let Err(e) = self.register_file_watchers() {
-                    self.log_message(format!("Failed to register file watchers: {}", e));
-                }
-                self.run_lint();
-            }
             "textDocument/didOpen" => {
                 let params = cast_not::<DidOpenTextDocument>(not)?;
                 self.log_message(format!("Document opened: {:?}", params.text_document.uri));
-                self.documents.insert(
-                    params.text_document.uri.to_string(),
-                    params.text_document.text,
-                );
-                self.run_lint();
+                let uri = params.text_document.uri.to_string();
+                self.documents
+                    .insert(uri.clone(), params.text_document.text.clone());
+                self.update_document(&uri, params.text_document.text);
+                // Always answer an open with the diagnostics of that document
+                if let Some(path) = uri_to_path(&uri) {
+                    self.published.lock().unwrap().remove(&path);
+                }
+                self.publish_all_diagnostics();
             }
             "workspace/didChangeWatchedFiles" => {
-                let _params = cast_not::<DidChangeWatchedFiles>(not)?;
-                self.log_message("Configuration file changed, re-linting...");
-                self.run_lint();
+                let params = cast_not::<DidChangeWatchedFiles>(not)?;
+                self.handle_watched_files(params.changes);
             }
             "textDocument/didChange" => {
                 let params = cast_not::<DidChangeTextDocument>(not)?;
-                // We assume full text sync
-                if let Some(change) = params.content_changes.first() {
-                    self.documents
-                        .insert(params.text_document.uri.to_string(), change.text.clone());
+                let uri = params.text_document.uri.to_string();
+                let mut text = match self.documents.get(&uri) {
+                    Some(doc) => doc.clone(),
+                    None => self.indexed_content(&uri).unwrap_or_default(),
+                };
+                // Changes are relative to the document after the previous change
+                for change in &params.content_changes {
+                    text::apply_change(&mut text, change);
+                }
+                self.documents.insert(uri.clone(), text.clone());
+                if self.update_document(&uri, text) {
+                    *self.lint_deadline.lock().unwrap() = Some(Instant::now() + LINT_DEBOUNCE);
                 }
-                self.run_lint();
             }
             "textDocument/didSave" => {
                 let _ = cast_not::<DidSaveTextDocument>(not)?;
-                self.run_lint();
+                self.publish_all_diagnostics();
             }
             "textDocument/didClose" => {
                 let params = cast_not::<DidCloseTextDocument>(not)?;
-                self.documents.remove(params.text_document.uri.as_str());
+                let uri = params.text_document.uri.to_string();
+                self.documents.remove(&uri);
+                // Unsaved edits are discarded, so fall back to the file on disk
+                if let Some(path) = uri_to_path(&uri) {
+                    if let Ok(content) = std::fs::read_to_string(&path) {
+                        self.update_document(&uri, content);
+                    }
+                    self.published.lock().unwrap().insert(path, vec![]);
+                }
                 self.publish_diagnostics(params.text_document.uri, vec![])?;
             }
             _ => {}
@@ -209,6 +341,67 @@ impl Backend {
         Ok(())
     }
 
+    fn send_error(&self, id: RequestId, code: ErrorCode, message: String) -> anyhow::Result<()> {
+        let resp = Response::new_err(id, code as i32, message);
+        self.sender.send(Message::Response(resp))?;
+        Ok(())
+    }
+
+    /// Ask the client to apply the workspace edit of a command; the edited files come back
+    /// as changes, and a refused edit is logged once the client answers
+    fn apply_edit(&self, command: &str, edit: WorkspaceEdit) -> anyhow::Result<()> {
+        let id = self.next_request_id();
+        self.pending_edits
+            .lock()
+            .unwrap()
+            .insert(id.clone(), command.to_string());
+        let req = lsp_server::Request::new(
+            id,
+            ApplyWorkspaceEdit::METHOD.to_string(),
+            ApplyWorkspaceEditParams {
+                label: Some(command.to_string()),
+                edit,
+            },
+        );
+        self.sender.send(Message::Request(req))?;
+        Ok(())
+    }
+
+    /// A unique ID for a request sent to the client
+    fn next_request_id(&self) -> RequestId {
+        let n = self.requests_sent.fetch_add(1, Ordering::Relaxed) + 1;
+        RequestId::from(format!("docgraph-{}", n))
+    }
+
+    /// Handle the client's answer to one of our requests
+    fn handle_response(&self, resp: Response) {
+        let Some(command) = self.pending_edits.lock().unwrap().remove(&resp.id) else {
+            return;
+        };
+        if let Some(error) = resp.error {
+            self.log_message(format!(
+                "Failed to apply the edit of {}: {}",
+                command, error.message
+            ));
+            return;
+        }
+        let applied = resp
+            .result
+            .and_then(|result| serde_json::from_value::<ApplyWorkspaceEditResponse>(result).ok());
+        if let Some(applied) = applied
+            && !applied.applied
+        {
+            self.log_message(format!(
+                "The client did not apply the edit of {}: {}",
+                command,
+                applied
+                    .failure_reason
+                    .as_deref()
+                    .unwrap_or("no reason given")
+            ));
+        }
+    }
+
     pub fn log_message<S: Into<String>>(&self, message: S) {
         let params = lsp_types::LogMessageParams {
             typ: lsp_types::MessageType::LOG,
@@ -228,6 +421,10 @@ impl Backend {
                 glob_pattern: lsp_types::GlobPattern::String("**/.gitignore".to_string()),
                 kind: Some(WatchKind::all()),
             },
+            FileSystemWatcher {
+                glob_pattern: lsp_types::GlobPattern::String("**/*.md".to_string()),
+                kind: Some(WatchKind::all()),
+            },
         ];
         let registration = Registration {
             id: "watch-config-files".to_string(),
@@ -240,7 +437,7 @@ impl Backend {
             registrations: vec![registration],
         };
         let req = lsp_server::Request::new(
-            RequestId::from("register-watchers".to_string()),
+            self.next_request_id(),
             RegisterCapability::METHOD.to_string(),
             params,
         );
@@ -259,7 +456,13 @@ impl Backend {
         Ok(())
     }
 
+    /// Rebuild the index from scratch (config, ignore rules and every file) and publish
     pub fn run_lint(&self) {
+        self.reload_index();
+        self.publish_all_diagnostics();
+    }
+
+    fn reload_index(&self) {
         let root_opt = self.workspace_root.lock().unwrap().clone();
         if let Some(root) = root_opt {
             let config = match config::Config::load(&root) {
@@ -282,89 +485,158 @@ impl Backend {
                 }
             };
 
-            // Create overrides map (convert DashMap<String, String> to HashMap<PathBuf, String>)
-            let mut overrides = std::collections::HashMap::new();
-            for entry in self.documents.iter() {
-                if let Ok(url) = Url::parse(entry.key())
-                    && let Ok(path) = url.to_file_path()
-                {
-                    // Try to canonicalize the path for consistent lookup
-                    if let Ok(canon_path) = std::fs::canonicalize(&path) {
-                        overrides.insert(canon_path, entry.value().clone());
-                    } else {
-                        overrides.insert(path, entry.value().clone());
-                    }
-                }
-            }
+            // Open documents take precedence over the files on disk
+            let overrides: HashMap<PathBuf, String> = self
+                .documents
+                .iter()
+                .filter_map(|entry| Some((uri_to_path(entry.key())?, entry.value().clone())))
+                .collect();
 
-            // Lint
-            let diagnostics =
-                lint::check_workspace(&root, false, None, true, &config, Some(&overrides));
+            let index = WorkspaceIndex::load(&root, config, Some(&overrides));
+            *self.blocks.lock().unwrap() = index.blocks();
+            *self.standalone_refs.lock().unwrap() = index.refs();
+            *self.snapshot_generation.lock().unwrap() = index.generation();
+            *self.index.lock().unwrap() = Some(index);
+        }
+    }
 
-            // Update index
-            let (blocks, refs) =
-                collect::collect_workspace_all(&root, &config.graph.ignore, Some(&overrides));
+    /// Re-parse a single indexed document. Returns true when the index changed.
+    fn update_document(&self, uri: &str, content: String) -> bool {
+        let Some(path) = uri_to_path(uri) else {
+            return false;
+        };
+        match self.index.lock().unwrap().as_mut() {
+            Some(index) if index.contains(&path) => index.update_file(&path, content),
+            _ => false,
+        }
+    }
+
+    fn indexed_content(&self, uri: &str) -> Option<String> {
+        let path = uri_to_path(uri)?;
+        let index = self.index.lock().unwrap();
+        index.as_ref()?.content(&path).map(str::to_string)
+    }
+
+    fn handle_watched_files(&self, changes: Vec<lsp_types::FileEvent>) {
+        let settings = match self.index.lock().unwrap().as_ref() {
+            Some(index) => watch::setting_files(index.root(), index.config()),
+            None => Vec::new(),
+        };
+        let mut reload = false;
+        for change in changes {
+            let Some(path) = uri_to_path(change.uri.as_str()) else {
+                continue;
+            };
+            let is_markdown = path.extension().is_some_and(|ext| ext == "md");
+            if !is_markdown || change.typ == FileChangeType::CREATED || settings.contains(&path) {
+                // Config, ignore rules, a template or a new file: the set of indexed files or
+                // the diagnostics of every file may change
+                reload = true;
+            } else if change.typ == FileChangeType::DELETED {
+                if let Some(index) = self.index.lock().unwrap().as_mut() {
+                    index.remove_file(&path);
+                }
+            } else if !self.documents.contains_key(change.uri.as_str())
+                && let Ok(content) = std::fs::read_to_string(&path)
             {
-                let mut b = self.blocks.lock().unwrap();
-                *b = blocks;
-                let mut r = self.standalone_refs.lock().unwrap();
-                *r = refs;
+                // Open documents are tracked through didChange instead
+                self.update_document(change.uri.as_str(), content);
             }
+        }
 
-            // Group diagnostics by file path
-            let mut file_diagnostics: std::collections::HashMap<PathBuf, Vec<Diagnostic>> =
-                std::collections::HashMap::new();
+        if reload {
+            self.log_message("Workspace files changed, re-indexing...");
+            self.reload_index();
+        }
+        self.publish_all_diagnostics();
+    }
 
-            // Initialize with all workspace files
-            let workspace_files =
-                crate::core::walk::find_markdown_files(&root, &config.graph.ignore);
-            for path in workspace_files {
+    /// Copy the blocks and references of the index for the request handlers, if it changed
+    fn refresh_snapshot(&self) {
+        let index = self.index.lock().unwrap();
+        let Some(index) = index.as_ref() else {
+            return;
+        };
+        let mut generation = self.snapshot_generation.lock().unwrap();
+        if *generation != index.generation() {
+            *self.blocks.lock().unwrap() = index.blocks();
+            *self.standalone_refs.lock().unwrap() = index.refs();
+            *generation = index.generation();
+        }
+    }
+
+    /// Lint the index and publish the diagnostics of every file whose diagnostics changed
+    fn publish_all_diagnostics(&self) {
+        *self.lint_deadline.lock().unwrap() = None;
+        let mut index = self.index.lock().unwrap();
+        let Some(index) = index.as_mut() else {
+            return;
+        };
+
+        // Group diagnostics by file path, starting with every indexed and open file
+        let mut file_diagnostics: HashMap<PathBuf, Vec<Diagnostic>> = index
+            .paths()
+            .map(|path| (path.to_path_buf(), Vec::new()))
+            .collect();
+        for entry in self.documents.iter() {
+            if let Some(path) = uri_to_path(entry.key()) {
                 file_diagnostics.entry(path).or_default();
             }
-            // Also open files
-            for entry in self.documents.iter() {
-                if let Ok(url) = Url::parse(entry.key())
-                    && let Ok(path) = url.to_file_path()
-                {
-                    file_diagnostics.entry(path).or_default();
-                }
-            }
+        }
 
-            for d in diagnostics {
-                let diag = Diagnostic {
-                    range: Range {
-                        start: Position {
-                            line: d.range.start_line as u32 - 1,
-                            character: d.range.start_col as u32 - 1,
-                        },
-                        end: Position {
-                            line: d.range.end_line as u32 - 1,
-                            character: d.range.end_col as u32 - 1,
-                        },
+        for d in index.diagnostics(None) {
+            let diag = Diagnostic {
+                range: Range {
+                    start: Position {
+                        line: d.range.start_line as u32 - 1,
+                        character: d.range.start_col as u32 - 1,
                     },
-                    severity: Some(match d.severity {
-                        types::Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
-                        types::Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
-                    }),
-                    code: Some(lsp_types::NumberOrString::String(d.code)),
-                    source: Some("docgraph".to_string()),
-                    message: d.message,
-                    ..Default::default()
-                };
-                file_diagnostics.entry(d.path).or_default().push(diag);
-            }
+                    end: Position {
+                        line: d.range.end_line as u32 - 1,
+                        character: d.range.end_col as u32 - 1,
+                    },
+                },
+                severity: Some(match d.severity {
+                    types::Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                    types::Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+                    types::Severity::Info => lsp_types::DiagnosticSeverity::INFORMATION,
+                    types::Severity::Hint => lsp_types::DiagnosticSeverity::HINT,
+                }),
+                code: Some(lsp_types::NumberOrString::String(d.code)),
+                source: Some("docgraph".to_string()),
+                message: d.message,
+                ..Default::default()
+            };
+            file_diagnostics.entry(d.path).or_default().push(diag);
+        }
 
-            for (path, diags) in file_diagnostics {
-                if let Ok(url) = Url::from_file_path(path)
-                    && let Ok(uri) = url.as_str().parse::<Uri>()
-                {
-                    let _ = self.publish_diagnostics(uri, diags);
-                }
+        // Clear files that left the index since the last publish
+        let mut published = self.published.lock().unwrap();
+        for path in published.keys() {
+            file_diagnostics.entry(path.clone()).or_default();
+        }
+
+        for (path, diags) in file_diagnostics {
+            if published.get(&path) == Some(&diags) {
+                continue;
+            }
+            if let Ok(url) = Url::from_file_path(&path)
+                && let Ok(uri) = url.as_str().parse::<Uri>()
+            {
+                let _ = self.publish_diagnostics(uri, diags.clone());
             }
+            published.insert(path, diags);
         }
     }
 }
 
+/// Convert a document URI to the path used as key in the index
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = Url::parse(uri).ok()?.to_file_path().ok()?;
+    // Try to canonicalize the path for consistent lookup
+    Some(std::fs::canonicalize(&path).unwrap_or(path))
+}
+
 fn cast_req<R>(req: lsp_server::Request) -> anyhow::Result<(RequestId, R::Params)>
 where
     R: lsp_types::request::Request,
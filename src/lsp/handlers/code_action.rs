@@ -0,0 +1,167 @@
+use crate::core::index::WorkspaceIndex;
+use crate::core::rules::dg006;
+use crate::core::{scaffold, types};
+use crate::lsp::handlers::command;
+use anyhow::Result;
+use lsp_types::*;
+use std::path::Path;
+use url::Url;
+
+/// Maximum number of "add link" actions offered for one DG006 diagnostic
+const MAX_LINK_CANDIDATES: usize = 10;
+
+pub fn code_action(
+    index: &mut WorkspaceIndex,
+    params: CodeActionParams,
+) -> Result<Option<CodeActionResponse>> {
+    let Ok(url) = Url::parse(params.text_document.uri.as_str()) else {
+        return Ok(None);
+    };
+    let Ok(path) = url.to_file_path() else {
+        return Ok(None);
+    };
+    let path = path.canonicalize().unwrap_or(path);
+    let first_line = params.range.start.line as usize + 1;
+    let last_line = params.range.end.line as usize + 1;
+
+    let diagnostics: Vec<types::Diagnostic> = index
+        .diagnostics(None)
+        .into_iter()
+        .filter(|d| d.path == path)
+        .filter(|d| d.range.start_line <= last_line && d.range.end_line >= first_line)
+        .collect();
+    let blocks = index.blocks();
+    let refs = index.refs();
+
+    let mut actions = Vec::new();
+    for d in &diagnostics {
+        let related = related_diagnostics(&params.context.diagnostics, d);
+
+        // Fixes computed by the rules themselves (DG001 heading, DG004 link text)
+        if let Some(fix) = &d.fix {
+            actions.push(quick_fix(
+                index,
+                &fix.description,
+                &fix.edits,
+                related.clone(),
+                true,
+            ));
+        }
+
+        match d.code.as_str() {
+            "DG003" => {
+                let Some(target_id) = broken_link_target(&blocks, &refs, d) else {
+                    continue;
+                };
+                // Put the new node next to the existing nodes of its type
+                let node_type = target_id.split(['-', '_']).next().unwrap_or(&target_id);
+                let file = blocks
+                    .iter()
+                    .find(|b| b.node_type == node_type)
+                    .map_or(path.as_path(), |b| b.file_path.as_path());
+                let content = index.content(file).unwrap_or_default();
+                let stub = scaffold::stub_node(index.root(), index.config(), &target_id, None);
+                let edit = scaffold::append_node(file, content, &stub);
+                actions.push(quick_fix(
+                    index,
+                    &format!(
+                        "Create node '{}' in {}",
+                        target_id,
+                        display_path(file, index.root())
+                    ),
+                    &[edit],
+                    related.clone(),
+                    false,
+                ));
+            }
+            "DG006" => {
+                let Some(block) = blocks
+                    .iter()
+                    .find(|b| b.file_path == path && b.line_start == d.range.start_line)
+                else {
+                    continue;
+                };
+                let content = index.content(&path).unwrap_or_default();
+                for target in dg006::link_candidates(index.config(), &blocks, block)
+                    .into_iter()
+                    .take(MAX_LINK_CANDIDATES)
+                {
+                    let title = match &target.name {
+                        Some(name) => format!("Add link to {} ({})", target.id, name),
+                        None => format!("Add link to {}", target.id),
+                    };
+                    let edit = scaffold::insert_link(content, block, target);
+                    actions.push(quick_fix(index, &title, &[edit], related.clone(), false));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if actions.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect(),
+    ))
+}
+
+/// The ID of the broken link a DG003 diagnostic points at
+fn broken_link_target(
+    blocks: &[types::SpecBlock],
+    refs: &[types::RefUse],
+    d: &types::Diagnostic,
+) -> Option<String> {
+    let at = |line: usize, col: usize| line == d.range.start_line && col == d.range.start_col;
+    blocks
+        .iter()
+        .filter(|b| b.file_path == d.path)
+        .flat_map(|b| &b.edges)
+        .find(|e| at(e.line, e.col_start))
+        .map(|e| e.id.clone())
+        .or_else(|| {
+            refs.iter()
+                .find(|r| r.file_path == d.path && at(r.line, r.col_start))
+                .map(|r| r.target_id.clone())
+        })
+}
+
+/// Diagnostics of the request context that `d` was published as
+fn related_diagnostics(context: &[Diagnostic], d: &types::Diagnostic) -> Vec<Diagnostic> {
+    context
+        .iter()
+        .filter(|c| {
+            c.code == Some(NumberOrString::String(d.code.clone()))
+                && c.range.start.line as usize + 1 == d.range.start_line
+        })
+        .cloned()
+        .collect()
+}
+
+/// A quick fix applying Core edits, whose byte columns are converted to UTF-16 positions
+fn quick_fix(
+    index: &WorkspaceIndex,
+    title: &str,
+    edits: &[types::TextEdit],
+    diagnostics: Vec<Diagnostic>,
+    preferred: bool,
+) -> CodeAction {
+    CodeAction {
+        title: title.to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: (!diagnostics.is_empty()).then_some(diagnostics),
+        edit: Some(command::workspace_edit(index, edits.to_vec())),
+        is_preferred: preferred.then_some(true),
+        ..Default::default()
+    }
+}
+
+fn display_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
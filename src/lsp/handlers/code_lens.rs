@@ -0,0 +1,174 @@
+use crate::core::index::WorkspaceIndex;
+use crate::core::rules::dg006;
+use crate::core::types;
+use crate::lsp::text;
+use anyhow::Result;
+use lsp_types::*;
+use std::collections::HashMap;
+use url::Url;
+
+/// Client command opening the references of the node at `[uri, position]`
+pub const SHOW_REFERENCES_COMMAND: &str = "docgraph.showReferences";
+/// Client command opening the outgoing call hierarchy of the node at `[uri, position]`
+pub const SHOW_OUTGOING_COMMAND: &str = "docgraph.showOutgoingCalls";
+/// Client command moving to the node at `[uri, position]` and opening the problems panel
+pub const SHOW_PROBLEMS_COMMAND: &str = "docgraph.showProblems";
+
+pub fn code_lens(
+    index: &mut WorkspaceIndex,
+    params: CodeLensParams,
+) -> Result<Option<Vec<CodeLens>>> {
+    let uri = params.text_document.uri;
+    let Ok(url) = Url::parse(uri.as_str()) else {
+        return Ok(None);
+    };
+    let Ok(path) = url.to_file_path() else {
+        return Ok(None);
+    };
+    let path = path.canonicalize().unwrap_or(path);
+    if !index.contains(&path) {
+        return Ok(None);
+    }
+
+    let diagnostics: Vec<types::Diagnostic> = index
+        .diagnostics(None)
+        .into_iter()
+        .filter(|d| d.path == path)
+        .collect();
+    let blocks = index.blocks();
+    let refs = index.refs();
+    let incoming_types = dg006::incoming_types(&blocks);
+    let mut incoming: HashMap<&str, usize> = HashMap::new();
+    for id in blocks
+        .iter()
+        .flat_map(|b| b.edges.iter().map(|e| e.id.as_str()))
+        .chain(refs.iter().map(|r| r.target_id.as_str()))
+    {
+        *incoming.entry(id).or_default() += 1;
+    }
+    let content = index.content(&path).unwrap_or_default();
+    let content_lines: Vec<&str> = content.lines().collect();
+
+    let mut lenses = Vec::new();
+    for block in blocks.iter().filter(|b| b.file_path == path) {
+        let line = content_lines
+            .get(block.line_start - 1)
+            .copied()
+            .unwrap_or("");
+        // Point at the ID inside the anchor so the references and call hierarchy
+        // requests resolve to this node
+        let col = line.find(block.id.as_str()).unwrap_or(0) + 1;
+        let position = text::position_at(content, block.line_start, col);
+        let range = Range {
+            start: position,
+            end: position,
+        };
+        let args = Some(vec![
+            serde_json::to_value(&uri)?,
+            serde_json::to_value(position)?,
+        ]);
+
+        let count = incoming.get(block.id.as_str()).copied().unwrap_or(0);
+        lenses.push(lens(
+            range,
+            &plural(count, "reference", "references"),
+            SHOW_REFERENCES_COMMAND,
+            args.clone(),
+        ));
+        lenses.push(lens(
+            range,
+            &format!("{} outgoing", block.edges.len()),
+            SHOW_OUTGOING_COMMAND,
+            args.clone(),
+        ));
+
+        let status = rule_status(index, block, &diagnostics, &incoming_types);
+        if !status.is_empty() {
+            lenses.push(lens(
+                range,
+                &status.join(" · "),
+                SHOW_PROBLEMS_COMMAND,
+                args,
+            ));
+        }
+    }
+    Ok(Some(lenses))
+}
+
+/// One short entry per rule with diagnostics inside the block, e.g. "DG006: missing MOD"
+fn rule_status(
+    index: &WorkspaceIndex,
+    block: &types::SpecBlock,
+    diagnostics: &[types::Diagnostic],
+    incoming_types: &HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    // `line_end` is the next anchor's line, which belongs to the next block
+    let last_line = if block.line_end > block.line_start {
+        block.line_end - 1
+    } else {
+        block.line_end
+    };
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for d in diagnostics
+        .iter()
+        .filter(|d| d.range.start_line >= block.line_start && d.range.start_line <= last_line)
+    {
+        match counts.iter_mut().find(|(code, _)| *code == d.code) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((&d.code, 1)),
+        }
+    }
+
+    counts
+        .into_iter()
+        .map(|(code, n)| {
+            let missing = if code == "DG006" {
+                dg006::missing_targets(index.config(), block, incoming_types)
+            } else {
+                Vec::new()
+            };
+            if !missing.is_empty() {
+                format!("{}: missing {}", code, missing.join(", "))
+            } else {
+                format!("{}: {}", code, plural(n, "issue", "issues"))
+            }
+        })
+        .collect()
+}
+
+fn plural(n: usize, one: &str, many: &str) -> String {
+    if n == 1 {
+        format!("1 {}", one)
+    } else {
+        format!("{} {}", n, many)
+    }
+}
+
+fn lens(
+    range: Range,
+    title: &str,
+    command: &str,
+    arguments: Option<Vec<serde_json::Value>>,
+) -> CodeLens {
+    CodeLens {
+        range,
+        command: Some(Command {
+            title: title.to_string(),
+            command: command.to_string(),
+            arguments,
+        }),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plural() {
+        assert_eq!(plural(0, "reference", "references"), "0 references");
+        assert_eq!(plural(1, "reference", "references"), "1 reference");
+        assert_eq!(plural(3, "issue", "issues"), "3 issues");
+    }
+}
@@ -0,0 +1,146 @@
+use crate::core::index::WorkspaceIndex;
+use crate::core::{refactor, scaffold, types};
+use crate::lsp::text;
+use anyhow::{Result, anyhow};
+use lsp_types::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Move a node to another file, with arguments `[id, to]`
+pub const MOVE_COMMAND: &str = "docgraph.move";
+/// Merge a node into another, with arguments `[into, from]`
+pub const MERGE_COMMAND: &str = "docgraph.merge";
+/// Split the file of a node at that node, with arguments `[id, to?]`
+pub const SPLIT_COMMAND: &str = "docgraph.split";
+/// Create a node from the template of its type, with arguments `[type, file, id?, title?]`
+pub const NEW_COMMAND: &str = "docgraph.new";
+
+/// Commands run through `workspace/executeCommand`
+pub fn commands() -> Vec<String> {
+    [MOVE_COMMAND, MERGE_COMMAND, SPLIT_COMMAND, NEW_COMMAND]
+        .iter()
+        .map(|c| c.to_string())
+        .collect()
+}
+
+/// The workspace edit performing a command, to be sent to the client with `workspace/applyEdit`
+pub fn execute_command(
+    index: &WorkspaceIndex,
+    params: ExecuteCommandParams,
+) -> Result<Option<WorkspaceEdit>> {
+    let args = params.arguments;
+    let edits = match params.command.as_str() {
+        MOVE_COMMAND => {
+            let id = string_arg(&args, 0)?;
+            let to = path_arg(index, &string_arg(&args, 1)?)?;
+            refactor::move_edits(index, &id, &to)?
+        }
+        MERGE_COMMAND => {
+            let into = string_arg(&args, 0)?;
+            let from = string_arg(&args, 1)?;
+            refactor::merge_edits(index, &into, &from)?
+        }
+        SPLIT_COMMAND => {
+            let id = string_arg(&args, 0)?;
+            let to = match args.get(1).and_then(|v| v.as_str()) {
+                Some(to) => Some(path_arg(index, to)?),
+                None => None,
+            };
+            refactor::split_edits(index, &id, to.as_deref())?
+        }
+        NEW_COMMAND => {
+            let node_type = string_arg(&args, 0)?;
+            let file = path_arg(index, &string_arg(&args, 1)?)?;
+            let id = args.get(2).and_then(|v| v.as_str());
+            let title = args.get(3).and_then(|v| v.as_str());
+            let (_, edit) = scaffold::new_node(index, &node_type, id, title, &file)?;
+            vec![edit]
+        }
+        command => return Err(anyhow!("unknown command '{}'", command)),
+    };
+    Ok(Some(workspace_edit(index, edits)))
+}
+
+/// Convert Core edits to a workspace edit, creating the files that do not exist yet
+pub(crate) fn workspace_edit(index: &WorkspaceIndex, edits: Vec<types::TextEdit>) -> WorkspaceEdit {
+    let mut files: Vec<(PathBuf, Uri, Vec<TextEdit>)> = Vec::new();
+    for edit in edits {
+        let Some(uri) = path_to_uri(&edit.path) else {
+            continue;
+        };
+        let content = match index.content(&edit.path) {
+            Some(content) => content.to_string(),
+            None => std::fs::read_to_string(&edit.path).unwrap_or_default(),
+        };
+        let path = edit.path.clone();
+        let edit = TextEdit {
+            range: Range {
+                start: text::position_at(&content, edit.range.start_line, edit.range.start_col),
+                end: text::position_at(&content, edit.range.end_line, edit.range.end_col),
+            },
+            new_text: edit.new_text,
+        };
+        match files.iter_mut().find(|(p, _, _)| *p == path) {
+            Some((_, _, file_edits)) => file_edits.push(edit),
+            None => files.push((path, uri, vec![edit])),
+        }
+    }
+
+    let creates = files.iter().any(|(path, _, _)| !path.exists());
+    if !creates {
+        #[allow(clippy::mutable_key_type)]
+        let changes: HashMap<Uri, Vec<TextEdit>> = files
+            .into_iter()
+            .map(|(_, uri, edits)| (uri, edits))
+            .collect();
+        return WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        };
+    }
+
+    // New files must be created before they can be edited
+    let mut operations = Vec::new();
+    for (path, uri, edits) in files {
+        if !path.exists() {
+            operations.push(DocumentChangeOperation::Op(ResourceOp::Create(
+                CreateFile {
+                    uri: uri.clone(),
+                    options: None,
+                    annotation_id: None,
+                },
+            )));
+        }
+        operations.push(DocumentChangeOperation::Edit(TextDocumentEdit {
+            text_document: OptionalVersionedTextDocumentIdentifier { uri, version: None },
+            edits: edits.into_iter().map(OneOf::Left).collect(),
+        }));
+    }
+    WorkspaceEdit {
+        document_changes: Some(DocumentChanges::Operations(operations)),
+        ..Default::default()
+    }
+}
+
+fn string_arg(args: &[serde_json::Value], i: usize) -> Result<String> {
+    args.get(i)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow!("missing argument {}", i + 1))
+}
+
+/// A file URI, or a path relative to the workspace root
+fn path_arg(index: &WorkspaceIndex, arg: &str) -> Result<PathBuf> {
+    if arg.starts_with("file:") {
+        return Url::parse(arg)
+            .ok()
+            .and_then(|u| u.to_file_path().ok())
+            .ok_or_else(|| anyhow!("invalid file URI '{}'", arg));
+    }
+    Ok(index.root().join(arg))
+}
+
+fn path_to_uri(path: &Path) -> Option<Uri> {
+    Url::from_file_path(path).ok()?.as_str().parse().ok()
+}
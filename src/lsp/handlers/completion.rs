@@ -1,16 +1,65 @@
+use crate::core::index::WorkspaceIndex;
+use crate::core::scaffold;
+use crate::lsp::text;
 use anyhow::Result;
 use lsp_types::*;
+use regex::Regex;
+use std::sync::OnceLock;
+use url::Url;
+
+static RE_LINK_DESTINATION: OnceLock<Regex> = OnceLock::new();
+static RE_LINK_TEXT: OnceLock<Regex> = OnceLock::new();
 
 pub fn completion(
-    blocks: &[crate::core::types::SpecBlock],
-    _: CompletionParams,
+    index: &WorkspaceIndex,
+    params: CompletionParams,
 ) -> Result<Option<CompletionResponse>> {
-    // Delegate candidate selection to Core
-    let candidates = crate::core::locate::completion_candidates(blocks);
+    let position = params.text_document_position.position;
+    let Ok(url) = Url::parse(params.text_document_position.text_document.uri.as_str()) else {
+        return Ok(None);
+    };
+    let Ok(path) = url.to_file_path() else {
+        return Ok(None);
+    };
+    let path = path.canonicalize().unwrap_or(path);
+    let (Some(document), Some(content)) = (index.document(&path), index.content(&path)) else {
+        return Ok(None);
+    };
+
+    let line_start = text::position_to_offset(content, Position::new(position.line, 0));
+    let cursor = text::position_to_offset(content, position);
+    let line = &content[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let (before, after) = line.split_at(cursor - line_start);
+
+    let Some(context) = link_context(before, after) else {
+        return Ok(None);
+    };
+    let utf16 = |s: &str| s.encode_utf16().count() as u32;
+    let range = Range {
+        start: Position::new(position.line, utf16(&before[..context.start])),
+        end: Position::new(
+            position.line,
+            utf16(before) + utf16(&after[..context.trailing]),
+        ),
+    };
+
+    // The block the cursor is in decides which targets are allowed
+    let line_no = position.line as usize + 1;
+    let current = document
+        .blocks
+        .iter()
+        .rev()
+        .find(|b| b.line_start <= line_no);
+
+    let blocks = index.blocks();
+    // Delegate candidate selection and ranking to Core
+    let candidates = crate::core::locate::completion_candidates(index.config(), &blocks, current);
 
     let items = candidates
         .into_iter()
-        .map(|block| CompletionItem {
+        .enumerate()
+        .map(|(rank, block)| CompletionItem {
             label: block.id.clone(),
             kind: Some(CompletionItemKind::REFERENCE),
             detail: block.name.clone(),
@@ -22,8 +71,78 @@ pub fn completion(
                     block.file_path.display()
                 ),
             })),
+            sort_text: Some(format!("{:05}", rank)),
+            // Match against what was typed from the opening bracket
+            filter_text: Some(match &block.name {
+                Some(name) => format!("{}{} {}", context.filter_prefix, block.id, name),
+                None => format!("{}{}", context.filter_prefix, block.id),
+            }),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                range,
+                new_text: scaffold::link_to(&path, block),
+            })),
             ..Default::default()
         })
         .collect();
     Ok(Some(CompletionResponse::Array(items)))
 }
+
+/// Where a link being typed starts, as byte offsets on the cursor line
+#[derive(Debug, PartialEq)]
+struct LinkContext {
+    /// Offset of the opening `[` in the text before the cursor
+    start: usize,
+    /// Closing characters right after the cursor that the link replaces
+    trailing: usize,
+    /// Text typed before the ID, for client-side filtering
+    filter_prefix: String,
+}
+
+/// Detect a link destination (`[text](#` or `[text](file.md#`) or link text (`[`) before the cursor
+fn link_context(before: &str, after: &str) -> Option<LinkContext> {
+    let destination = RE_LINK_DESTINATION
+        .get_or_init(|| Regex::new(r"\[[^\[\]]*\]\([^()\s#]*#[^()\s#]*$").unwrap());
+    if let Some(m) = destination.find(before) {
+        let typed = m.as_str();
+        return Some(LinkContext {
+            start: m.start(),
+            trailing: usize::from(after.starts_with(')')),
+            filter_prefix: typed[..=typed.rfind('#').unwrap_or(0)].to_string(),
+        });
+    }
+
+    let text = RE_LINK_TEXT.get_or_init(|| Regex::new(r"\[[^\[\]()]*$").unwrap());
+    text.find(before).map(|m| LinkContext {
+        start: m.start(),
+        trailing: usize::from(after.starts_with(']')),
+        filter_prefix: "[".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_context() {
+        assert_eq!(
+            link_context("See [REQ", ""),
+            Some(LinkContext {
+                start: 4,
+                trailing: 0,
+                filter_prefix: "[".to_string()
+            })
+        );
+        assert_eq!(
+            link_context("- [x](req.md#RE", ")"),
+            Some(LinkContext {
+                start: 2,
+                trailing: 1,
+                filter_prefix: "[x](req.md#".to_string()
+            })
+        );
+        assert_eq!(link_context("[", "]").map(|c| c.trailing), Some(1));
+        assert_eq!(link_context("plain text", ""), None);
+        assert_eq!(link_context("[done](#A) and", ""), None);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::core::index::WorkspaceIndex;
+use crate::core::parse;
+use anyhow::Result;
+use lsp_types::*;
+use std::collections::HashMap;
+use url::Url;
+
+pub fn inlay_hint(
+    index: &WorkspaceIndex,
+    params: InlayHintParams,
+) -> Result<Option<Vec<InlayHint>>> {
+    let Ok(url) = Url::parse(params.text_document.uri.as_str()) else {
+        return Ok(None);
+    };
+    let Ok(path) = url.to_file_path() else {
+        return Ok(None);
+    };
+    let path = path.canonicalize().unwrap_or(path);
+    let (Some(document), Some(content)) = (index.document(&path), index.content(&path)) else {
+        return Ok(None);
+    };
+    let content_lines: Vec<&str> = content.lines().collect();
+    let config = index.config();
+
+    let mut titles: HashMap<&str, Option<&str>> = HashMap::new();
+    let mut incoming: HashMap<&str, usize> = HashMap::new();
+    for doc in index.documents() {
+        for block in &doc.blocks {
+            titles.insert(&block.id, block.name.as_deref());
+            for edge in &block.edges {
+                *incoming.entry(edge.id.as_str()).or_default() += 1;
+            }
+        }
+        for r in &doc.refs {
+            *incoming.entry(r.target_id.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut hints = Vec::new();
+
+    // Incoming reference count after each anchor
+    for block in &document.blocks {
+        let Some(line) = content_lines.get(block.line_start - 1) else {
+            continue;
+        };
+        let end = line.find("</a>").map_or(line.len(), |i| i + "</a>".len());
+        let count = incoming.get(block.id.as_str()).copied().unwrap_or(0);
+        let label = if count == 1 {
+            "1 reference".to_string()
+        } else {
+            format!("{} references", count)
+        };
+        hints.push(hint(line, block.line_start, end, label, None, true));
+    }
+
+    // Relationship name before each edge
+    for block in &document.blocks {
+        for edge in &block.edges {
+            let Some(line) = content_lines.get(edge.line - 1) else {
+                continue;
+            };
+            let target_type = edge.id.split(['-', '_']).next().unwrap_or(&edge.id);
+            if let Some(rel) = config.relationship_rel(&block.node_type, target_type) {
+                hints.push(hint(
+                    line,
+                    edge.line,
+                    edge.col_start - 1,
+                    format!("{}:", rel),
+                    Some(InlayHintKind::PARAMETER),
+                    false,
+                ));
+            }
+        }
+    }
+
+    // Target title after links whose text is just the ID
+    let edges = document
+        .blocks
+        .iter()
+        .flat_map(|b| &b.edges)
+        .map(|e| (e.id.as_str(), e.line, e.col_start, e.col_end));
+    let refs = document
+        .refs
+        .iter()
+        .map(|r| (r.target_id.as_str(), r.line, r.col_start, r.col_end));
+    for (id, line_no, col_start, col_end) in edges.chain(refs) {
+        let Some(line) = content_lines.get(line_no - 1) else {
+            continue;
+        };
+        // Links spanning several lines have no end column on their first line
+        if col_end <= col_start {
+            continue;
+        }
+        let Some(link) = line.get(col_start - 1..col_end - 1) else {
+            continue;
+        };
+        if parse::parse_link_text(link).as_deref() != Some(id) {
+            continue;
+        }
+        if let Some(Some(title)) = titles.get(id) {
+            hints.push(hint(
+                line,
+                line_no,
+                col_end - 1,
+                title.to_string(),
+                None,
+                true,
+            ));
+        }
+    }
+
+    let lines = params.range.start.line..=params.range.end.line;
+    hints.retain(|h| lines.contains(&h.position.line));
+    hints.sort_by_key(|h| (h.position.line, h.position.character));
+    Ok(Some(hints))
+}
+
+/// A hint at the byte offset `at` of the 1-based line `line_no`, padded away from
+/// the text it annotates (trailing hints follow that text, leading ones precede it)
+fn hint(
+    line: &str,
+    line_no: usize,
+    at: usize,
+    label: String,
+    kind: Option<InlayHintKind>,
+    trailing: bool,
+) -> InlayHint {
+    let at = at.min(line.len());
+    InlayHint {
+        position: Position {
+            line: line_no as u32 - 1,
+            character: line[..at].encode_utf16().count() as u32,
+        },
+        label: InlayHintLabel::String(label),
+        kind,
+        text_edits: None,
+        tooltip: None,
+        padding_left: trailing.then_some(true),
+        padding_right: (!trailing).then_some(true),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hint_position_utf16() {
+        let line = "é [REQ-001](#REQ-001)";
+        let end = line.len();
+        let h = hint(line, 3, end, "Login".to_string(), None, true);
+        assert_eq!(h.position, Position::new(2, 21));
+        assert_eq!(h.padding_left, Some(true));
+        assert_eq!(h.padding_right, None);
+
+        let h = hint(line, 1, 3, "rel:".to_string(), None, false);
+        assert_eq!(h.position, Position::new(0, 2));
+        assert_eq!(h.padding_right, Some(true));
+    }
+}
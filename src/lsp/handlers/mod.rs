@@ -1,15 +1,25 @@
 mod call_hierarchy;
+mod code_action;
+mod code_lens;
+mod command;
 mod completion;
 mod definition;
 mod hover;
+mod inlay_hint;
 mod references;
 mod rename;
+mod semantic_tokens;
 mod symbols;
 
 pub use call_hierarchy::*;
+pub use code_action::*;
+pub use code_lens::*;
+pub use command::*;
 pub use completion::*;
 pub use definition::*;
 pub use hover::*;
+pub use inlay_hint::*;
 pub use references::*;
 pub use rename::*;
+pub use semantic_tokens::*;
 pub use symbols::*;
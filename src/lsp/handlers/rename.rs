@@ -1,67 +1,67 @@
+use crate::core::index::WorkspaceIndex;
+use crate::core::{locate, refactor};
+use crate::lsp::text;
 use anyhow::Result;
 use lsp_types::*;
+use std::path::PathBuf;
 use url::Url;
 
-pub fn rename(
-    blocks: &[crate::core::types::SpecBlock],
-    refs: &[crate::core::types::RefUse],
-    params: RenameParams,
-) -> Result<Option<WorkspaceEdit>> {
-    let uri = params.text_document_position.text_document.uri;
-    let position = params.text_document_position.position;
-    let line = position.line as usize + 1;
-    let col = position.character as usize + 1;
+pub fn prepare_rename(
+    index: &WorkspaceIndex,
+    params: TextDocumentPositionParams,
+) -> Result<Option<PrepareRenameResponse>> {
+    let Some((path, target_id)) = id_at(index, &params) else {
+        return Ok(None);
+    };
+    let content = index.content(&path).unwrap_or_default();
+    let line_no = params.position.line as usize + 1;
+    let line = content.lines().nth(line_no - 1).unwrap_or("");
+    let cursor = text::position_to_offset(content, params.position)
+        - text::position_to_offset(content, Position::new(params.position.line, 0));
 
-    if let Ok(url) = Url::parse(uri.as_str())
-        && let Ok(path) = url.to_file_path()
-    {
-        let path = path.canonicalize().unwrap_or(path);
-
-        // Delegate to Core Logic
-        if let Some(target_id) =
-            crate::core::locate::locate_id_at_position(blocks, refs, &path, line, col)
-        {
-            let locations = crate::core::locate::find_references_msg(blocks, refs, &target_id);
-
-            #[allow(clippy::mutable_key_type)]
-            let mut changes: std::collections::HashMap<Uri, Vec<TextEdit>> =
-                std::collections::HashMap::new();
+    // Prefer the occurrence under the cursor, e.g. the fragment when on the link destination
+    let occurrences = refactor::id_occurrences(line, &target_id);
+    let Some(found) = occurrences
+        .iter()
+        .find(|r| r.start <= cursor && cursor <= r.end)
+        .or(occurrences.first())
+    else {
+        return Ok(None);
+    };
+    Ok(Some(PrepareRenameResponse::RangeWithPlaceholder {
+        range: Range {
+            start: text::position_at(content, line_no, found.start + 1),
+            end: text::position_at(content, line_no, found.end + 1),
+        },
+        placeholder: target_id,
+    }))
+}
 
-            for loc in locations {
-                if let Ok(u) = Url::from_file_path(&loc.file_path)
-                    && let Ok(uri) = u.as_str().parse::<Uri>()
-                {
-                    let edit = TextEdit {
-                        range: Range {
-                            start: Position {
-                                line: loc.range_start_line as u32 - 1,
-                                character: loc.range_start_col as u32 - 1,
-                            },
-                            end: Position {
-                                line: loc.range_end_line as u32 - 1,
-                                character: loc.range_end_col as u32 - 1,
-                            },
-                        },
-                        new_text: params.new_name.clone(),
-                    };
+pub fn rename(index: &WorkspaceIndex, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+    let Some((_, target_id)) = id_at(index, &params.text_document_position) else {
+        return Ok(None);
+    };
 
-                    let edits = changes.entry(uri).or_default();
+    // Rejected renames are reported back to the client as request errors
+    let edits = refactor::rename_edits(index, &target_id, &params.new_name)?;
 
-                    // Simple deduplication: allow if exact range doesn't exist
-                    // Note: ideally we should check for overlap, but exact duplicate is the likely cause here.
-                    let is_duplicate = edits.iter().any(|e| e.range == edit.range);
+    Ok(Some(super::command::workspace_edit(index, edits)))
+}
 
-                    if !is_duplicate {
-                        edits.push(edit);
-                    }
-                }
-            }
+/// The file and the node ID at a position, if the position is on an anchor or a link
+fn id_at(index: &WorkspaceIndex, params: &TextDocumentPositionParams) -> Option<(PathBuf, String)> {
+    let url = Url::parse(params.text_document.uri.as_str()).ok()?;
+    let path = url.to_file_path().ok()?;
+    let path = path.canonicalize().unwrap_or(path);
+    let content = index.content(&path)?;
 
-            return Ok(Some(WorkspaceEdit {
-                changes: Some(changes),
-                ..Default::default()
-            }));
-        }
-    }
-    Ok(None)
+    // Core positions are byte columns
+    let line = params.position.line as usize + 1;
+    let col = text::position_to_offset(content, params.position)
+        - text::position_to_offset(content, Position::new(params.position.line, 0))
+        + 1;
+    let blocks = index.blocks();
+    let refs = index.refs();
+    let target_id = locate::locate_id_at_position(&blocks, &refs, &path, line, col)?;
+    Some((path, target_id))
 }
@@ -0,0 +1,245 @@
+use crate::core::index::WorkspaceIndex;
+use anyhow::Result;
+use lsp_types::*;
+use std::collections::HashSet;
+use url::Url;
+
+const TOKEN_NODE: u32 = 0;
+const TOKEN_NODE_TYPE: u32 = 1;
+
+const MOD_DEFINITION: u32 = 1 << 0;
+const MOD_RESOLVED: u32 = 1 << 1;
+const MOD_BROKEN: u32 = 1 << 2;
+
+/// Token types and modifiers, indexed by the constants above
+pub fn semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![SemanticTokenType::CLASS, SemanticTokenType::TYPE],
+        token_modifiers: vec![
+            SemanticTokenModifier::DEFINITION,
+            SemanticTokenModifier::new("resolved"),
+            SemanticTokenModifier::new("broken"),
+        ],
+    }
+}
+
+pub fn semantic_tokens_full(
+    index: &WorkspaceIndex,
+    params: SemanticTokensParams,
+) -> Result<Option<SemanticTokensResult>> {
+    Ok(
+        document_tokens(index, &params.text_document.uri, None).map(|data| {
+            SemanticTokensResult::Tokens(SemanticTokens {
+                result_id: None,
+                data,
+            })
+        }),
+    )
+}
+
+pub fn semantic_tokens_range(
+    index: &WorkspaceIndex,
+    params: SemanticTokensRangeParams,
+) -> Result<Option<SemanticTokensRangeResult>> {
+    let lines = params.range.start.line..=params.range.end.line;
+    Ok(
+        document_tokens(index, &params.text_document.uri, Some(lines)).map(|data| {
+            SemanticTokensRangeResult::Tokens(SemanticTokens {
+                result_id: None,
+                data,
+            })
+        }),
+    )
+}
+
+/// A token before delta encoding: 0-based line, UTF-16 start and length
+struct Token {
+    line: u32,
+    start: u32,
+    length: u32,
+    token_type: u32,
+    modifiers: u32,
+}
+
+fn document_tokens(
+    index: &WorkspaceIndex,
+    uri: &Uri,
+    lines: Option<std::ops::RangeInclusive<u32>>,
+) -> Option<Vec<SemanticToken>> {
+    let path = Url::parse(uri.as_str()).ok()?.to_file_path().ok()?;
+    let path = path.canonicalize().unwrap_or(path);
+    let document = index.document(&path)?;
+    let content = index.content(&path)?;
+    let content_lines: Vec<&str> = content.lines().collect();
+
+    let known_ids: HashSet<&str> = index
+        .documents()
+        .flat_map(|d| d.blocks.iter().map(|b| b.id.as_str()))
+        .collect();
+
+    let mut tokens = Vec::new();
+
+    // Definitions: the ID inside the anchor tag
+    for block in &document.blocks {
+        if let Some(line) = content_lines.get(block.line_start - 1)
+            && let Some(start) = anchor_id_offset(line, &block.id)
+        {
+            push_id_tokens(
+                &mut tokens,
+                line,
+                block.line_start,
+                start,
+                &block.id,
+                MOD_DEFINITION,
+            );
+        }
+    }
+
+    // References: the `#ID` fragment of each link
+    let edges = document
+        .blocks
+        .iter()
+        .flat_map(|b| &b.edges)
+        .map(|e| (e.id.as_str(), e.line, e.col_start, e.col_end));
+    let refs = document
+        .refs
+        .iter()
+        .map(|r| (r.target_id.as_str(), r.line, r.col_start, r.col_end));
+    for (id, line_no, col_start, col_end) in edges.chain(refs) {
+        let Some(line) = content_lines.get(line_no - 1) else {
+            continue;
+        };
+        // Links spanning several lines end on another line; search to the end of this one
+        let end = if col_end > col_start {
+            (col_end - 1).min(line.len())
+        } else {
+            line.len()
+        };
+        let Some(link) = line.get(col_start - 1..end) else {
+            continue;
+        };
+        if let Some(pos) = link.rfind(&format!("#{}", id)) {
+            let modifiers = if known_ids.contains(id) {
+                MOD_RESOLVED
+            } else {
+                MOD_BROKEN
+            };
+            push_id_tokens(
+                &mut tokens,
+                line,
+                line_no,
+                col_start - 1 + pos + 1,
+                id,
+                modifiers,
+            );
+        }
+    }
+
+    if let Some(lines) = lines {
+        tokens.retain(|t| lines.contains(&t.line));
+    }
+    tokens.sort_by_key(|t| (t.line, t.start));
+    Some(encode(&tokens))
+}
+
+/// Byte offset of the ID in `<a id="ID">` on the line
+fn anchor_id_offset(line: &str, id: &str) -> Option<usize> {
+    ["\"", "'"].iter().find_map(|quote| {
+        line.find(&format!("id={}{}{}", quote, id, quote))
+            .map(|i| i + "id=".len() + 1)
+    })
+}
+
+/// Split an ID into its node type prefix and the rest, e.g. `FR` + `_LOGIN`
+fn push_id_tokens(
+    tokens: &mut Vec<Token>,
+    line: &str,
+    line_no: usize,
+    start: usize,
+    id: &str,
+    modifiers: u32,
+) {
+    let utf16 = |s: &str| s.encode_utf16().count() as u32;
+    let line_idx = line_no as u32 - 1;
+    let start16 = utf16(&line[..start]);
+    let prefix_len = id.find(['-', '_']).unwrap_or(id.len());
+    tokens.push(Token {
+        line: line_idx,
+        start: start16,
+        length: utf16(&id[..prefix_len]),
+        token_type: TOKEN_NODE_TYPE,
+        modifiers,
+    });
+    if prefix_len < id.len() {
+        tokens.push(Token {
+            line: line_idx,
+            start: start16 + utf16(&id[..prefix_len]),
+            length: utf16(&id[prefix_len..]),
+            token_type: TOKEN_NODE,
+            modifiers,
+        });
+    }
+}
+
+/// Relative encoding of sorted tokens, as required by the protocol
+fn encode(tokens: &[Token]) -> Vec<SemanticToken> {
+    let mut data = Vec::with_capacity(tokens.len());
+    let (mut prev_line, mut prev_start) = (0, 0);
+    for t in tokens {
+        let delta_line = t.line - prev_line;
+        let delta_start = if delta_line == 0 {
+            t.start - prev_start
+        } else {
+            t.start
+        };
+        data.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: t.length,
+            token_type: t.token_type,
+            token_modifiers_bitset: t.modifiers,
+        });
+        prev_line = t.line;
+        prev_start = t.start;
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_relative_positions() {
+        let token = |line, start, length| Token {
+            line,
+            start,
+            length,
+            token_type: TOKEN_NODE,
+            modifiers: 0,
+        };
+        let data = encode(&[token(1, 4, 2), token(1, 8, 3), token(3, 2, 1)]);
+        let deltas: Vec<(u32, u32, u32)> = data
+            .iter()
+            .map(|t| (t.delta_line, t.delta_start, t.length))
+            .collect();
+        assert_eq!(deltas, vec![(1, 4, 2), (0, 4, 3), (2, 2, 1)]);
+    }
+
+    #[test]
+    fn test_push_id_tokens_utf16() {
+        let line = "é [x](#FR_LOGIN)";
+        let mut tokens = Vec::new();
+        let start = line.find("FR_").unwrap();
+        push_id_tokens(&mut tokens, line, 1, start, "FR_LOGIN", MOD_RESOLVED);
+
+        assert_eq!(tokens.len(), 2);
+        assert_eq!((tokens[0].start, tokens[0].length), (7, 2));
+        assert_eq!(tokens[0].token_type, TOKEN_NODE_TYPE);
+        assert_eq!((tokens[1].start, tokens[1].length), (9, 6));
+        assert_eq!(
+            anchor_id_offset("<a id=\"FR_LOGIN\"></a>", "FR_LOGIN"),
+            Some(7)
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use lsp_types::{Position, TextDocumentContentChangeEvent};
+
+/// Apply one `didChange` content change to a document. A change without a range replaces the
+/// whole text, as in full synchronization.
+pub fn apply_change(text: &mut String, change: &TextDocumentContentChangeEvent) {
+    match change.range {
+        Some(range) => {
+            let start = position_to_offset(text, range.start);
+            let end = position_to_offset(text, range.end).max(start);
+            text.replace_range(start..end, &change.text);
+        }
+        None => *text = change.text.clone(),
+    }
+}
+
+/// Convert an LSP position (0-based line, UTF-16 code units) to a byte offset in `text`.
+/// Positions past the end of a line or of the document are clamped.
+pub fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |i| line_start + i);
+    let mut units = 0;
+    for (i, c) in text[line_start..line_end].char_indices() {
+        if units >= position.character as usize {
+            return line_start + i;
+        }
+        units += c.len_utf16();
+    }
+    line_end
+}
+
+/// Convert a 1-based line and byte column, as used by the core, to an LSP position
+pub fn position_at(text: &str, line: usize, col: usize) -> Position {
+    let line_text = text.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    let end = col.saturating_sub(1).min(line_text.len());
+    let prefix = line_text.get(..end).unwrap_or(line_text);
+    Position::new(
+        line.saturating_sub(1) as u32,
+        prefix.encode_utf16().count() as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Range;
+
+    fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range {
+                start: Position::new(start.0, start.1),
+                end: Position::new(end.0, end.1),
+            }),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_position_to_offset_utf16() {
+        // "é" is 2 bytes / 1 unit, "😀" is 4 bytes / 2 units
+        let text = "aé😀b\nxyz";
+        assert_eq!(position_to_offset(text, Position::new(0, 0)), 0);
+        assert_eq!(position_to_offset(text, Position::new(0, 2)), 3);
+        assert_eq!(position_to_offset(text, Position::new(0, 4)), 7);
+        assert_eq!(position_to_offset(text, Position::new(0, 99)), 8);
+        assert_eq!(position_to_offset(text, Position::new(1, 1)), 10);
+        assert_eq!(position_to_offset(text, Position::new(5, 0)), text.len());
+    }
+
+    #[test]
+    fn test_position_at_utf16() {
+        let text = "# Title\né [x](#ID-1)\n";
+        assert_eq!(position_at(text, 1, 3), Position::new(0, 2));
+        assert_eq!(position_at(text, 2, 10), Position::new(1, 8));
+        assert_eq!(position_at(text, 2, 99), Position::new(1, 12));
+    }
+
+    #[test]
+    fn test_apply_changes_in_order() {
+        let mut text = "# Title\n\nSee 😀 [x](#ID-1)\n".to_string();
+        for c in [
+            change((2, 8), (2, 9), "ID-1 (Start)"),
+            change((0, 2), (0, 7), "Heading"),
+            change((3, 0), (3, 0), "new line\n"),
+        ] {
+            apply_change(&mut text, &c);
+        }
+        assert_eq!(
+            text,
+            "# Heading\n\nSee 😀 [ID-1 (Start)](#ID-1)\nnew line\n"
+        );
+
+        let full = TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "replaced".to_string(),
+        };
+        apply_change(&mut text, &full);
+        assert_eq!(text, "replaced");
+    }
+}
@@ -6,7 +6,17 @@ mod common;
 mod describe;
 #[path = "cli/graph.rs"]
 mod graph;
+#[path = "cli/matrix.rs"]
+mod matrix;
+#[path = "cli/new.rs"]
+mod new;
 #[path = "cli/query.rs"]
 mod query;
+#[path = "cli/refactor.rs"]
+mod refactor;
 #[path = "cli/rule.rs"]
 mod rule;
+#[path = "cli/site.rs"]
+mod site;
+#[path = "cli/stats.rs"]
+mod stats;
@@ -90,6 +90,65 @@ fn check_json_output() {
     assert!(stdout.starts_with('[') || stdout.starts_with('{'));
 }
 
+#[test]
+fn check_sarif_output() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    create_docs_with_duplicate_id(tmp.path(), "TEST-01");
+
+    let output = assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg("--format")
+        .arg("sarif")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert!(results.iter().any(|r| r["ruleId"] == "DG002"));
+}
+
+#[test]
+fn check_junit_output() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    create_doc_missing_heading(tmp.path(), "TEST-01");
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg("--format")
+        .arg("junit")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::starts_with("<?xml"))
+        .stdout(predicate::str::contains(
+            "<testsuite name=\"DG001\" tests=\"1\" failures=\"1\">",
+        ));
+}
+
+#[test]
+fn check_github_output() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    create_doc_missing_heading(tmp.path(), "TEST-01");
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg("--format")
+        .arg("github")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::starts_with("::error file="))
+        .stdout(predicate::str::contains("title=DG001::"));
+}
+
 #[test]
 fn check_with_rule_filter() {
     let tmp = crate::common::setup_temp_dir();
@@ -176,3 +235,204 @@ desc = "Test node"
         .stdout(predicate::str::contains("DG005"))
         .stdout(predicate::str::contains("Unknown node type prefix"));
 }
+
+#[test]
+fn check_fix_rewrites_link_text() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    crate::common::create_test_doc(tmp.path(), "defs.md", "<a id=\"REQ-01\"></a>\n\n# Login\n");
+    let index = crate::common::create_test_doc(
+        tmp.path(),
+        "index.md",
+        "See [click here](defs.md#REQ-01).\n",
+    );
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg("--fix")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 1 issue(s) in 1 file(s)."))
+        .stdout(predicate::str::contains("No errors"));
+
+    let content = std::fs::read_to_string(index).unwrap();
+    assert_eq!(content, "See [REQ-01 (Login)](defs.md#REQ-01).\n");
+}
+
+#[test]
+fn check_fix_dry_run_prints_diff_without_writing() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    let path = create_doc_missing_heading(tmp.path(), "TEST-LOGIN");
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg("--fix")
+        .arg("--dry-run")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("+# Login"))
+        .stdout(predicate::str::contains("Would fix 1 issue(s)"));
+
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        "<a id=\"TEST-LOGIN\"></a>\n"
+    );
+}
+
+#[test]
+fn check_fix_dry_run_keeps_structured_stdout() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    create_doc_missing_heading(tmp.path(), "TEST-LOGIN");
+
+    let output = assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg("--fix")
+        .arg("--dry-run")
+        .arg("--format")
+        .arg("sarif")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("+# Login"))
+        .get_output()
+        .stdout
+        .clone();
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+}
+
+#[test]
+fn fmt_inserts_missing_heading() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    let path = create_doc_missing_heading(tmp.path(), "TEST-LOGIN");
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("fmt")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 1 issue(s)"));
+
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        "<a id=\"TEST-LOGIN\"></a>\n# Login\n"
+    );
+}
+
+#[test]
+fn check_rule_levels_from_config() {
+    let tmp = crate::common::setup_temp_dir();
+    let config = format!(
+        "{}\n[rules]\nDG001 = \"warning\"\n",
+        crate::common::default_config()
+    );
+    crate::common::create_config(tmp.path(), &config);
+    create_doc_missing_heading(tmp.path(), "TEST-01");
+
+    // A downgraded rule is reported but does not fail the check
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("warning[DG001]"));
+
+    let config = format!(
+        "{}\n[rules]\nDG001 = \"off\"\n",
+        crate::common::default_config()
+    );
+    crate::common::create_config(tmp.path(), &config);
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DG001").not());
+}
+
+#[test]
+fn check_suppression_comments() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    crate::common::create_test_doc(
+        tmp.path(),
+        "test.md",
+        "<!-- docgraph-disable-next-line DG001 -->\n<a id=\"TEST-01\"></a>\n",
+    );
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No errors"));
+
+    // Once the heading exists, the suppression is reported as unused
+    crate::common::create_test_doc(
+        tmp.path(),
+        "test.md",
+        "<!-- docgraph-disable-next-line DG001 -->\n<a id=\"TEST-01\"></a>\n\n# Test\n",
+    );
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("check")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("warning[DG008]"));
+}
+
+#[test]
+fn check_watch_rechecks_changed_files() {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    let doc = crate::common::create_test_doc(
+        tmp.path(),
+        "test.md",
+        "<a id=\"TEST-01\"></a>\n\n# Test\n\n[TEST-02](#TEST-02)\n",
+    );
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_docgraph"))
+        .arg("check")
+        .arg("--watch")
+        .arg(tmp.path())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let (tx, rx) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let _ = tx.send(line);
+        }
+    });
+    let wait_for = |text: &str| {
+        while let Ok(line) = rx.recv_timeout(Duration::from_secs(10)) {
+            if line.contains(text) {
+                return true;
+            }
+        }
+        false
+    };
+
+    assert!(wait_for("Watching"));
+    assert!(wait_for("DG003"));
+
+    std::fs::write(&doc, "<a id=\"TEST-01\"></a>\n\n# Test\n").unwrap();
+    assert!(wait_for("1 file(s) changed"));
+    assert!(wait_for("No errors or warnings found."));
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
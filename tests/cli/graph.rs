@@ -47,3 +47,70 @@ fn graph_with_edges() {
         .stdout(predicate::str::contains("\"id\": \"REQ-01\""))
         .stdout(predicate::str::contains("\"id\": \"ADR-01\""));
 }
+
+fn create_linked_docs(dir: &std::path::Path) {
+    crate::common::create_test_doc(
+        dir,
+        "a.md",
+        "<a id=\"REQ-01\"></a>\n\n# Requirement\n\n<a id=\"REQ-02\"></a>\n\n# Other\n",
+    );
+    crate::common::create_test_doc(
+        dir,
+        "b.md",
+        "<a id=\"ADR-01\"></a>\n\n# Decision\n\n- [REQ-01 (Requirement)](a.md#REQ-01)\n",
+    );
+}
+
+#[test]
+fn graph_dot_format() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    create_linked_docs(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("graph")
+        .arg("--format")
+        .arg("dot")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("digraph docgraph {"))
+        .stdout(predicate::str::contains(
+            "\"ADR-01\" -> \"REQ-01\" [label=\"references\"];",
+        ));
+}
+
+#[test]
+fn graph_mermaid_neighborhood() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    create_linked_docs(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("graph")
+        .arg("--format")
+        .arg("mermaid")
+        .arg("--root")
+        .arg("ADR-01")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("REQ-01<br/>Requirement"))
+        .stdout(predicate::str::contains("REQ-02").not());
+}
+
+#[test]
+fn graph_unknown_root_fails() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    create_linked_docs(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("graph")
+        .arg("--root")
+        .arg("NOPE-01")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("node 'NOPE-01' not found"));
+}
@@ -0,0 +1,78 @@
+use predicates::prelude::*;
+
+fn setup(dir: &std::path::Path) {
+    crate::common::create_config(
+        dir,
+        r#"
+[nodes.UC]
+desc = "Use Case"
+[nodes.FR]
+desc = "Functional Requirement"
+rules = [{ dir = "to", targets = ["UC"], rel = "derived_from" }]
+"#,
+    );
+    crate::common::create_test_doc(
+        dir,
+        "uc.md",
+        "<a id=\"UC-01\"></a>\n\n# Login\n\n<a id=\"UC-02\"></a>\n\n# Logout\n",
+    );
+    crate::common::create_test_doc(
+        dir,
+        "fr.md",
+        "<a id=\"FR-01\"></a>\n\n# Password\n\n- [UC-01 (Login)](uc.md#UC-01)\n",
+    );
+}
+
+#[test]
+fn matrix_markdown() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("matrix")
+        .arg("--rows")
+        .arg("UC")
+        .arg("--cols")
+        .arg("FR")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| UC-01 (Login) | derived_from |"))
+        .stdout(predicate::str::contains("**UC-02 (Logout) — uncovered**"));
+}
+
+#[test]
+fn matrix_csv() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("matrix")
+        .arg("--rows")
+        .arg("UC")
+        .arg("--cols")
+        .arg("FR")
+        .arg("--format")
+        .arg("csv")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout("UC,FR-01,covered\nUC-01,derived_from,yes\nUC-02,,no\n");
+}
+
+#[test]
+fn matrix_unknown_type_fails() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("matrix")
+        .arg("--rows")
+        .arg("UC")
+        .arg("--cols")
+        .arg("MOD")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid node type: MOD"));
+}
@@ -0,0 +1,90 @@
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn new_instantiates_template() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(
+        tmp.path(),
+        r#"
+[nodes.FR]
+desc = "Functional requirement"
+template = "templates/fr.md"
+"#,
+    );
+    fs::create_dir(tmp.path().join("templates")).unwrap();
+    crate::common::create_test_doc(
+        tmp.path(),
+        "templates/fr.md",
+        "<a id=\"FR_*\"></a>\n\n## {Title}\n\n{Description}\n",
+    );
+    let file = tmp.path().join("doc/functional/export.md");
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("new")
+        .arg("FR")
+        .arg("--id")
+        .arg("FR_EXPORT")
+        .arg("--title")
+        .arg("Export")
+        .arg("--file")
+        .arg(&file)
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created 'FR_EXPORT'"));
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        "<a id=\"FR_EXPORT\"></a>\n\n## Export\n\n{Description}\n"
+    );
+
+    // The same ID cannot be created twice
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("new")
+        .arg("FR")
+        .arg("--id")
+        .arg("FR_EXPORT")
+        .arg("--file")
+        .arg(&file)
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ID 'FR_EXPORT' already exists"));
+}
+
+#[test]
+fn new_picks_next_free_id() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    crate::common::create_test_doc(
+        tmp.path(),
+        "req.md",
+        "<a id=\"REQ-001\"></a>\n\n# Login\n\n<a id=\"REQ-002\"></a>\n\n# Logout\n",
+    );
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("new")
+        .arg("REQ")
+        .arg("--title")
+        .arg("Export")
+        .arg("--file")
+        .arg(tmp.path().join("req.md"))
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created 'REQ-003'"));
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("req.md")).unwrap(),
+        "<a id=\"REQ-001\"></a>\n\n# Login\n\n<a id=\"REQ-002\"></a>\n\n# Logout\n\n<a id=\"REQ-003\"></a>\n\n# Export\n"
+    );
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("new")
+        .arg("UC")
+        .arg("--file")
+        .arg(tmp.path().join("req.md"))
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown node type 'UC'"));
+}
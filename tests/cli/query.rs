@@ -90,7 +90,7 @@ desc = "Use Case"
         .success()
         .stdout(predicate::str::contains("\"n.name\": \"Data Export\""))
         .stdout(predicate::str::contains("sub/export.md"))
-        .stdout(predicate::str::contains("\"n.line\": \"1\""));
+        .stdout(predicate::str::contains("\"n.line\": 1"));
 }
 
 #[test]
@@ -372,3 +372,255 @@ This is content.
             .contains("This is content.")
     );
 }
+
+#[test]
+fn test_query_with_parse_cache() {
+    let tmp = crate::common::setup_temp_dir();
+    let config = r#"
+[nodes.UC]
+desc = "Use Case"
+
+[cache]
+enabled = true
+"#;
+    crate::common::create_config(tmp.path(), config);
+    crate::common::create_test_doc(
+        tmp.path(),
+        "uc.md",
+        "<a id=\"UC_001\"></a>\n\n# User Login\n",
+    );
+
+    let query = |expected: &str| {
+        assert_cmd::cargo_bin_cmd!("docgraph")
+            .arg("query")
+            .arg("MATCH (n:UC) RETURN n.name")
+            .arg(tmp.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(expected));
+    };
+    query("User Login");
+    assert!(tmp.path().join(".docgraph/cache/documents.json").exists());
+
+    // A changed file is parsed again instead of served from the cache
+    crate::common::create_test_doc(
+        tmp.path(),
+        "uc.md",
+        "<a id=\"UC_001\"></a>\n\n# User Logout\n",
+    );
+    query("User Logout");
+}
+
+#[test]
+fn test_query_order_skip_limit_distinct() {
+    let tmp = crate::common::setup_temp_dir();
+    let config = r#"
+[nodes.UC]
+desc = "Use Case"
+"#;
+    crate::common::create_config(tmp.path(), config);
+    for id in ["UC_002", "UC_003", "UC_001"] {
+        crate::common::create_test_doc(
+            tmp.path(),
+            &format!("{}.md", id),
+            &format!("<a id=\"{}\"></a>\n\n# Use Case\n", id),
+        );
+    }
+
+    let query = |q: &str| -> Vec<serde_json::Value> {
+        let output = assert_cmd::cargo_bin_cmd!("docgraph")
+            .arg("query")
+            .arg(q)
+            .arg("--format")
+            .arg("json")
+            .arg(tmp.path())
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        serde_json::from_slice(&output).unwrap()
+    };
+
+    let rows = query("MATCH (n:UC) RETURN n.id AS id ORDER BY id DESC SKIP 1 LIMIT 1");
+    assert_eq!(rows, [serde_json::json!({ "id": "UC_002" })]);
+
+    let rows = query("MATCH (n:UC) RETURN DISTINCT n.name");
+    assert_eq!(rows, [serde_json::json!({ "n.name": "Use Case" })]);
+}
+
+#[test]
+fn test_query_aggregation() {
+    let tmp = crate::common::setup_temp_dir();
+    let config = r#"
+[nodes.UC]
+desc = "Use Case"
+[nodes.FR]
+desc = "Functional Requirement"
+"#;
+    crate::common::create_config(tmp.path(), config);
+    crate::common::create_test_doc(
+        tmp.path(),
+        "uc.md",
+        "<a id=\"UC_001\"></a>\n\n# Login\n\n[FR_001](#FR_001)\n[FR_002](#FR_002)\n\n<a id=\"UC_002\"></a>\n\n# Logout\n\n[FR_002](#FR_002)\n",
+    );
+    crate::common::create_test_doc(
+        tmp.path(),
+        "fr.md",
+        "<a id=\"FR_001\"></a>\n\n# Password\n\n<a id=\"FR_002\"></a>\n\n# Session\n",
+    );
+
+    let output = assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg("MATCH (u:UC)-[]->(f:FR) RETURN u.id, count(f) AS frs, collect(f.id) AS ids ORDER BY frs DESC")
+        .arg("--format")
+        .arg("json")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let rows: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        rows,
+        serde_json::json!([
+            { "u.id": "UC_001", "frs": 2, "ids": ["FR_001", "FR_002"] },
+            { "u.id": "UC_002", "frs": 1, "ids": ["FR_002"] },
+        ])
+    );
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg("MATCH (n) RETURN count(*)")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("count(*)"))
+        .stdout(predicate::str::contains("4"));
+
+    // Aggregates cannot filter single matches
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg("MATCH (n) WHERE count(*) > 1 RETURN n.id")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not in WHERE"));
+}
+
+#[test]
+fn test_query_paths() {
+    let tmp = crate::common::setup_temp_dir();
+    let config = r#"
+[nodes.UC]
+desc = "Use Case"
+[nodes.FR]
+desc = "Functional Requirement"
+
+[[nodes.FR.rules]]
+dir = "to"
+targets = ["UC"]
+rel = "realizes"
+"#;
+    crate::common::create_config(tmp.path(), config);
+    crate::common::create_test_doc(
+        tmp.path(),
+        "chain.md",
+        "<a id=\"UC_001\"></a>\n\n# Login\n\n<a id=\"FR_001\"></a>\n\n# Password\n\n[UC_001](#UC_001)\n",
+    );
+
+    let query = "MATCH p = shortestPath((f:FR)-[*]->(u:UC)) RETURN p, length(p)";
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg(query)
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(FR_001)-[realizes]->(UC_001)"));
+
+    let output = assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg(query)
+        .arg("--format")
+        .arg("json")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let rows: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        rows,
+        serde_json::json!([{
+            "p": {
+                "nodes": ["FR_001", "UC_001"],
+                "relationships": [{ "type": "realizes", "from": "FR_001", "to": "UC_001" }],
+            },
+            "length(p)": 1,
+        }])
+    );
+}
+
+#[test]
+fn test_query_where_predicates() {
+    let tmp = crate::common::setup_temp_dir();
+    let config = r#"
+[nodes.FR]
+desc = "Functional Requirement"
+[nodes.MOD]
+desc = "Module"
+"#;
+    crate::common::create_config(tmp.path(), config);
+    crate::common::create_test_doc(
+        tmp.path(),
+        "fr.md",
+        "<a id=\"FR_001\"></a>\n\n# Password\n\n<a id=\"FR_002\"></a>\n\n# Session\n\n<a id=\"FR_003\"></a>\n\n# Logout\n",
+    );
+    crate::common::create_test_doc(
+        tmp.path(),
+        "mod.md",
+        "<a id=\"MOD_001\"></a>\n\n# Auth\n\n[FR_001](#FR_001)\n",
+    );
+
+    let output = assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg("MATCH (n:FR) WHERE NOT (n)<--(:MOD) AND n.id =~ 'FR_00[12]' RETURN n.id")
+        .arg("--format")
+        .arg("json")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let rows: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(rows, serde_json::json!([{ "n.id": "FR_002" }]));
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg("MATCH (n) WHERE n.id IN ['FR_003', 'MOD_001'] AND NOT n.id STARTS WITH 'MOD' RETURN n.id")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("FR_003"))
+        .stdout(predicate::str::contains("MOD_001").not());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg("MATCH (n) WHERE n.id =~ 'FR_(' RETURN n.id")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid regular expression"));
+
+    // A typo in a pattern predicate is an error, not an empty result
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("query")
+        .arg("MATCH (n:FR) WHERE NOT (m)<--(:MOD) RETURN n.id")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Variable 'm' is not defined"));
+}
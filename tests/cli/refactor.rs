@@ -0,0 +1,175 @@
+use predicates::prelude::*;
+use std::fs;
+
+fn setup(dir: &std::path::Path) {
+    crate::common::create_config(dir, crate::common::default_config());
+    crate::common::create_test_doc(
+        dir,
+        "req.md",
+        "<a id=\"REQ-01\"></a>\n\n# REQ-01 Login\n\n<a id=\"REQ-02\"></a>\n\n# Logout\n",
+    );
+    crate::common::create_test_doc(
+        dir,
+        "adr.md",
+        "<a id=\"ADR-01\"></a>\n\n# Sessions\n\n- [REQ-01 (Login)](req.md#REQ-01)\n",
+    );
+}
+
+#[test]
+fn mv_renames_id_everywhere() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("mv")
+        .arg("REQ-01")
+        .arg("TEST-01")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed 'REQ-01' to 'TEST-01': 4 edit(s) in 2 file(s).",
+        ));
+
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("req.md")).unwrap(),
+        "<a id=\"TEST-01\"></a>\n\n# TEST-01 Login\n\n<a id=\"REQ-02\"></a>\n\n# Logout\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("adr.md")).unwrap(),
+        "<a id=\"ADR-01\"></a>\n\n# Sessions\n\n- [TEST-01 (Login)](req.md#TEST-01)\n"
+    );
+}
+
+#[test]
+fn mv_dry_run_prints_diff() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("mv")
+        .arg("REQ-01")
+        .arg("REQ-03")
+        .arg(tmp.path())
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "+- [REQ-03 (Login)](req.md#REQ-03)",
+        ))
+        .stdout(predicate::str::contains(
+            "Would rename 'REQ-01' to 'REQ-03'",
+        ));
+
+    assert!(
+        fs::read_to_string(tmp.path().join("adr.md"))
+            .unwrap()
+            .contains("req.md#REQ-01")
+    );
+}
+
+#[test]
+fn mv_rejects_existing_id_and_unknown_prefix() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("mv")
+        .arg("REQ-01")
+        .arg("REQ-02")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ID 'REQ-02' already exists"));
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("mv")
+        .arg("REQ-01")
+        .arg("FOO-01")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown node type prefix 'FOO' in ID 'FOO-01' (DG005)",
+        ));
+}
+
+#[test]
+fn move_relocates_node_and_links() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("move")
+        .arg("REQ-01")
+        .arg("--to")
+        .arg(tmp.path().join("adr.md"))
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Moved 'REQ-01' to"));
+
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("req.md")).unwrap(),
+        "<a id=\"REQ-02\"></a>\n\n# Logout\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("adr.md")).unwrap(),
+        "<a id=\"ADR-01\"></a>\n\n# Sessions\n\n- [REQ-01 (Login)](#REQ-01)\n\n<a id=\"REQ-01\"></a>\n\n# REQ-01 Login\n"
+    );
+}
+
+#[test]
+fn merge_retargets_links() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("merge")
+        .arg("REQ-02")
+        .arg("REQ-01")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Merged 'REQ-01' into 'REQ-02'"));
+
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("req.md")).unwrap(),
+        "<a id=\"REQ-02\"></a>\n\n# Logout\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("adr.md")).unwrap(),
+        "<a id=\"ADR-01\"></a>\n\n# Sessions\n\n- [REQ-02 (Logout)](req.md#REQ-02)\n"
+    );
+}
+
+#[test]
+fn split_moves_trailing_nodes_to_new_file() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("split")
+        .arg("REQ-02")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Split at 'REQ-02'"));
+
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("req.md")).unwrap(),
+        "<a id=\"REQ-01\"></a>\n\n# REQ-01 Login\n"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("REQ-02.md")).unwrap(),
+        "<a id=\"REQ-02\"></a>\n\n# Logout\n"
+    );
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("split")
+        .arg("REQ-01")
+        .arg(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("first node"));
+}
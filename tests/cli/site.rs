@@ -0,0 +1,41 @@
+use predicates::prelude::*;
+
+#[test]
+fn site_help_works() {
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("site")
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Generate a static HTML site"));
+}
+
+#[test]
+fn site_generates_pages() {
+    let tmp = crate::common::setup_temp_dir();
+    crate::common::create_config(tmp.path(), crate::common::default_config());
+    crate::common::create_test_doc(tmp.path(), "a.md", "<a id=\"REQ-01\"></a>\n\n# Login\n");
+    crate::common::create_test_doc(
+        tmp.path(),
+        "b.md",
+        "<a id=\"ADR-01\"></a>\n\n# Decision\n\n- [REQ-01 (Login)](a.md#REQ-01)\n",
+    );
+    let out = tmp.path().join("site");
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("site")
+        .arg(&out)
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Generated"));
+
+    let a = std::fs::read_to_string(out.join("a.html")).unwrap();
+    assert!(a.contains("Referenced by"));
+    assert!(a.contains("href=\"b.html#ADR-01\""));
+    let b = std::fs::read_to_string(out.join("b.html")).unwrap();
+    assert!(b.contains("href=\"a.html#REQ-01\""));
+    assert!(out.join("index.html").exists());
+    assert!(out.join("_docgraph/graph.html").exists());
+    assert!(out.join("_docgraph/types/REQ.html").exists());
+}
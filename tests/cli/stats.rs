@@ -0,0 +1,63 @@
+use predicates::prelude::*;
+
+fn setup(dir: &std::path::Path) {
+    crate::common::create_config(
+        dir,
+        r#"
+[nodes.FR]
+desc = "Functional Requirement"
+rules = [{ dir = "from", targets = ["MOD"], min = 1, rel = "realized_by" }]
+[nodes.MOD]
+desc = "Module"
+"#,
+    );
+    crate::common::create_test_doc(
+        dir,
+        "fr.md",
+        "<a id=\"FR-01\"></a>\n\n# Login\n\n<a id=\"FR-02\"></a>\n\n# Logout\n",
+    );
+    crate::common::create_test_doc(
+        dir,
+        "mod.md",
+        "<a id=\"MOD-01\"></a>\n\n# Auth\n\n- [FR-01 (Login)](fr.md#FR-01)\n",
+    );
+}
+
+#[test]
+fn stats_text() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("stats")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nodes: 3"))
+        .stdout(predicate::str::contains("Orphan nodes: 1\n  FR-02"))
+        .stdout(predicate::str::contains(
+            "FR <- MOD (realized_by): 1/2 linked (50.0%), min 1 met by 1/2 (50.0%)",
+        ));
+}
+
+#[test]
+fn stats_json() {
+    let tmp = crate::common::setup_temp_dir();
+    setup(tmp.path());
+
+    let output = assert_cmd::cargo_bin_cmd!("docgraph")
+        .arg("stats")
+        .arg("--format")
+        .arg("json")
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let stats: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(stats["total_nodes"], 3);
+    assert_eq!(stats["nodes_per_type"]["FR"], 2);
+    assert_eq!(stats["rules"][0]["satisfaction_pct"], 50.0);
+}
@@ -8,11 +8,21 @@ mod diagnostics;
 mod completion;
 #[path = "lsp/hover.rs"]
 mod hover;
+#[path = "lsp/inlay_hint.rs"]
+mod inlay_hint;
 #[path = "lsp/rename.rs"]
 mod rename;
+#[path = "lsp/semantic_tokens.rs"]
+mod semantic_tokens;
 
 #[path = "lsp/call_hierarchy.rs"]
 mod call_hierarchy;
+#[path = "lsp/code_action.rs"]
+mod code_action;
+#[path = "lsp/code_lens.rs"]
+mod code_lens;
+#[path = "lsp/command.rs"]
+mod command;
 
 #[path = "lsp/navigation.rs"]
 mod navigation;
@@ -0,0 +1,173 @@
+use crate::support::lsp_client::LspClient;
+use crate::support::server_bin;
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn e2e_code_actions() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+
+    fs::write(
+        root_path.join("docgraph.toml"),
+        r#"[nodes.REQ]
+desc = "Requirement"
+
+[nodes.TEST]
+desc = "Test"
+rules = [{ dir = "to", targets = ["REQ"], min = 1 }]
+"#,
+    )?;
+
+    let file_req = root_path.join("req.md");
+    let uri_req = format!("file://{}", file_req.to_str().unwrap());
+    fs::write(&file_req, "<a id=\"REQ-001\"></a>\n\n# Login\n")?;
+
+    let file_test = root_path.join("test.md");
+    let uri_test = format!("file://{}", file_test.to_str().unwrap());
+    fs::write(
+        &file_test,
+        r#"<a id="TEST-001"></a>
+
+# Login Test
+
+- [wrong](req.md#REQ-001)
+- [x](#REQ-404)
+
+<a id="TEST-002"></a>
+
+# Orphan Test
+
+<a id="TEST-003"></a>
+"#,
+    )?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    let init_res: Value = c
+        .send_request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+                "capabilities": {}
+            }),
+        )
+        .await?;
+    assert_eq!(
+        init_res["result"]["capabilities"]["codeActionProvider"],
+        true
+    );
+    c.send_notification("initialized", json!({})).await?;
+
+    let res: Value = c
+        .send_request(
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": uri_test },
+                "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 12, "character": 0 } },
+                "context": { "diagnostics": [] }
+            }),
+        )
+        .await?;
+    let actions = res["result"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("No code actions returned"))?;
+    let action = |title: &str| {
+        actions
+            .iter()
+            .find(|a| a["title"] == title)
+            .unwrap_or_else(|| panic!("missing action '{}' in {:?}", title, actions))
+    };
+
+    // DG004: replace the link text
+    let fix = action("Replace link text with 'REQ-001 (Login)'");
+    assert_eq!(fix["kind"], "quickfix");
+    assert_eq!(
+        fix["edit"]["changes"][&uri_test][0]["newText"],
+        "REQ-001 (Login)"
+    );
+
+    // DG003: the stub goes next to the other REQ nodes
+    let stub = action("Create node 'REQ-404' in req.md");
+    let edit = &stub["edit"]["changes"][&uri_req][0];
+    assert_eq!(edit["range"]["start"]["line"], 3);
+    assert_eq!(edit["newText"], "\n<a id=\"REQ-404\"></a>\n\n# REQ 404\n");
+
+    // DG006: link the orphan test to a requirement, at the end of its block
+    let link = action("Add link to REQ-001 (Login)");
+    let edit = &link["edit"]["changes"][&uri_test][0];
+    assert_eq!(edit["range"]["start"]["line"], 10);
+    assert_eq!(edit["newText"], "\n- [REQ-001 (Login)](req.md#REQ-001)\n");
+
+    // DG001: insert a heading
+    action("Insert heading 'TEST 003'");
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_code_action_multibyte_columns() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+
+    fs::write(
+        root_path.join("docgraph.toml"),
+        "[nodes.REQ]\ndesc = \"Requirement\"\n",
+    )?;
+    let file = root_path.join("req.md");
+    let uri = format!("file://{}", file.to_str().unwrap());
+    fs::write(
+        &file,
+        "<a id=\"REQ-001\"></a>\n\n# ログイン\n\n<a id=\"REQ-002\"></a>\n\n# 関連\n\n- 要件: [wrong](#REQ-001)\n",
+    )?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    c.send_request(
+        "initialize",
+        json!({
+            "processId": null,
+            "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+            "capabilities": {}
+        }),
+    )
+    .await?;
+    c.send_notification("initialized", json!({})).await?;
+
+    let res: Value = c
+        .send_request(
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": uri },
+                "range": { "start": { "line": 8, "character": 0 }, "end": { "line": 8, "character": 0 } },
+                "context": { "diagnostics": [] }
+            }),
+        )
+        .await?;
+    let fix = res["result"]
+        .as_array()
+        .and_then(|actions| {
+            actions
+                .iter()
+                .find(|a| a["title"] == "Replace link text with 'REQ-001 (ログイン)'")
+        })
+        .ok_or_else(|| anyhow::anyhow!("missing DG004 quick fix in {}", res))?;
+
+    // Columns count UTF-16 code units, not bytes
+    let edit = &fix["edit"]["changes"][&uri][0];
+    assert_eq!(
+        edit["range"],
+        json!({
+            "start": { "line": 8, "character": 7 },
+            "end": { "line": 8, "character": 12 }
+        })
+    );
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use crate::support::lsp_client::LspClient;
+use crate::support::server_bin;
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn e2e_code_lens() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+    fs::write(
+        root_path.join("docgraph.toml"),
+        r#"
+[nodes.UC]
+desc = "Use Case"
+
+[nodes.MOD]
+desc = "Module"
+
+[nodes.FR]
+desc = "Functional"
+rules = [
+  { dir = "to", targets = ["UC"] },
+  { dir = "to", targets = ["MOD"], min = 1 },
+]
+"#,
+    )?;
+
+    let file = root_path.join("a.md");
+    let uri = format!("file://{}", file.to_str().unwrap());
+    fs::write(
+        &file,
+        "<a id=\"UC_LOGIN\"></a>\n# Login\n\n<a id=\"FR_LOGIN\"></a>\n# Login Form\n[UC_LOGIN (Login)](#UC_LOGIN)\n",
+    )?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    let init_res: Value = c
+        .send_request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+                "capabilities": {}
+            }),
+        )
+        .await?;
+    assert!(init_res["result"]["capabilities"]["codeLensProvider"].is_object());
+    c.send_notification("initialized", json!({})).await?;
+
+    let res: Value = c
+        .send_request(
+            "textDocument/codeLens",
+            json!({ "textDocument": { "uri": uri } }),
+        )
+        .await?;
+    let lenses: Vec<(u64, u64, &str, &str)> = res["result"]
+        .as_array()
+        .expect("lenses")
+        .iter()
+        .map(|l| {
+            (
+                l["range"]["start"]["line"].as_u64().unwrap(),
+                l["range"]["start"]["character"].as_u64().unwrap(),
+                l["command"]["title"].as_str().unwrap(),
+                l["command"]["command"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        lenses,
+        vec![
+            (0, 7, "1 reference", "docgraph.showReferences"),
+            (0, 7, "0 outgoing", "docgraph.showOutgoingCalls"),
+            (3, 7, "0 references", "docgraph.showReferences"),
+            (3, 7, "1 outgoing", "docgraph.showOutgoingCalls"),
+            (3, 7, "DG006: missing MOD", "docgraph.showProblems"),
+        ]
+    );
+    // The commands carry the position of the node for the references request
+    assert_eq!(
+        res["result"][0]["command"]["arguments"],
+        json!([uri, { "line": 0, "character": 7 }])
+    );
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_code_lens_multibyte_position() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+    fs::write(
+        root_path.join("docgraph.toml"),
+        "[nodes.UC]\ndesc = \"Use Case\"\n",
+    )?;
+    let file = root_path.join("a.md");
+    let uri = format!("file://{}", file.to_str().unwrap());
+    fs::write(&file, "要件 <a id=\"UC_LOGIN\"></a>\n# ログイン\n")?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    c.send_request(
+        "initialize",
+        json!({
+            "processId": null,
+            "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+            "capabilities": {}
+        }),
+    )
+    .await?;
+    c.send_notification("initialized", json!({})).await?;
+
+    let res: Value = c
+        .send_request(
+            "textDocument/codeLens",
+            json!({ "textDocument": { "uri": uri } }),
+        )
+        .await?;
+    // The ID starts after 10 UTF-16 code units (14 bytes)
+    assert_eq!(
+        res["result"][0]["range"]["start"],
+        json!({ "line": 0, "character": 10 })
+    );
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::support::lsp_client::LspClient;
+use crate::support::server_bin;
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::tempdir;
+use tokio::time::Duration;
+
+#[tokio::test]
+async fn e2e_execute_refactoring_commands() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+
+    fs::write(
+        root_path.join("docgraph.toml"),
+        r#"[nodes.REQ]
+desc = "Requirement"
+"#,
+    )?;
+    let file_a = root_path.join("a.md");
+    let uri_a = format!("file://{}", file_a.to_str().unwrap());
+    fs::write(
+        &file_a,
+        "<a id=\"REQ-001\"></a>\n# Login\n\n<a id=\"REQ-002\"></a>\n# Logout\n",
+    )?;
+    let file_b = root_path.join("b.md");
+    let uri_b = format!("file://{}", file_b.to_str().unwrap());
+    fs::write(&file_b, "- [REQ-002 (Logout)](a.md#REQ-002)\n")?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    let init: Value = c
+        .send_request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+                "capabilities": {}
+            }),
+        )
+        .await?;
+    let commands = &init["result"]["capabilities"]["executeCommandProvider"]["commands"];
+    assert_eq!(
+        commands,
+        &json!([
+            "docgraph.move",
+            "docgraph.merge",
+            "docgraph.split",
+            "docgraph.new"
+        ])
+    );
+    c.send_notification("initialized", json!({})).await?;
+
+    // Moving into an existing file edits both files in place
+    let res = c
+        .send_request(
+            "workspace/executeCommand",
+            json!({ "command": "docgraph.move", "arguments": ["REQ-002", "b.md"] }),
+        )
+        .await?;
+    assert!(res.get("error").is_none(), "unexpected error: {}", res);
+    let apply = c
+        .wait_notification("workspace/applyEdit", Duration::from_secs(5))
+        .await?;
+    assert_eq!(apply["params"]["label"], "docgraph.move");
+    let move_id = apply["id"].clone();
+    let changes = apply["params"]["edit"]["changes"]
+        .as_object()
+        .expect("move should send changes");
+    assert_eq!(
+        changes[&uri_a][0]["newText"],
+        "<a id=\"REQ-001\"></a>\n# Login\n"
+    );
+    assert_eq!(
+        changes[&uri_b][0]["newText"],
+        "- [REQ-002 (Logout)](#REQ-002)\n\n<a id=\"REQ-002\"></a>\n# Logout\n"
+    );
+
+    // Splitting into a new file creates it first
+    c.send_request(
+        "workspace/executeCommand",
+        json!({ "command": "docgraph.split", "arguments": ["REQ-002", "logout.md"] }),
+    )
+    .await?;
+    let apply = c
+        .wait_notification("workspace/applyEdit", Duration::from_secs(5))
+        .await?;
+    assert_ne!(apply["id"], move_id, "every edit request needs its own ID");
+    let operations = apply["params"]["edit"]["documentChanges"]
+        .as_array()
+        .expect("split should send document changes");
+    let uri_new = format!("file://{}", root_path.join("logout.md").to_str().unwrap());
+    let create = operations
+        .iter()
+        .position(|op| op["kind"] == "create")
+        .expect("split should create the new file");
+    assert_eq!(operations[create]["uri"], uri_new);
+    assert_eq!(operations[create + 1]["textDocument"]["uri"], uri_new);
+
+    // New nodes get the next free ID of their type
+    c.send_request(
+        "workspace/executeCommand",
+        json!({ "command": "docgraph.new", "arguments": ["REQ", "a.md", null, "Export"] }),
+    )
+    .await?;
+    let apply = c
+        .wait_notification("workspace/applyEdit", Duration::from_secs(5))
+        .await?;
+    assert_eq!(
+        apply["params"]["edit"]["changes"][&uri_a][0]["newText"],
+        "\n<a id=\"REQ-003\"></a>\n\n# Export\n"
+    );
+
+    // A refused edit is logged
+    c.write(json!({
+        "jsonrpc": "2.0",
+        "id": apply["id"],
+        "result": { "applied": false, "failureReason": "file is read-only" }
+    }))?;
+    loop {
+        let log = c
+            .wait_notification("window/logMessage", Duration::from_secs(5))
+            .await?;
+        let message = log["params"]["message"].as_str().unwrap_or_default();
+        if message.contains("did not apply") {
+            assert!(message.contains("docgraph.new"));
+            assert!(message.contains("file is read-only"));
+            break;
+        }
+    }
+
+    // Invalid commands are reported as request errors
+    let res = c
+        .send_request(
+            "workspace/executeCommand",
+            json!({ "command": "docgraph.merge", "arguments": ["REQ-001", "REQ-404"] }),
+        )
+        .await?;
+    assert!(res.get("error").is_some());
+
+    Ok(())
+}
@@ -16,6 +16,10 @@ async fn e2e_completion() -> anyhow::Result<()> {
         r#"
 [nodes.REQ]
 desc = "Requirement"
+rules = [{ dir = "to", targets = ["SPEC"] }]
+
+[nodes.SPEC]
+desc = "Specification"
 "#,
     )?;
 
@@ -27,8 +31,13 @@ desc = "Requirement"
 <a id="REQ-001"></a>
 # Target
 
-[REQ
-    "#,
+[SPEC
+[x](#
+"#,
+    )?;
+    fs::write(
+        root_path.join("spec.md"),
+        "<a id=\"SPEC-001\"></a>\n# Export\n",
     )?;
 
     let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
@@ -61,13 +70,13 @@ desc = "Requirement"
         .wait_notification("textDocument/publishDiagnostics", Duration::from_secs(5))
         .await?;
 
-    // Trigger completion after `[REQ` (line 4, col 4)
+    // Trigger completion after `[SPEC` (line 4, col 5)
     let comp_res: Value = c
         .send_request(
             "textDocument/completion",
             json!({
                 "textDocument": {"uri": file_uri},
-                "position": {"line": 4, "character": 4}
+                "position": {"line": 4, "character": 5}
             }),
         )
         .await?;
@@ -79,10 +88,38 @@ desc = "Requirement"
         .and_then(|r| r.as_array())
         .expect("Completion items not found");
 
-    let found = items
+    // Only targets allowed by the REQ rules, never the node itself
+    let labels: Vec<&str> = items
         .iter()
-        .any(|item| item.get("label").and_then(|l| l.as_str()).unwrap_or("") == "REQ-001");
-    assert!(found, "Should find REQ-001 in completion candidates");
+        .filter_map(|item| item.get("label").and_then(|l| l.as_str()))
+        .collect();
+    assert_eq!(labels, vec!["SPEC-001"], "Should offer only SPEC-001");
+    assert_eq!(
+        items[0]["textEdit"],
+        json!({
+            "range": {"start": {"line": 4, "character": 0}, "end": {"line": 4, "character": 5}},
+            "newText": "[SPEC-001 (Export)](spec.md#SPEC-001)"
+        })
+    );
+
+    // Inside a link destination the whole link is replaced as well
+    let comp_res: Value = c
+        .send_request(
+            "textDocument/completion",
+            json!({
+                "textDocument": {"uri": file_uri},
+                "position": {"line": 5, "character": 5}
+            }),
+        )
+        .await?;
+    assert_eq!(
+        comp_res["result"][0]["textEdit"]["range"],
+        json!({"start": {"line": 5, "character": 0}, "end": {"line": 5, "character": 5}})
+    );
+    assert_eq!(
+        comp_res["result"][0]["filterText"],
+        json!("[x](#SPEC-001 Export")
+    );
 
     c.send_request("shutdown", json!({})).await?;
     c.send_notification("exit", json!({})).await?;
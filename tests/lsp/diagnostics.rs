@@ -99,3 +99,237 @@ This is a test.
 
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_diagnostics_after_change() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = std::fs::canonicalize(dir.path())?;
+    fs::write(
+        root_path.join("docgraph.toml"),
+        "[nodes.REQ]\ndesc = \"Requirement\"\n",
+    )?;
+
+    let file_path = root_path.join("test.md");
+    let file_uri = format!("file://{}", file_path.to_str().unwrap());
+    let text = "<a id=\"REQ-001\"></a>\n# Known Block\n";
+    fs::write(&file_path, text)?;
+
+    let mut c: LspClient = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    c.send_request(
+        "initialize",
+        json!({
+            "processId": null,
+            "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+            "capabilities": {}
+        }),
+    )
+    .await?;
+    c.send_notification("initialized", json!({})).await?;
+    c.send_notification(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": file_uri, "languageId": "markdown", "version": 1, "text": text }
+        }),
+    )
+    .await?;
+    c.wait_notification("textDocument/publishDiagnostics", Duration::from_secs(5))
+        .await?;
+
+    // Several quick edits are linted once, against the last version of the document
+    for (version, text) in [
+        (2, "<a id=\"UNK-001\"></a>\n# Unknown Block\n"),
+        (
+            3,
+            "<a id=\"REQ-001\"></a>\n# Known Block\n[Broken](#REQ-999)\n",
+        ),
+    ] {
+        c.send_notification(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": file_uri, "version": version },
+                "contentChanges": [{ "text": text }]
+            }),
+        )
+        .await?;
+    }
+
+    // Skip the diagnostics published at startup and on open
+    let messages = loop {
+        let diag_msg: Value = c
+            .wait_notification("textDocument/publishDiagnostics", Duration::from_secs(5))
+            .await?;
+        let messages: Vec<String> = diag_msg["params"]["diagnostics"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("No diagnostics found"))?
+            .iter()
+            .filter_map(|d| d["message"].as_str().map(str::to_string))
+            .collect();
+        if !messages.is_empty() {
+            break messages;
+        }
+    };
+
+    assert!(messages.iter().any(|m| m.contains("REQ-999")));
+    assert!(
+        !messages
+            .iter()
+            .any(|m| m.contains("Unknown node type prefix"))
+    );
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_incremental_changes() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = std::fs::canonicalize(dir.path())?;
+    fs::write(
+        root_path.join("docgraph.toml"),
+        "[nodes.REQ]\ndesc = \"Requirement\"\n",
+    )?;
+
+    let file_path = root_path.join("test.md");
+    let file_uri = format!("file://{}", file_path.to_str().unwrap());
+    let text = "<a id=\"REQ-001\"></a>\n# Known Block\n";
+    fs::write(&file_path, text)?;
+
+    let mut c: LspClient = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    let init_res: Value = c
+        .send_request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+                "capabilities": {}
+            }),
+        )
+        .await?;
+    // TextDocumentSyncKind::Incremental
+    assert_eq!(init_res["result"]["capabilities"]["textDocumentSync"], 2);
+    c.send_notification("initialized", json!({})).await?;
+    c.send_notification(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": file_uri, "languageId": "markdown", "version": 1, "text": text }
+        }),
+    )
+    .await?;
+
+    // Both changes of one notification are applied in order; the emoji counts as
+    // two UTF-16 code units
+    c.send_notification(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": file_uri, "version": 2 },
+            "contentChanges": [
+                {
+                    "range": { "start": { "line": 2, "character": 0 }, "end": { "line": 2, "character": 0 } },
+                    "text": "😀 [x](#REQ-999)\n"
+                },
+                {
+                    "range": { "start": { "line": 2, "character": 12 }, "end": { "line": 2, "character": 15 } },
+                    "text": "998"
+                }
+            ]
+        }),
+    )
+    .await?;
+
+    let diags = loop {
+        let diag_msg: Value = c
+            .wait_notification("textDocument/publishDiagnostics", Duration::from_secs(5))
+            .await?;
+        let diags = diag_msg["params"]["diagnostics"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        if !diags.is_empty() {
+            break diags;
+        }
+    };
+
+    let broken: Vec<&Value> = diags.iter().filter(|d| d["code"] == "DG003").collect();
+    assert_eq!(broken.len(), 1, "{:?}", diags);
+    assert_eq!(broken[0]["message"], "Link to unknown ID 'REQ-998'");
+    assert_eq!(broken[0]["range"]["start"]["line"], 2);
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn e2e_template_change_rechecks_nodes() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = std::fs::canonicalize(dir.path())?;
+    fs::write(
+        root_path.join("docgraph.toml"),
+        "[nodes.REQ]\ndesc = \"Requirement\"\ntemplate = \"req_template.md\"\n",
+    )?;
+    let template_path = root_path.join("req_template.md");
+    let template_uri = format!("file://{}", template_path.to_str().unwrap());
+    fs::write(&template_path, "<a id=\"REQ-*\"></a>\n\n# *\n\nText.\n")?;
+    fs::write(
+        root_path.join("test.md"),
+        "<a id=\"REQ-001\"></a>\n\n# Known\n\nText.\n",
+    )?;
+
+    let mut c: LspClient = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    c.send_request(
+        "initialize",
+        json!({
+            "processId": null,
+            "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+            "capabilities": {}
+        }),
+    )
+    .await?;
+    c.send_notification("initialized", json!({})).await?;
+    // The node matches the template at startup
+    loop {
+        let diag_msg: Value = c
+            .wait_notification("textDocument/publishDiagnostics", Duration::from_secs(5))
+            .await?;
+        if diag_msg["params"]["uri"]
+            .as_str()
+            .is_some_and(|uri| uri.ends_with("/test.md"))
+        {
+            assert_eq!(diag_msg["params"]["diagnostics"], json!([]));
+            break;
+        }
+    }
+
+    // Saving the template re-checks the nodes of its type
+    fs::write(
+        &template_path,
+        "<a id=\"REQ-*\"></a>\n\n# *\n\nText.\n\n## Details\n\nMore.\n",
+    )?;
+    c.send_notification(
+        "workspace/didChangeWatchedFiles",
+        json!({ "changes": [{ "uri": template_uri, "type": 2 }] }),
+    )
+    .await?;
+
+    loop {
+        let diag_msg: Value = c
+            .wait_notification("textDocument/publishDiagnostics", Duration::from_secs(5))
+            .await?;
+        let uri = diag_msg["params"]["uri"].as_str().unwrap_or_default();
+        let diags = diag_msg["params"]["diagnostics"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        if uri.ends_with("/test.md") && diags.iter().any(|d| d["code"] == "DG007") {
+            break;
+        }
+    }
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
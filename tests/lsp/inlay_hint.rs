@@ -0,0 +1,94 @@
+use crate::support::lsp_client::LspClient;
+use crate::support::server_bin;
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn e2e_inlay_hints() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+    fs::write(
+        root_path.join("docgraph.toml"),
+        r#"
+[nodes.UC]
+desc = "Use Case"
+
+[nodes.FR]
+desc = "Functional"
+rules = [{ dir = "to", targets = ["UC"], rel = "derived_from" }]
+"#,
+    )?;
+
+    let file = root_path.join("a.md");
+    let uri = format!("file://{}", file.to_str().unwrap());
+    fs::write(
+        &file,
+        "<a id=\"UC_LOGIN\"></a>\n# Login\n\n<a id=\"FR_LOGIN\"></a>\n# Login Form\n[UC_LOGIN](#UC_LOGIN)\n",
+    )?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    let init_res: Value = c
+        .send_request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+                "capabilities": {}
+            }),
+        )
+        .await?;
+    assert_eq!(
+        init_res["result"]["capabilities"]["inlayHintProvider"],
+        json!(true)
+    );
+    c.send_notification("initialized", json!({})).await?;
+
+    let res: Value = c
+        .send_request(
+            "textDocument/inlayHint",
+            json!({
+                "textDocument": { "uri": uri },
+                "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 6, "character": 0 } }
+            }),
+        )
+        .await?;
+    let hints: Vec<(u64, u64, &str)> = res["result"]
+        .as_array()
+        .expect("hints")
+        .iter()
+        .map(|h| {
+            (
+                h["position"]["line"].as_u64().unwrap(),
+                h["position"]["character"].as_u64().unwrap(),
+                h["label"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        hints,
+        vec![
+            (0, 21, "1 reference"),
+            (3, 21, "0 references"),
+            (5, 0, "derived_from:"),
+            (5, 21, "Login"),
+        ]
+    );
+
+    // Only hints within the requested range
+    let res: Value = c
+        .send_request(
+            "textDocument/inlayHint",
+            json!({
+                "textDocument": { "uri": uri },
+                "range": { "start": { "line": 5, "character": 0 }, "end": { "line": 5, "character": 21 } }
+            }),
+        )
+        .await?;
+    assert_eq!(res["result"].as_array().map(|a| a.len()), Some(2));
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
@@ -168,3 +168,113 @@ Reference: [REQ-001](a.md#REQ-001)
     c.send_notification("exit", json!({})).await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn e2e_prepare_rename_and_rejection() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+
+    fs::write(
+        root_path.join("docgraph.toml"),
+        "[nodes.REQ]\ndesc = \"Requirement\"\n",
+    )?;
+    let file_a = root_path.join("a.md");
+    let uri_a = format!("file://{}", file_a.to_str().unwrap());
+    fs::write(
+        &file_a,
+        "<a id=\"REQ-001\"></a>\n# REQ-001 Login\n\n<a id=\"REQ-002\"></a>\n# Logout\n[REQ-001 (Login)](#REQ-001)\n",
+    )?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    let init_res: Value = c
+        .send_request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+                "capabilities": { "textDocument": { "rename": { "prepareSupport": true } } }
+            }),
+        )
+        .await?;
+    assert_eq!(
+        init_res["result"]["capabilities"]["renameProvider"]["prepareProvider"],
+        json!(true)
+    );
+    c.send_notification("initialized", json!({})).await?;
+
+    // On the link fragment, the fragment is the range to rename
+    let res: Value = c
+        .send_request(
+            "textDocument/prepareRename",
+            json!({
+                "textDocument": {"uri": uri_a},
+                "position": {"line": 5, "character": 21}
+            }),
+        )
+        .await?;
+    assert_eq!(
+        res["result"],
+        json!({
+            "range": {"start": {"line": 5, "character": 19}, "end": {"line": 5, "character": 26}},
+            "placeholder": "REQ-001"
+        })
+    );
+
+    // Plain text cannot be renamed
+    let res: Value = c
+        .send_request(
+            "textDocument/prepareRename",
+            json!({
+                "textDocument": {"uri": uri_a},
+                "position": {"line": 4, "character": 3}
+            }),
+        )
+        .await?;
+    assert!(res["result"].is_null());
+
+    // The heading and the link text are renamed with the anchor
+    let res: Value = c
+        .send_request(
+            "textDocument/rename",
+            json!({
+                "textDocument": {"uri": uri_a},
+                "position": {"line": 0, "character": 8},
+                "newName": "REQ-100"
+            }),
+        )
+        .await?;
+    let edits = res["result"]["changes"][&uri_a].as_array().unwrap();
+    let starts: Vec<(u64, u64)> = edits
+        .iter()
+        .map(|e| {
+            (
+                e["range"]["start"]["line"].as_u64().unwrap(),
+                e["range"]["start"]["character"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(starts, vec![(0, 7), (1, 2), (5, 1), (5, 19)]);
+
+    // Colliding IDs are rejected with an error response
+    let res: Value = c
+        .send_request(
+            "textDocument/rename",
+            json!({
+                "textDocument": {"uri": uri_a},
+                "position": {"line": 0, "character": 8},
+                "newName": "REQ-002"
+            }),
+        )
+        .await?;
+    assert!(res["result"].is_null());
+    assert!(
+        res["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("ID 'REQ-002' already exists")
+    );
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::support::lsp_client::LspClient;
+use crate::support::server_bin;
+use serde_json::{Value, json};
+use std::fs;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn e2e_semantic_tokens() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root_path = dir.path().canonicalize()?;
+    fs::write(
+        root_path.join("docgraph.toml"),
+        "[nodes.FR]\ndesc = \"Functional\"\n",
+    )?;
+
+    let file = root_path.join("a.md");
+    let uri = format!("file://{}", file.to_str().unwrap());
+    fs::write(
+        &file,
+        "<a id=\"FR_LOGIN\"></a>\n# Login\n[FR_LOGIN (Login)](#FR_LOGIN)\n[x](#FR_MISSING)\n",
+    )?;
+
+    let mut c = LspClient::spawn(&server_bin(), &["lsp"]).await?;
+    let init_res: Value = c
+        .send_request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": format!("file://{}", root_path.to_str().unwrap()),
+                "capabilities": {}
+            }),
+        )
+        .await?;
+    let provider = &init_res["result"]["capabilities"]["semanticTokensProvider"];
+    assert_eq!(provider["legend"]["tokenTypes"], json!(["class", "type"]));
+    assert_eq!(
+        provider["legend"]["tokenModifiers"],
+        json!(["definition", "resolved", "broken"])
+    );
+    c.send_notification("initialized", json!({})).await?;
+
+    let res: Value = c
+        .send_request(
+            "textDocument/semanticTokens/full",
+            json!({ "textDocument": { "uri": uri } }),
+        )
+        .await?;
+    // [deltaLine, deltaStart, length, type, modifiers] per token:
+    // node type prefix and rest of the ID for the anchor, the resolved link and the broken link
+    assert_eq!(
+        res["result"]["data"],
+        json!([
+            0, 7, 2, 1, 1, 0, 2, 6, 0, 1, //
+            2, 20, 2, 1, 2, 0, 2, 6, 0, 2, //
+            1, 5, 2, 1, 4, 0, 2, 8, 0, 4
+        ])
+    );
+
+    let res: Value = c
+        .send_request(
+            "textDocument/semanticTokens/range",
+            json!({
+                "textDocument": { "uri": uri },
+                "range": { "start": { "line": 3, "character": 0 }, "end": { "line": 3, "character": 16 } }
+            }),
+        )
+        .await?;
+    assert_eq!(res["result"]["data"], json!([3, 5, 2, 1, 4, 0, 2, 8, 0, 4]));
+
+    c.send_request("shutdown", json!({})).await?;
+    c.send_notification("exit", json!({})).await?;
+
+    Ok(())
+}
@@ -97,7 +97,10 @@ impl LspClient {
                 Err(_) => anyhow::bail!("Timeout waiting for response to {}", method),
             };
 
-            if let Some(res_id) = msg.get("id").and_then(|i| i.as_i64()) {
+            // Requests from the server carry a method and their own ID
+            if msg.get("method").is_some() {
+                self.pending_notifications.push(msg);
+            } else if let Some(res_id) = msg.get("id").and_then(|i| i.as_i64()) {
                 if res_id == id {
                     return Ok(msg);
                 } else {
@@ -175,7 +178,7 @@ impl LspClient {
             let tick = Duration::from_millis(100);
             match timeout(tick, self.receiver.recv()).await {
                 Ok(Some(msg)) => {
-                    if msg.get("id").is_some() {
+                    if msg.get("method").is_none() {
                         eprintln!(
                             "Warning: received unexpected response while waiting for notification"
                         );
@@ -1,4 +1,4 @@
-import { commands, type ExtensionContext, window, workspace } from 'vscode';
+import { commands, type ExtensionContext, Position, Range, Uri, window, workspace } from 'vscode';
 import { LanguageClient, type LanguageClientOptions, type ServerOptions } from 'vscode-languageclient/node';
 
 let client: LanguageClient;
@@ -13,7 +13,35 @@ export function activate(context: ExtensionContext) {
     }
   });
 
-  context.subscriptions.push(restartCommand);
+  // Targets of the code lenses shown above each node
+  const showReferences = commands.registerCommand(
+    'docgraph.showReferences',
+    (uri: string, position: { line: number; character: number }) =>
+      commands.executeCommand(
+        'editor.action.findReferences',
+        Uri.parse(uri),
+        new Position(position.line, position.character),
+      ),
+  );
+  const showOutgoingCalls = commands.registerCommand(
+    'docgraph.showOutgoingCalls',
+    async (uri: string, position: { line: number; character: number }) => {
+      const pos = new Position(position.line, position.character);
+      await window.showTextDocument(Uri.parse(uri), { selection: new Range(pos, pos) });
+      await commands.executeCommand('editor.showOutgoingCalls');
+    },
+  );
+
+  const showProblems = commands.registerCommand(
+    'docgraph.showProblems',
+    async (uri: string, position: { line: number; character: number }) => {
+      const pos = new Position(position.line, position.character);
+      await window.showTextDocument(Uri.parse(uri), { selection: new Range(pos, pos) });
+      await commands.executeCommand('workbench.actions.view.problems');
+    },
+  );
+
+  context.subscriptions.push(restartCommand, showReferences, showOutgoingCalls, showProblems);
 
   startServer(context);
 }